@@ -34,9 +34,13 @@ done
 
 # Publish the pre-built WASM module
 echo "Publishing SpacetimeDB module..."
-spacetime publish "$SPACETIME_DB" --server http://127.0.0.1:3000 -y --bin-path /opt/spacetime-module.wasm --anonymous || {
-    echo "Module may already be published, continuing..."
-}
+# Republishing an existing database migrates it; if that fails (e.g. a schema change
+# the automatic migration can't apply), stop rather than run against the old module
+if ! spacetime publish "$SPACETIME_DB" --server http://127.0.0.1:3000 -y --bin-path /opt/spacetime-module.wasm --anonymous; then
+    echo "Failed to publish the SpacetimeDB module, see the error above" >&2
+    kill $STDB_PID 2>/dev/null
+    exit 1
+fi
 
 # Start the MCP server in background
 echo "Starting MCP server..."
@@ -45,7 +49,7 @@ MCP_PID=$!
 
 # Start the sync daemon (foreground - keeps container running)
 echo "Starting sync daemon..."
-spacenotes \
+spacenotes sync \
     --vault-path "$VAULT_PATH" \
     --spacetime-host "$SPACETIME_HOST" \
     --database "$SPACETIME_DB" &
@@ -18,6 +18,8 @@ pub struct Note {
     pub created_time: u64,
     pub modified_time: u64,
     pub db_updated_at: __sdk::Timestamp,
+    pub version: u64,
+    pub safe_path: String,
 }
 
 impl __sdk::InModule for Note {
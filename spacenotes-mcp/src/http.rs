@@ -64,7 +64,13 @@ async fn handle_request(
 
             match tools::execute_tool(client, params).await {
                 Ok(result) => json!({"jsonrpc": "2.0", "id": request.id, "result": result}),
-                Err(err) => json!({"jsonrpc": "2.0", "id": request.id, "error": {"code": -32603, "message": err}})
+                // Tool failures (including rejected reducer calls) are results with isError,
+                // so the model sees them instead of a protocol error
+                Err(err) => json!({
+                    "jsonrpc": "2.0",
+                    "id": request.id,
+                    "result": {"content": [{"type": "text", "text": err}], "isError": true}
+                })
             }
         }
         _ => json!({"jsonrpc": "2.0", "id": request.id, "error": {"code": -32601, "message": "Method not found"}})
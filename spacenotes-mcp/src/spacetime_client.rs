@@ -1,24 +1,93 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use serde::Serialize;
-use spacetimedb_sdk::{DbContext, Table, TableWithPrimaryKey};
+use spacetimedb_sdk::{DbContext, Status, Table, TableWithPrimaryKey, Timestamp};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
 
 use crate::bindings::{
     append_to_note_reducer::append_to_note,
+    attachment_table::AttachmentTableAccess,
     create_folder_reducer::create_folder,
     create_note_reducer::create_note,
     delete_folder_reducer::delete_folder,
     delete_note_reducer::delete_note,
+    find_replace_in_note_if_version_reducer::find_replace_in_note_if_version,
     find_replace_in_note_reducer::find_replace_in_note,
+    link_rewrite_table::LinkRewriteTableAccess,
     move_folder_reducer::move_folder,
     move_note_reducer::move_note,
+    note_link_table::NoteLinkTableAccess,
+    note_revision_table::NoteRevisionTableAccess,
+    note_tag_table::NoteTagTableAccess,
     note_table::NoteTableAccess,
     prepend_to_note_reducer::prepend_to_note,
     rename_note_reducer::rename_note,
-    update_note_content_reducer::update_note_content,
+    restore_from_trash_reducer::restore_from_trash,
+    restore_note_revision_reducer::restore_note_revision,
+    trash_table::TrashTableAccess,
+    update_note_content_if_version_reducer::update_note_content_if_version,
     DbConnection,
+    ReducerEventContext,
 };
 
+/// How long a tool waits for the module to commit or reject a reducer call
+const REDUCER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Callback half of a reducer call we're waiting on
+///
+/// Registered (via the reducer's `on_*` callback) before the call is sent, and
+/// resolved by the first event that is our own call with matching arguments.
+struct PendingCall(Option<oneshot::Sender<Result<Timestamp, String>>>);
+
+impl PendingCall {
+    fn new() -> (Self, oneshot::Receiver<Result<Timestamp, String>>) {
+        let (tx, rx) = oneshot::channel();
+        (Self(Some(tx)), rx)
+    }
+
+    fn resolve(&mut self, ctx: &ReducerEventContext, args_match: bool) {
+        if !args_match || ctx.event.caller_connection_id != Some(ctx.connection_id()) {
+            return;
+        }
+        if let Some(tx) = self.0.take() {
+            let outcome = match &ctx.event.status {
+                Status::Committed => Ok(ctx.event.timestamp),
+                Status::Failed(err) => Err(err.to_string()),
+                Status::OutOfEnergy => Err("Out of energy".to_string()),
+            };
+            let _ = tx.send(outcome);
+        }
+    }
+}
+
+/// Current time in ms since epoch, the unit of the module's `created_time`/`modified_time`
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+/// Wait for the outcome of a reducer call, turning rejections and timeouts into errors
+async fn await_outcome(reducer: &str, rx: oneshot::Receiver<Result<Timestamp, String>>) -> Result<()> {
+    await_commit(reducer, rx).await.map(|_| ())
+}
+
+/// Like `await_outcome`, returning the time the call was committed at
+async fn await_commit(reducer: &str, rx: oneshot::Receiver<Result<Timestamp, String>>) -> Result<Timestamp> {
+    match tokio::time::timeout(REDUCER_TIMEOUT, rx).await {
+        Ok(Ok(Ok(committed_at))) => Ok(committed_at),
+        Ok(Ok(Err(err))) => Err(anyhow!("{} failed: {}", reducer, err)),
+        Ok(Err(_)) => Err(anyhow!("{} was cancelled: connection closed", reducer)),
+        Err(_) => Err(anyhow!(
+            "Timed out after {}s waiting for {} to complete; it may or may not have been applied",
+            REDUCER_TIMEOUT.as_secs(),
+            reducer
+        )),
+    }
+}
+
 pub struct SpacetimeClient {
     conn: DbConnection,
     synced: Arc<Mutex<bool>>,
@@ -38,7 +107,8 @@ impl SpacetimeClient {
         // Start the background thread
         conn.run_threaded();
 
-        // Subscribe to all notes and folders
+        // Subscribe to all notes, folders, tags, links, link rewrites, revision history, trash
+        // and attachment metadata (not attachment_chunk: the bytes aren't needed here)
         let synced_clone = synced.clone();
         conn.subscription_builder()
             .on_applied(move |_ctx| {
@@ -49,38 +119,71 @@ impl SpacetimeClient {
             .on_error(|_ctx, err| {
                 tracing::error!("SpacetimeDB subscription error: {:?}", err);
             })
-            .subscribe(vec!["SELECT * FROM note", "SELECT * FROM folder"]);
+            .subscribe(vec![
+                "SELECT * FROM note",
+                "SELECT * FROM folder",
+                "SELECT * FROM note_tag",
+                "SELECT * FROM note_link",
+                "SELECT * FROM link_rewrite",
+                "SELECT * FROM note_revision",
+                "SELECT * FROM trash",
+                "SELECT * FROM attachment",
+            ]);
 
         tracing::info!("SpacetimeDB connection established");
 
         Ok(Self { conn, synced })
     }
 
-    pub fn rename_note(&self, id: String, new_path: String) -> Result<()> {
+    pub async fn rename_note(&self, id: String, new_path: String, rewrite_links: bool) -> Result<()> {
         tracing::info!("Renaming note {} to {}", id, new_path);
 
+        let (mut pending, rx) = PendingCall::new();
+        let (expected_id, expected_path) = (id.clone(), new_path.clone());
+        let callback = self.conn.reducers().on_rename_note(move |ctx, id, new_path, _| {
+            pending.resolve(ctx, *id == expected_id && *new_path == expected_path);
+        });
+
         // Call the rename_note reducer
-        self.conn.reducers().rename_note(id, new_path)?;
+        self.conn.reducers().rename_note(id, new_path, rewrite_links)?;
 
-        Ok(())
+        let result = await_outcome("rename_note", rx).await;
+        self.conn.reducers().remove_on_rename_note(callback);
+        result
     }
 
-    pub fn delete_note(&self, id: String) -> Result<()> {
+    pub async fn delete_note(&self, id: String) -> Result<()> {
         tracing::info!("Deleting note {}", id);
 
+        let (mut pending, rx) = PendingCall::new();
+        let expected_id = id.clone();
+        let callback = self.conn.reducers().on_delete_note(move |ctx, id| {
+            pending.resolve(ctx, *id == expected_id);
+        });
+
         // Call the delete_note reducer
         self.conn.reducers().delete_note(id)?;
 
-        Ok(())
+        let result = await_outcome("delete_note", rx).await;
+        self.conn.reducers().remove_on_delete_note(callback);
+        result
     }
 
-    pub fn create_folder(&self, path: String, name: String, depth: u32) -> Result<()> {
+    pub async fn create_folder(&self, path: String, name: String, depth: u32) -> Result<()> {
         tracing::info!("Creating folder {} at depth {}", path, depth);
 
+        let (mut pending, rx) = PendingCall::new();
+        let expected_path = path.clone();
+        let callback = self.conn.reducers().on_create_folder(move |ctx, path, _, _| {
+            pending.resolve(ctx, *path == expected_path);
+        });
+
         // Call the create_folder reducer
         self.conn.reducers().create_folder(path, name, depth)?;
 
-        Ok(())
+        let result = await_outcome("create_folder", rx).await;
+        self.conn.reducers().remove_on_create_folder(callback);
+        result
     }
 
     pub fn list_notes_in_folder(&self, folder_path: &str) -> Result<Vec<NoteInfo>> {
@@ -121,6 +224,7 @@ impl SpacetimeClient {
                 content: note.content.clone(),
                 folder_path: note.folder_path.clone(),
                 frontmatter: note.frontmatter.clone(),
+                version: note.version,
             });
 
         Ok(note)
@@ -142,12 +246,13 @@ impl SpacetimeClient {
                 content: note.content.clone(),
                 folder_path: note.folder_path.clone(),
                 frontmatter: note.frontmatter.clone(),
+                version: note.version,
             });
 
         Ok(note)
     }
 
-    pub fn create_note(
+    pub async fn create_note(
         &self,
         id: String,
         path: String,
@@ -159,10 +264,13 @@ impl SpacetimeClient {
 
         let depth = path.matches('/').count() as u32;
         let size = content.len() as u64;
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        let now = now_millis();
+
+        let (mut pending, rx) = PendingCall::new();
+        let expected_id = id.clone();
+        let callback = self.conn.reducers().on_create_note(move |ctx, id, _, _, _, _, _, _, _, _, _| {
+            pending.resolve(ctx, *id == expected_id);
+        });
 
         self.conn.reducers().create_note(
             id,
@@ -177,71 +285,457 @@ impl SpacetimeClient {
             now,
         )?;
 
-        Ok(())
+        let result = await_outcome("create_note", rx).await;
+        self.conn.reducers().remove_on_create_note(callback);
+        result
     }
 
-    pub fn update_note_content(&self, id: String, content: String) -> Result<()> {
-        tracing::info!("Updating note content: {}", id);
+    /// Replace a note's content, rejected by the server if the note is no longer at `expected_version`
+    pub async fn update_note_content(
+        &self,
+        id: String,
+        content: String,
+        frontmatter: String,
+        expected_version: u64,
+    ) -> Result<()> {
+        tracing::info!("Updating note content: {} (expected version {})", id, expected_version);
 
         let size = content.len() as u64;
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        let now = now_millis();
 
-        self.conn.reducers().update_note_content(
+        let (mut pending, rx) = PendingCall::new();
+        let expected_id = id.clone();
+        let callback = self.conn.reducers().on_update_note_content_if_version(
+            move |ctx, id, _, _, _, _, version| {
+                pending.resolve(ctx, *id == expected_id && *version == expected_version);
+            },
+        );
+
+        self.conn.reducers().update_note_content_if_version(
             id,
             content,
-            String::new(), // frontmatter - keep existing or empty
+            frontmatter,
             size,
             now,
+            expected_version,
         )?;
 
-        Ok(())
+        let result = await_outcome("update_note_content", rx).await;
+        self.conn.reducers().remove_on_update_note_content_if_version(callback);
+        result
     }
 
-    pub fn move_note(&self, old_path: String, new_path: String) -> Result<()> {
+    /// Move a note; returns the paths of the notes whose links the move rewrote
+    pub async fn move_note(&self, old_path: String, new_path: String, rewrite_links: bool) -> Result<Vec<String>> {
         tracing::info!("Moving note from {} to {}", old_path, new_path);
-        self.conn.reducers().move_note(old_path, new_path)?;
-        Ok(())
+        let (mut pending, rx) = PendingCall::new();
+        let (expected_old, expected_new) = (old_path.clone(), new_path.clone());
+        let callback = self.conn.reducers().on_move_note(move |ctx, old_path, new_path, _| {
+            pending.resolve(ctx, *old_path == expected_old && *new_path == expected_new);
+        });
+        self.conn.reducers().move_note(old_path, new_path, rewrite_links)?;
+        let result = await_commit("move_note", rx).await;
+        self.conn.reducers().remove_on_move_note(callback);
+        Ok(self.rewritten_by_call(result?))
     }
 
-    pub fn move_folder(&self, old_path: String, new_path: String) -> Result<()> {
+    /// Move a folder; returns the paths of the notes whose links the move rewrote
+    pub async fn move_folder(&self, old_path: String, new_path: String, rewrite_links: bool) -> Result<Vec<String>> {
         tracing::info!("Moving folder from {} to {}", old_path, new_path);
-        self.conn.reducers().move_folder(old_path, new_path)?;
-        Ok(())
+        let (mut pending, rx) = PendingCall::new();
+        let (expected_old, expected_new) = (old_path.clone(), new_path.clone());
+        let callback = self.conn.reducers().on_move_folder(move |ctx, old_path, new_path, _| {
+            pending.resolve(ctx, *old_path == expected_old && *new_path == expected_new);
+        });
+        self.conn.reducers().move_folder(old_path, new_path, rewrite_links)?;
+        let result = await_commit("move_folder", rx).await;
+        self.conn.reducers().remove_on_move_folder(callback);
+        Ok(self.rewritten_by_call(result?))
     }
 
-    pub fn delete_folder(&self, path: String) -> Result<()> {
+    /// Notes whose links our call committed at `committed_at` rewrote (from `link_rewrite`)
+    fn rewritten_by_call(&self, committed_at: Timestamp) -> Vec<String> {
+        let me = self.conn.identity();
+        let mut paths: Vec<String> = self
+            .conn
+            .db()
+            .link_rewrite()
+            .iter()
+            .filter(|r| r.caller == me && r.rewritten_at == committed_at)
+            .map(|r| r.note_path)
+            .collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+
+    pub async fn delete_folder(&self, path: String) -> Result<()> {
         tracing::info!("Deleting folder: {}", path);
+        let (mut pending, rx) = PendingCall::new();
+        let expected_path = path.clone();
+        let callback = self.conn.reducers().on_delete_folder(move |ctx, path| {
+            pending.resolve(ctx, *path == expected_path);
+        });
         self.conn.reducers().delete_folder(path)?;
-        Ok(())
+        let result = await_outcome("delete_folder", rx).await;
+        self.conn.reducers().remove_on_delete_folder(callback);
+        result
     }
 
-    pub fn append_to_note(&self, path: String, content: String) -> Result<()> {
+    pub async fn append_to_note(&self, path: String, content: String) -> Result<()> {
         tracing::info!("Appending to note: {}", path);
+        let (mut pending, rx) = PendingCall::new();
+        let (expected_path, expected_content) = (path.clone(), content.clone());
+        let callback = self.conn.reducers().on_append_to_note(move |ctx, path, content| {
+            pending.resolve(ctx, *path == expected_path && *content == expected_content);
+        });
         self.conn.reducers().append_to_note(path, content)?;
-        Ok(())
+        let result = await_outcome("append_to_note", rx).await;
+        self.conn.reducers().remove_on_append_to_note(callback);
+        result
     }
 
-    pub fn prepend_to_note(&self, path: String, content: String) -> Result<()> {
+    pub async fn prepend_to_note(&self, path: String, content: String) -> Result<()> {
         tracing::info!("Prepending to note: {}", path);
+        let (mut pending, rx) = PendingCall::new();
+        let (expected_path, expected_content) = (path.clone(), content.clone());
+        let callback = self.conn.reducers().on_prepend_to_note(move |ctx, path, content| {
+            pending.resolve(ctx, *path == expected_path && *content == expected_content);
+        });
         self.conn.reducers().prepend_to_note(path, content)?;
-        Ok(())
+        let result = await_outcome("prepend_to_note", rx).await;
+        self.conn.reducers().remove_on_prepend_to_note(callback);
+        result
     }
 
-    pub fn find_replace_in_note(
+    pub async fn find_replace_in_note(
         &self,
         path: String,
         old_text: String,
         new_text: String,
         replace_all: bool,
+        expected_version: Option<u64>,
     ) -> Result<()> {
         tracing::info!("Find/replace in note: {}", path);
-        self.conn
-            .reducers()
-            .find_replace_in_note(path, old_text, new_text, replace_all)?;
-        Ok(())
+        let (mut pending, rx) = PendingCall::new();
+        let (expected_path, expected_old) = (path.clone(), old_text.clone());
+        match expected_version {
+            Some(version) => {
+                let callback = self.conn.reducers().on_find_replace_in_note_if_version(
+                    move |ctx, path, old_text, _, _, _| {
+                        pending.resolve(ctx, *path == expected_path && *old_text == expected_old);
+                    },
+                );
+                self.conn
+                    .reducers()
+                    .find_replace_in_note_if_version(path, old_text, new_text, replace_all, version)?;
+                let result = await_outcome("find_replace_in_note", rx).await;
+                self.conn.reducers().remove_on_find_replace_in_note_if_version(callback);
+                result
+            }
+            None => {
+                let callback = self.conn.reducers().on_find_replace_in_note(
+                    move |ctx, path, old_text, _, _| {
+                        pending.resolve(ctx, *path == expected_path && *old_text == expected_old);
+                    },
+                );
+                self.conn
+                    .reducers()
+                    .find_replace_in_note(path, old_text, new_text, replace_all)?;
+                let result = await_outcome("find_replace_in_note", rx).await;
+                self.conn.reducers().remove_on_find_replace_in_note(callback);
+                result
+            }
+        }
+    }
+
+    pub fn list_attachments(&self, folder_path: Option<&str>) -> Result<Vec<AttachmentInfo>> {
+        tracing::info!("Listing attachments in: {:?}", folder_path);
+
+        let mut attachments: Vec<AttachmentInfo> = self
+            .conn
+            .db()
+            .attachment()
+            .iter()
+            .filter(|a| folder_path.is_none_or(|folder| a.folder_path.starts_with(folder)))
+            .map(|a| AttachmentInfo {
+                path: a.path,
+                mime_type: a.mime_type,
+                size: a.size,
+                modified_time: a.modified_time,
+            })
+            .collect();
+
+        attachments.sort_by(|a, b| a.path.cmp(&b.path));
+
+        tracing::info!("Found {} attachments", attachments.len());
+
+        Ok(attachments)
+    }
+
+    pub fn list_tags(&self) -> Result<Vec<TagInfo>> {
+        tracing::info!("Listing tags");
+
+        let mut counts: std::collections::BTreeMap<String, usize> = std::collections::BTreeMap::new();
+        for note_tag in self.conn.db().note_tag().iter() {
+            *counts.entry(note_tag.tag).or_default() += 1;
+        }
+
+        let mut tags: Vec<TagInfo> = counts
+            .into_iter()
+            .map(|(tag, note_count)| TagInfo { tag, note_count })
+            .collect();
+
+        // Most used first, then alphabetical
+        tags.sort_by(|a, b| b.note_count.cmp(&a.note_count).then_with(|| a.tag.cmp(&b.tag)));
+
+        tracing::info!("Found {} tags", tags.len());
+
+        Ok(tags)
+    }
+
+    pub fn find_notes_by_tag(&self, tag: &str) -> Result<Vec<NoteInfo>> {
+        tracing::info!("Finding notes tagged: {}", tag);
+
+        // Tags are indexed lowercased and without the leading '#'
+        let tag = tag.trim().trim_start_matches('#').to_lowercase();
+
+        let note_ids: std::collections::HashSet<String> = self
+            .conn
+            .db()
+            .note_tag()
+            .iter()
+            .filter(|note_tag| note_tag.tag == tag)
+            .map(|note_tag| note_tag.note_id)
+            .collect();
+
+        let mut notes: Vec<NoteInfo> = note_ids
+            .iter()
+            .filter_map(|id| self.conn.db().note().id().find(id))
+            .map(|note| NoteInfo {
+                id: note.id.clone(),
+                path: note.path.clone(),
+                name: note.name.clone(),
+            })
+            .collect();
+
+        notes.sort_by(|a, b| a.path.cmp(&b.path));
+
+        tracing::info!("Found {} notes tagged '{}'", notes.len(), tag);
+
+        Ok(notes)
+    }
+
+    /// Notes that link to the given note
+    pub fn get_backlinks(&self, note_id: &str) -> Result<Vec<LinkInfo>> {
+        tracing::info!("Getting backlinks for note: {}", note_id);
+
+        let links: Vec<LinkInfo> = self
+            .conn
+            .db()
+            .note_link()
+            .iter()
+            .filter(|link| link.target_id == note_id)
+            .filter_map(|link| {
+                let source = self.conn.db().note().id().find(&link.source_id)?;
+                Some(LinkInfo {
+                    source_path: source.path,
+                    target_path: link.target_path,
+                    target_id: link.target_id,
+                    kind: link.kind,
+                    anchor: link.anchor,
+                })
+            })
+            .collect();
+
+        tracing::info!("Found {} backlinks for note {}", links.len(), note_id);
+
+        Ok(links)
+    }
+
+    /// Links written in the given note, resolved or not
+    pub fn get_outgoing_links(&self, note_id: &str) -> Result<Vec<LinkInfo>> {
+        tracing::info!("Getting outgoing links for note: {}", note_id);
+
+        let Some(source) = self.conn.db().note().id().find(&note_id.to_string()) else {
+            return Ok(Vec::new());
+        };
+
+        let links: Vec<LinkInfo> = self
+            .conn
+            .db()
+            .note_link()
+            .iter()
+            .filter(|link| link.source_id == note_id)
+            .map(|link| LinkInfo {
+                source_path: source.path.clone(),
+                target_path: link.target_path,
+                target_id: link.target_id,
+                kind: link.kind,
+                anchor: link.anchor,
+            })
+            .collect();
+
+        tracing::info!("Found {} outgoing links for note {}", links.len(), note_id);
+
+        Ok(links)
+    }
+
+    /// Notes linking to the note at `path`, or to any note under it if it is a folder
+    ///
+    /// Taken before a move that doesn't rewrite links, to report what still points at
+    /// the old location.
+    pub fn get_linking_notes(&self, path: &str) -> Result<Vec<LinkingNote>> {
+        let folder_prefix = format!("{}/", path.trim_end_matches('/'));
+
+        let target_ids: std::collections::HashSet<String> = self
+            .conn
+            .db()
+            .note()
+            .iter()
+            .filter(|note| note.path == path || note.path.starts_with(&folder_prefix))
+            .map(|note| note.id)
+            .collect();
+
+        let mut sources: Vec<LinkingNote> = self
+            .conn
+            .db()
+            .note_link()
+            .iter()
+            .filter(|link| target_ids.contains(&link.target_id) && !target_ids.contains(&link.source_id))
+            .filter_map(|link| self.conn.db().note().id().find(&link.source_id))
+            .map(|note| LinkingNote { id: note.id, path: note.path })
+            .collect();
+
+        sources.sort_by(|a, b| a.path.cmp(&b.path));
+        sources.dedup_by(|a, b| a.id == b.id);
+
+        Ok(sources)
+    }
+
+    /// Links across the vault whose target note doesn't exist
+    pub fn list_unresolved_links(&self) -> Result<Vec<LinkInfo>> {
+        tracing::info!("Listing unresolved links");
+
+        let mut links: Vec<LinkInfo> = self
+            .conn
+            .db()
+            .note_link()
+            .iter()
+            .filter(|link| link.target_id.is_empty())
+            .filter_map(|link| {
+                let source = self.conn.db().note().id().find(&link.source_id)?;
+                Some(LinkInfo {
+                    source_path: source.path,
+                    target_path: link.target_path,
+                    target_id: link.target_id,
+                    kind: link.kind,
+                    anchor: link.anchor,
+                })
+            })
+            .collect();
+
+        links.sort_by(|a, b| a.source_path.cmp(&b.source_path));
+
+        tracing::info!("Found {} unresolved links", links.len());
+
+        Ok(links)
+    }
+
+    pub fn list_note_revisions(&self, note_id: &str) -> Result<Vec<RevisionInfo>> {
+        tracing::info!("Listing revisions for note: {}", note_id);
+
+        let mut revisions: Vec<RevisionInfo> = self
+            .conn
+            .db()
+            .note_revision()
+            .iter()
+            .filter(|revision| revision.note_id == note_id)
+            .map(|revision| RevisionInfo {
+                revision_id: revision.id,
+                path: revision.path.clone(),
+                size: revision.size,
+                modified_time: revision.modified_time,
+                captured_at_micros: revision.captured_at.to_micros_since_unix_epoch(),
+            })
+            .collect();
+
+        // Newest first
+        revisions.sort_by_key(|r| std::cmp::Reverse(r.captured_at_micros));
+
+        tracing::info!("Found {} revisions for note {}", revisions.len(), note_id);
+
+        Ok(revisions)
+    }
+
+    pub fn get_note_revision_content(&self, revision_id: u64) -> Result<Option<String>> {
+        tracing::info!("Getting revision content: {}", revision_id);
+
+        let content = self
+            .conn
+            .db()
+            .note_revision()
+            .id()
+            .find(&revision_id)
+            .map(|revision| revision.content);
+
+        Ok(content)
+    }
+
+    pub async fn restore_note_revision(&self, revision_id: u64) -> Result<()> {
+        tracing::info!("Restoring note revision {}", revision_id);
+        let (mut pending, rx) = PendingCall::new();
+        let callback = self.conn.reducers().on_restore_note_revision(move |ctx, id| {
+            pending.resolve(ctx, *id == revision_id);
+        });
+        self.conn.reducers().restore_note_revision(revision_id)?;
+        let result = await_outcome("restore_note_revision", rx).await;
+        self.conn.reducers().remove_on_restore_note_revision(callback);
+        result
+    }
+
+    pub fn list_trash(&self) -> Result<Vec<TrashInfo>> {
+        tracing::info!("Listing trash");
+
+        let mut entries: Vec<TrashInfo> = self
+            .conn
+            .db()
+            .trash()
+            .iter()
+            .map(|entry| TrashInfo {
+                trash_id: entry.id,
+                kind: if entry.folder.is_some() {
+                    "folder"
+                } else if entry.attachment.is_some() {
+                    "attachment"
+                } else {
+                    "note"
+                }
+                .to_string(),
+                original_path: entry.original_path.clone(),
+                deleted_with_folder: entry.cascade_root.clone(),
+                deleted_at_micros: entry.deleted_at.to_micros_since_unix_epoch(),
+            })
+            .collect();
+
+        // Most recently deleted first
+        entries.sort_by_key(|e| std::cmp::Reverse(e.deleted_at_micros));
+
+        tracing::info!("Found {} trash entries", entries.len());
+
+        Ok(entries)
+    }
+
+    pub async fn restore_from_trash(&self, trash_id: u64) -> Result<()> {
+        tracing::info!("Restoring trash entry {}", trash_id);
+        let (mut pending, rx) = PendingCall::new();
+        let callback = self.conn.reducers().on_restore_from_trash(move |ctx, id| {
+            pending.resolve(ctx, *id == trash_id);
+        });
+        self.conn.reducers().restore_from_trash(trash_id)?;
+        let result = await_outcome("restore_from_trash", rx).await;
+        self.conn.reducers().remove_on_restore_from_trash(callback);
+        result
     }
 
     pub fn search_notes(&self, query: &str) -> Result<Vec<NoteInfo>> {
@@ -304,6 +798,54 @@ pub struct NoteInfo {
     pub name: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct AttachmentInfo {
+    pub path: String,
+    pub mime_type: String,
+    pub size: u64,
+    pub modified_time: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TagInfo {
+    pub tag: String,
+    pub note_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkInfo {
+    pub source_path: String,
+    pub target_path: String,
+    pub target_id: String,
+    pub kind: String,
+    pub anchor: String,
+}
+
+/// Note linking into a path that is about to move
+#[derive(Debug, Clone)]
+pub struct LinkingNote {
+    pub id: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RevisionInfo {
+    pub revision_id: u64,
+    pub path: String,
+    pub size: u64,
+    pub modified_time: u64,
+    pub captured_at_micros: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TrashInfo {
+    pub trash_id: u64,
+    pub kind: String,
+    pub original_path: String,
+    pub deleted_with_folder: String,
+    pub deleted_at_micros: i64,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct FullNote {
     pub id: String,
@@ -312,4 +854,5 @@ pub struct FullNote {
     pub content: String,
     pub folder_path: String,
     pub frontmatter: String,
+    pub version: u64,
 }
@@ -71,7 +71,7 @@ pub fn get_tools() -> Vec<Tool> {
         },
         Tool {
             name: "delete_note".to_string(),
-            description: "Delete a note by ID".to_string(),
+            description: "Delete a note by ID (moves it to the trash, see restore_from_trash)".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -82,7 +82,7 @@ pub fn get_tools() -> Vec<Tool> {
         },
         Tool {
             name: "delete_notes".to_string(),
-            description: "Delete multiple notes by ID in a single operation".to_string(),
+            description: "Delete multiple notes by ID in a single operation (moves them to the trash)".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -102,7 +102,8 @@ pub fn get_tools() -> Vec<Tool> {
                 "type": "object",
                 "properties": {
                     "old_path": {"type": "string", "description": "Current path"},
-                    "new_path": {"type": "string", "description": "New path"}
+                    "new_path": {"type": "string", "description": "New path"},
+                    "rewrite_links": {"type": "boolean", "description": "Update [[links]] and (links.md) in other notes that point here, and relative links in the moved notes (default: false, links are left as they are)"}
                 },
                 "required": ["old_path", "new_path"]
             }),
@@ -114,7 +115,8 @@ pub fn get_tools() -> Vec<Tool> {
                 "type": "object",
                 "properties": {
                     "old_path": {"type": "string", "description": "Current folder path"},
-                    "new_path": {"type": "string", "description": "New folder path"}
+                    "new_path": {"type": "string", "description": "New folder path"},
+                    "rewrite_links": {"type": "boolean", "description": "Update [[links]] and (links.md) in other notes that point here, and relative links in the moved notes (default: false, links are left as they are)"}
                 },
                 "required": ["old_path", "new_path"]
             }),
@@ -132,7 +134,7 @@ pub fn get_tools() -> Vec<Tool> {
         },
         Tool {
             name: "delete_folder".to_string(),
-            description: "Delete an empty folder".to_string(),
+            description: "Delete a folder and everything in it (moves them to the trash)".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -174,7 +176,8 @@ pub fn get_tools() -> Vec<Tool> {
                     "path": {"type": "string", "description": "Note path (e.g., 'Development/My Note.md')"},
                     "old_string": {"type": "string", "description": "Text to find (must match exactly)"},
                     "new_string": {"type": "string", "description": "Text to replace with (empty to delete)"},
-                    "replace_all": {"type": "boolean", "description": "Replace all occurrences (default: false, replaces first only)"}
+                    "replace_all": {"type": "boolean", "description": "Replace all occurrences (default: false, replaces first only)"},
+                    "expected_version": {"type": "integer", "description": "Version from get_note; the edit is rejected if the note changed since (optional)"}
                 },
                 "required": ["path", "old_string"]
             }),
@@ -193,7 +196,8 @@ pub fn get_tools() -> Vec<Tool> {
                     "destination_folder": {
                         "type": "string",
                         "description": "Destination folder (e.g., 'Development/SpaceNotes/')"
-                    }
+                    },
+                    "rewrite_links": {"type": "boolean", "description": "Update [[links]] and (links.md) in other notes that point here, and relative links in the moved notes (default: false, links are left as they are)"}
                 },
                 "required": ["paths", "destination_folder"]
             }),
@@ -208,11 +212,123 @@ pub fn get_tools() -> Vec<Tool> {
                     "pattern": {"type": "string", "description": "Regex pattern (e.g., '\\n\\n+' for multiple newlines)"},
                     "replacement": {"type": "string", "description": "Replacement string (supports $1, $2 for capture groups)"},
                     "case_insensitive": {"type": "boolean", "description": "Case-insensitive matching (default: false)"},
-                    "multiline": {"type": "boolean", "description": "Multiline mode: ^ and $ match line boundaries (default: false)"}
+                    "multiline": {"type": "boolean", "description": "Multiline mode: ^ and $ match line boundaries (default: false)"},
+                    "expected_version": {"type": "integer", "description": "Version from get_note; the edit is rejected if the note changed since (default: the version the regex ran against)"}
                 },
                 "required": ["path", "pattern", "replacement"]
             }),
         },
+        Tool {
+            name: "list_attachments".to_string(),
+            description: "List images, PDFs and other non-markdown files synced from the vault".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "folder_path": {"type": "string", "description": "Only list attachments under this folder (e.g., 'Projects/')"}
+                }
+            }),
+        },
+        Tool {
+            name: "list_tags".to_string(),
+            description: "List all tags (from frontmatter tags and inline #tags) with how many notes use each".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {}
+            }),
+        },
+        Tool {
+            name: "find_notes_by_tag".to_string(),
+            description: "List all notes with a given tag".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "tag": {"type": "string", "description": "Tag name, with or without leading '#' (case-insensitive)"}
+                },
+                "required": ["tag"]
+            }),
+        },
+        Tool {
+            name: "get_backlinks".to_string(),
+            description: "List the notes that link to a note via [[wikilinks]] or [markdown](links.md)".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "id": {"type": "string", "description": "Note UUID (optional if path provided)"},
+                    "path": {"type": "string", "description": "Note path (optional if id provided)"}
+                }
+            }),
+        },
+        Tool {
+            name: "get_outgoing_links".to_string(),
+            description: "List the links written in a note and which notes they resolve to (empty target_id = broken link)".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "id": {"type": "string", "description": "Note UUID (optional if path provided)"},
+                    "path": {"type": "string", "description": "Note path (optional if id provided)"}
+                }
+            }),
+        },
+        Tool {
+            name: "list_unresolved_links".to_string(),
+            description: "List all links in the vault that point to notes which don't exist".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {}
+            }),
+        },
+        Tool {
+            name: "list_note_revisions".to_string(),
+            description: "List previous versions of a note, newest first. Use before restore_note_revision.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "id": {"type": "string", "description": "Note UUID (optional if path provided)"},
+                    "path": {"type": "string", "description": "Note path (optional if id provided)"}
+                }
+            }),
+        },
+        Tool {
+            name: "get_note_revision".to_string(),
+            description: "Get the full content of a previous version of a note".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "revision_id": {"type": "integer", "description": "Revision ID from list_note_revisions"}
+                },
+                "required": ["revision_id"]
+            }),
+        },
+        Tool {
+            name: "restore_note_revision".to_string(),
+            description: "Restore a note's content to a previous version. The current content is kept as a new revision. The note keeps its current path; renames and moves are listed too, with the path the note had before.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "revision_id": {"type": "integer", "description": "Revision ID from list_note_revisions"}
+                },
+                "required": ["revision_id"]
+            }),
+        },
+        Tool {
+            name: "list_trash".to_string(),
+            description: "List deleted notes and folders that can still be restored, most recent first".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {}
+            }),
+        },
+        Tool {
+            name: "restore_from_trash".to_string(),
+            description: "Restore a deleted note or folder. Restoring a folder also restores the notes and subfolders deleted with it.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "trash_id": {"type": "integer", "description": "Trash entry ID from list_trash"}
+                },
+                "required": ["trash_id"]
+            }),
+        },
     ]
 }
 
@@ -268,8 +384,8 @@ pub async fn execute_tool(
                         .join("\n");
 
                     let text = format!(
-                        "id: {}\npath: {}\nname: {}\nfolder_path: {}\nfrontmatter: {}\n\n{}",
-                        n.id, n.path, n.name, n.folder_path, n.frontmatter, numbered_content
+                        "id: {}\npath: {}\nname: {}\nfolder_path: {}\nversion: {}\nfrontmatter: {}\n\n{}",
+                        n.id, n.path, n.name, n.folder_path, n.version, n.frontmatter, numbered_content
                     );
                     Ok(json!({"content": [{"type": "text", "text": text}]}))
                 },
@@ -302,7 +418,7 @@ pub async fn execute_tool(
             let id = uuid::Uuid::new_v4().to_string();
 
             client
-                .create_note(id.clone(), path.clone(), name, content, folder_path)
+                .create_note(id.clone(), path.clone(), name, content, folder_path).await
                 .map_err(|e| e.to_string())?;
 
             Ok(
@@ -313,7 +429,7 @@ pub async fn execute_tool(
             let id: String = serde_json::from_value(params.arguments["id"].clone())
                 .map_err(|e| e.to_string())?;
 
-            client.delete_note(id.clone()).map_err(|e| e.to_string())?;
+            client.delete_note(id.clone()).await.map_err(|e| e.to_string())?;
 
             Ok(json!({"content": [{"type": "text", "text": format!("Deleted note: {}", id)}]}))
         }
@@ -325,7 +441,7 @@ pub async fn execute_tool(
             let mut errors = Vec::new();
 
             for id in ids {
-                match client.delete_note(id.clone()) {
+                match client.delete_note(id.clone()).await {
                     Ok(_) => deleted.push(id),
                     Err(e) => errors.push(format!("{}: {}", id, e)),
                 }
@@ -333,7 +449,9 @@ pub async fn execute_tool(
 
             let mut result = format!("Deleted {} notes", deleted.len());
             if !errors.is_empty() {
+                // Partial failures are still failures: report them as a tool error
                 result.push_str(&format!("\nErrors: {:?}", errors));
+                return Err(result);
             }
 
             Ok(json!({"content": [{"type": "text", "text": result}]}))
@@ -344,13 +462,20 @@ pub async fn execute_tool(
             let new_path: String = serde_json::from_value(params.arguments["new_path"].clone())
                 .map_err(|e| e.to_string())?;
 
-            client
-                .move_note(old_path.clone(), new_path.clone())
+            let rewrite_links = params.arguments.get("rewrite_links")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
+            let linking = client.get_linking_notes(&old_path).map_err(|e| e.to_string())?;
+
+            let rewritten = client
+                .move_note(old_path.clone(), new_path.clone(), rewrite_links).await
                 .map_err(|e| e.to_string())?;
 
-            Ok(
-                json!({"content": [{"type": "text", "text": format!("Moved note from {} to {}", old_path, new_path)}]}),
-            )
+            let mut result = format!("Moved note from {} to {}", old_path, new_path);
+            result.push_str(&link_report(&rewritten, &linking, rewrite_links));
+
+            Ok(json!({"content": [{"type": "text", "text": result}]}))
         }
         "move_folder" => {
             let old_path: String = serde_json::from_value(params.arguments["old_path"].clone())
@@ -358,13 +483,20 @@ pub async fn execute_tool(
             let new_path: String = serde_json::from_value(params.arguments["new_path"].clone())
                 .map_err(|e| e.to_string())?;
 
-            client
-                .move_folder(old_path.clone(), new_path.clone())
+            let rewrite_links = params.arguments.get("rewrite_links")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
+            let linking = client.get_linking_notes(&old_path).map_err(|e| e.to_string())?;
+
+            let rewritten = client
+                .move_folder(old_path.clone(), new_path.clone(), rewrite_links).await
                 .map_err(|e| e.to_string())?;
 
-            Ok(
-                json!({"content": [{"type": "text", "text": format!("Moved folder from {} to {}", old_path, new_path)}]}),
-            )
+            let mut result = format!("Moved folder from {} to {}", old_path, new_path);
+            result.push_str(&link_report(&rewritten, &linking, rewrite_links));
+
+            Ok(json!({"content": [{"type": "text", "text": result}]}))
         }
         "create_folder" => {
             let path: String = serde_json::from_value(params.arguments["path"].clone())
@@ -378,7 +510,7 @@ pub async fn execute_tool(
             let depth = path.matches('/').count() as u32;
 
             client
-                .create_folder(path.clone(), name, depth)
+                .create_folder(path.clone(), name, depth).await
                 .map_err(|e| e.to_string())?;
 
             Ok(json!({"content": [{"type": "text", "text": format!("Created folder: {}", path)}]}))
@@ -388,7 +520,7 @@ pub async fn execute_tool(
                 .map_err(|e| e.to_string())?;
 
             client
-                .delete_folder(path.clone())
+                .delete_folder(path.clone()).await
                 .map_err(|e| e.to_string())?;
 
             Ok(json!({"content": [{"type": "text", "text": format!("Deleted folder: {}", path)}]}))
@@ -400,7 +532,7 @@ pub async fn execute_tool(
                 .map_err(|e| e.to_string())?;
 
             client
-                .append_to_note(path.clone(), content)
+                .append_to_note(path.clone(), content).await
                 .map_err(|e| e.to_string())?;
 
             Ok(json!({"content": [{"type": "text", "text": format!("Appended to note: {}", path)}]}))
@@ -412,7 +544,7 @@ pub async fn execute_tool(
                 .map_err(|e| e.to_string())?;
 
             client
-                .prepend_to_note(path.clone(), content)
+                .prepend_to_note(path.clone(), content).await
                 .map_err(|e| e.to_string())?;
 
             Ok(json!({"content": [{"type": "text", "text": format!("Prepended to note: {}", path)}]}))
@@ -435,7 +567,9 @@ pub async fn execute_tool(
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false);
 
-            client.find_replace_in_note(path.clone(), old_string, new_string, replace_all)
+            let expected_version = params.arguments.get("expected_version").and_then(|v| v.as_u64());
+
+            client.find_replace_in_note(path.clone(), old_string, new_string, replace_all, expected_version).await
                 .map_err(|e| e.to_string())?;
 
             Ok(json!({"content": [{"type": "text", "text": format!("Edited note: {}", path)}]}))
@@ -453,23 +587,44 @@ pub async fn execute_tool(
                 format!("{}/", destination_folder)
             };
 
+            let rewrite_links = params.arguments.get("rewrite_links")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
             let mut moved = Vec::new();
             let mut errors = Vec::new();
+            let mut linking = Vec::new();
+            let mut rewritten = Vec::new();
 
             for old_path in paths {
                 // Extract filename from old path
                 let filename = old_path.split('/').last().unwrap_or(&old_path);
                 let new_path = format!("{}{}", dest, filename);
 
-                match client.move_note(old_path.clone(), new_path.clone()) {
-                    Ok(_) => moved.push(format!("{} -> {}", old_path, new_path)),
+                linking.extend(client.get_linking_notes(&old_path).map_err(|e| e.to_string())?);
+
+                match client.move_note(old_path.clone(), new_path.clone(), rewrite_links).await {
+                    Ok(paths) => {
+                        moved.push(format!("{} -> {}", old_path, new_path));
+                        rewritten.extend(paths);
+                    }
                     Err(e) => errors.push(format!("{}: {}", old_path, e)),
                 }
             }
 
+            // A note linking to several of the moved notes was captured once per target
+            linking.sort_by(|a, b| a.id.cmp(&b.id));
+            linking.dedup_by(|a, b| a.id == b.id);
+            // A note may have been rewritten by several of the moves (and may have moved since)
+            rewritten.sort();
+            rewritten.dedup();
+
             let mut result = format!("Moved {} notes to {}", moved.len(), dest);
+            result.push_str(&link_report(&rewritten, &linking, rewrite_links));
             if !errors.is_empty() {
+                // Partial failures are still failures: report them as a tool error
                 result.push_str(&format!("\nErrors: {:?}", errors));
+                return Err(result);
             }
 
             Ok(json!({"content": [{"type": "text", "text": result}]}))
@@ -506,12 +661,171 @@ pub async fn execute_tool(
 
             let match_count = re.find_iter(&current_note.content).count();
 
+            // The replacement was computed on this snapshot: don't apply it over newer edits
+            let expected_version = params.arguments.get("expected_version")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(current_note.version);
+
             client
-                .update_note_content(current_note.id, new_content.clone())
+                .update_note_content(current_note.id, new_content.clone(), current_note.frontmatter, expected_version).await
                 .map_err(|e| e.to_string())?;
 
             Ok(json!({"content": [{"type": "text", "text": format!("Replaced {} matches in {}\n\n---\n\n{}", match_count, path, new_content)}]}))
         }
+        "list_attachments" => {
+            let folder_path = params.arguments.get("folder_path").and_then(|v| v.as_str());
+
+            let attachments = client.list_attachments(folder_path).map_err(|e| e.to_string())?;
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": serde_json::to_string_pretty(&attachments).unwrap_or_else(|_| "[]".to_string())
+                }]
+            }))
+        }
+        "list_tags" => {
+            let tags = client.list_tags().map_err(|e| e.to_string())?;
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": serde_json::to_string_pretty(&tags).unwrap_or_else(|_| "[]".to_string())
+                }]
+            }))
+        }
+        "find_notes_by_tag" => {
+            let tag: String = serde_json::from_value(params.arguments["tag"].clone())
+                .map_err(|e| e.to_string())?;
+
+            let notes = client.find_notes_by_tag(&tag).map_err(|e| e.to_string())?;
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": serde_json::to_string_pretty(&notes).unwrap_or_else(|_| "[]".to_string())
+                }]
+            }))
+        }
+        "get_backlinks" | "get_outgoing_links" => {
+            let note = if let Some(id) = params.arguments.get("id").and_then(|v| v.as_str()) {
+                client.get_note_by_id(id).map_err(|e| e.to_string())?
+            } else if let Some(path) = params.arguments.get("path").and_then(|v| v.as_str()) {
+                client.get_note_by_path(path).map_err(|e| e.to_string())?
+            } else {
+                return Err("Must provide either 'id' or 'path'".to_string());
+            };
+
+            let note = note.ok_or_else(|| "Note not found".to_string())?;
+
+            let links = if params.name == "get_backlinks" {
+                client.get_backlinks(&note.id)
+            } else {
+                client.get_outgoing_links(&note.id)
+            }
+            .map_err(|e| e.to_string())?;
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": serde_json::to_string_pretty(&links).unwrap_or_else(|_| "[]".to_string())
+                }]
+            }))
+        }
+        "list_unresolved_links" => {
+            let links = client.list_unresolved_links().map_err(|e| e.to_string())?;
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": serde_json::to_string_pretty(&links).unwrap_or_else(|_| "[]".to_string())
+                }]
+            }))
+        }
+        "list_note_revisions" => {
+            let note = if let Some(id) = params.arguments.get("id").and_then(|v| v.as_str()) {
+                client.get_note_by_id(id).map_err(|e| e.to_string())?
+            } else if let Some(path) = params.arguments.get("path").and_then(|v| v.as_str()) {
+                client.get_note_by_path(path).map_err(|e| e.to_string())?
+            } else {
+                return Err("Must provide either 'id' or 'path'".to_string());
+            };
+
+            let note = note.ok_or_else(|| "Note not found".to_string())?;
+
+            let revisions = client
+                .list_note_revisions(&note.id)
+                .map_err(|e| e.to_string())?;
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": serde_json::to_string_pretty(&revisions).unwrap_or_else(|_| "[]".to_string())
+                }]
+            }))
+        }
+        "get_note_revision" => {
+            let revision_id: u64 = serde_json::from_value(params.arguments["revision_id"].clone())
+                .map_err(|e| e.to_string())?;
+
+            let content = client
+                .get_note_revision_content(revision_id)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Revision not found: {}", revision_id))?;
+
+            Ok(json!({"content": [{"type": "text", "text": content}]}))
+        }
+        "restore_note_revision" => {
+            let revision_id: u64 = serde_json::from_value(params.arguments["revision_id"].clone())
+                .map_err(|e| e.to_string())?;
+
+            client
+                .restore_note_revision(revision_id).await
+                .map_err(|e| e.to_string())?;
+
+            Ok(json!({"content": [{"type": "text", "text": format!("Restored revision {}", revision_id)}]}))
+        }
+        "list_trash" => {
+            let entries = client.list_trash().map_err(|e| e.to_string())?;
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": serde_json::to_string_pretty(&entries).unwrap_or_else(|_| "[]".to_string())
+                }]
+            }))
+        }
+        "restore_from_trash" => {
+            let trash_id: u64 = serde_json::from_value(params.arguments["trash_id"].clone())
+                .map_err(|e| e.to_string())?;
+
+            client
+                .restore_from_trash(trash_id).await
+                .map_err(|e| e.to_string())?;
+
+            Ok(json!({"content": [{"type": "text", "text": format!("Restored trash entry {}", trash_id)}]}))
+        }
         _ => Err(format!("Unknown tool: {}", params.name)),
     }
 }
+
+/// Summary line for moves: which notes the move rewrote links in, or which still point
+/// at the old location
+fn link_report(
+    rewritten: &[String],
+    linking: &[crate::spacetime_client::LinkingNote],
+    rewrite_links: bool,
+) -> String {
+    if rewrite_links {
+        if rewritten.is_empty() {
+            return String::new();
+        }
+        format!("\nRewrote links in {} notes: {}", rewritten.len(), rewritten.join(", "))
+    } else {
+        if linking.is_empty() {
+            return String::new();
+        }
+        let paths: Vec<&str> = linking.iter().map(|note| note.path.as_str()).collect();
+        format!("\nLinks not rewritten; {} notes still point at the old location: {}", paths.len(), paths.join(", "))
+    }
+}
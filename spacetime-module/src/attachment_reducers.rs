@@ -0,0 +1,253 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::{Attachment, AttachmentChunk, ChunkUpload, attachment, attachment_chunk, chunk_upload, trash};
+use crate::link_index::refresh_attachment_links;
+use crate::trash_reducers::{deleted_directly, purge_expired_trash, trash_attachment};
+
+/// Chunks of an upload not finished within a day (e.g. the file was deleted meanwhile) are dropped
+const ABANDONED_UPLOAD_MICROS: i64 = 24 * 60 * 60 * 1_000_000;
+
+// =============================================================================
+// Attachment Helpers
+// =============================================================================
+
+/// Refuse anything but a hex SHA-256, since clients put hashes in subscription queries
+fn check_hash(hash: &str) -> Result<(), String> {
+    if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b)) {
+        return Err(format!("Invalid attachment hash (expected 64 lowercase hex digits): {:?}", hash));
+    }
+    Ok(())
+}
+
+/// Drop the chunks of `hash` once no attachment (live or trashed) uses them anymore
+pub(crate) fn release_chunks(ctx: &ReducerContext, hash: &String) {
+    if ctx.db.attachment().hash().filter(hash).next().is_some() {
+        return;
+    }
+
+    let in_trash = ctx
+        .db
+        .trash()
+        .iter()
+        .any(|entry| entry.attachment.as_ref().is_some_and(|a| &a.hash == hash));
+    if in_trash {
+        return;
+    }
+
+    ctx.db.attachment_chunk().hash().delete(hash);
+}
+
+/// Drop the chunks of uploads that started long ago and never got their `upsert_attachment`
+pub(crate) fn purge_abandoned_uploads(ctx: &ReducerContext) {
+    let now = ctx.timestamp.to_micros_since_unix_epoch();
+
+    let abandoned: Vec<String> = ctx
+        .db
+        .chunk_upload()
+        .iter()
+        .filter(|u| now - u.started_at.to_micros_since_unix_epoch() > ABANDONED_UPLOAD_MICROS)
+        .map(|u| u.hash)
+        .collect();
+
+    for hash in &abandoned {
+        ctx.db.chunk_upload().hash().delete(hash);
+        release_chunks(ctx, hash);
+    }
+
+    if !abandoned.is_empty() {
+        log::info!("Dropped chunks of {} abandoned attachment uploads", abandoned.len());
+    }
+}
+
+/// Move every attachment under `folder_prefix` (e.g. "Projects/") to the trash
+///
+/// Used by the `delete_folder` cascade. Returns the number of attachments trashed.
+pub(crate) fn trash_attachments_under(ctx: &ReducerContext, folder_prefix: &str, cascade_root: &str) -> usize {
+    let attachments: Vec<Attachment> = ctx
+        .db
+        .attachment()
+        .iter()
+        .filter(|a| a.folder_path.starts_with(folder_prefix))
+        .collect();
+
+    for existing in &attachments {
+        trash_attachment(ctx, existing, cascade_root);
+        ctx.db.attachment().path().delete(&existing.path);
+        refresh_attachment_links(ctx, &existing.path);
+    }
+
+    attachments.len()
+}
+
+/// Make sure every attachment under `old_prefix` can move to `new_prefix` (both with
+/// trailing slash): no other attachment may already sit at its new path
+pub(crate) fn check_attachment_moves(ctx: &ReducerContext, old_prefix: &str, new_prefix: &str) -> Result<(), String> {
+    for existing in ctx.db.attachment().iter().filter(|a| a.folder_path.starts_with(old_prefix)) {
+        let new_path = existing.path.replacen(old_prefix, new_prefix, 1);
+        if ctx.db.attachment().path().find(&new_path).is_some() {
+            return Err(format!("Cannot move: Attachment already exists at {}", new_path));
+        }
+    }
+    Ok(())
+}
+
+/// Re-path every attachment under `old_prefix` to `new_prefix` (both with trailing slash)
+///
+/// Used by the `move_folder` cascade. Chunks are keyed by hash, so no bytes move.
+/// Call `check_attachment_moves` first: a clash would fail the insert halfway through.
+pub(crate) fn move_attachments_under(ctx: &ReducerContext, old_prefix: &str, new_prefix: &str) -> usize {
+    let attachments: Vec<Attachment> = ctx
+        .db
+        .attachment()
+        .iter()
+        .filter(|a| a.folder_path.starts_with(old_prefix))
+        .collect();
+
+    for existing in &attachments {
+        let new_path = existing.path.replacen(old_prefix, new_prefix, 1);
+        let new_folder_path = existing.folder_path.replacen(old_prefix, new_prefix, 1);
+
+        ctx.db.attachment().path().delete(&existing.path);
+        ctx.db.attachment().insert(Attachment {
+            path: new_path.clone(),
+            folder_path: new_folder_path,
+            db_updated_at: ctx.timestamp,
+            ..existing.clone()
+        });
+        refresh_attachment_links(ctx, &existing.path);
+        refresh_attachment_links(ctx, &new_path);
+    }
+
+    attachments.len()
+}
+
+// =============================================================================
+// Attachment Reducers
+// =============================================================================
+
+/// Store one chunk of an attachment's bytes
+///
+/// Clients upload every chunk of a file first, then call `upsert_attachment`.
+/// Chunks already stored for the same hash are skipped, so re-uploading an
+/// identical file (or a copy under another path) is cheap.
+#[spacetimedb::reducer]
+pub fn upload_attachment_chunk(ctx: &ReducerContext, hash: String, chunk_index: u32, data: Vec<u8>) -> Result<(), String> {
+    check_hash(&hash)?;
+
+    let exists = ctx
+        .db
+        .attachment_chunk()
+        .hash()
+        .filter(&hash)
+        .any(|chunk| chunk.chunk_index == chunk_index);
+
+    if exists {
+        log::debug!("Chunk {} of {} already stored", chunk_index, hash);
+        return Ok(());
+    }
+
+    if ctx.db.chunk_upload().hash().find(&hash).is_none() {
+        ctx.db.chunk_upload().insert(ChunkUpload { hash: hash.clone(), started_at: ctx.timestamp });
+    }
+
+    ctx.db.attachment_chunk().insert(AttachmentChunk {
+        id: 0, // auto_inc
+        hash,
+        chunk_index,
+        data,
+    });
+    Ok(())
+}
+
+/// Create or update an attachment whose chunks have all been uploaded
+#[spacetimedb::reducer]
+pub fn upsert_attachment(
+    ctx: &ReducerContext,
+    path: String,
+    hash: String,
+    mime_type: String,
+    size: u64,
+    chunk_count: u32,
+    modified_time: u64,
+) -> Result<(), String> {
+    check_hash(&hash)?;
+
+    let stored = ctx.db.attachment_chunk().hash().filter(&hash).count() as u32;
+    if stored < chunk_count {
+        return Err(format!("Cannot save attachment {}: only {} of {} chunks uploaded", path, stored, chunk_count));
+    }
+
+    let name = path.rsplit('/').next().unwrap_or(&path).to_string();
+    let folder_path = match path.rfind('/') {
+        Some(idx) => format!("{}/", &path[..idx]),
+        None => String::new(),
+    };
+
+    let previous = ctx.db.attachment().path().find(&path);
+    if previous.is_some() {
+        ctx.db.attachment().path().delete(&path);
+    }
+
+    ctx.db.chunk_upload().hash().delete(&hash);
+    ctx.db.attachment().insert(Attachment {
+        path: path.clone(),
+        name,
+        folder_path,
+        hash,
+        mime_type,
+        size,
+        chunk_count,
+        modified_time,
+        db_updated_at: ctx.timestamp,
+    });
+
+    match previous {
+        Some(previous) => {
+            release_chunks(ctx, &previous.hash);
+            log::info!("Updated attachment: {}", path);
+        }
+        None => {
+            refresh_attachment_links(ctx, &path);
+            log::info!("Created attachment: {}", path);
+        }
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn delete_attachment(ctx: &ReducerContext, path: String) -> Result<(), String> {
+    let Some(existing) = ctx.db.attachment().path().find(&path) else {
+        // Deleted already (e.g. the same delete, resent after a reconnect)
+        if deleted_directly(ctx, &path, false) {
+            log::info!("Attachment already deleted: {}", path);
+            return Ok(());
+        }
+        return Err(format!("Attachment not found for deletion: {}", path));
+    };
+
+    // Soft delete: the trash entry keeps the chunks alive until it is purged
+    trash_attachment(ctx, &existing, "");
+    ctx.db.attachment().path().delete(&path);
+    refresh_attachment_links(ctx, &path);
+    purge_expired_trash(ctx);
+    log::info!("Moved attachment to trash: {}", path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_hex_accepted() {
+        assert!(check_hash(&"0123456789abcdef".repeat(4)).is_ok());
+    }
+
+    #[test]
+    fn test_other_hashes_refused() {
+        assert!(check_hash("").is_err());
+        assert!(check_hash(&"ab".repeat(31)).is_err());
+        assert!(check_hash(&"AB".repeat(32)).is_err());
+        assert!(check_hash(&format!("{}' OR '1'='1", "a".repeat(52))).is_err());
+    }
+}
@@ -0,0 +1,54 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::{Conflict, conflict, note};
+
+// =============================================================================
+// Conflict Reducers
+// =============================================================================
+
+/// Record that `conflict_note_id` holds a version of `note_id` that lost a concurrent edit
+///
+/// Called by the daemon right after uploading the conflict copy, so both notes exist.
+#[spacetimedb::reducer]
+pub fn record_conflict(
+    ctx: &ReducerContext,
+    note_id: String,
+    conflict_note_id: String,
+    device: String,
+) -> Result<(), String> {
+    let original = ctx.db.note().id().find(&note_id)
+        .ok_or_else(|| format!("Note not found for conflict: {}", note_id))?;
+    let copy = ctx.db.note().id().find(&conflict_note_id)
+        .ok_or_else(|| format!("Conflict copy not found: {}", conflict_note_id))?;
+
+    // Each copy is recorded once, however often the call is resent
+    if ctx.db.conflict().iter().any(|c| c.conflict_note_id == conflict_note_id) {
+        log::info!("Conflict already recorded: {} vs {}", original.path, copy.path);
+        return Ok(());
+    }
+
+    ctx.db.conflict().insert(Conflict {
+        id: 0, // auto_inc
+        note_id,
+        conflict_note_id,
+        path: original.path.clone(),
+        conflict_path: copy.path.clone(),
+        device,
+        created_at: ctx.timestamp,
+    });
+    log::info!("Recorded conflict: {} vs {}", original.path, copy.path);
+    Ok(())
+}
+
+/// Mark a conflict as resolved
+///
+/// Only removes the record: the client keeps, merges or deletes the two notes itself.
+#[spacetimedb::reducer]
+pub fn resolve_conflict(ctx: &ReducerContext, id: u64) -> Result<(), String> {
+    let existing = ctx.db.conflict().id().find(id)
+        .ok_or_else(|| format!("Conflict not found: {}", id))?;
+
+    ctx.db.conflict().id().delete(id);
+    log::info!("Resolved conflict: {} vs {}", existing.path, existing.conflict_path);
+    Ok(())
+}
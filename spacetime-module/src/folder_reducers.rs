@@ -1,130 +1,231 @@
 use spacetimedb::{ReducerContext, Table};
 
-use crate::{Folder, folder, note};
+use crate::{Folder, attachment, folder, note};
+use crate::attachment_reducers::{check_attachment_moves, move_attachments_under, trash_attachments_under};
+use crate::revision_reducers::capture_revision;
+use crate::safe_path::encode_path;
+use crate::link_index::{LinkRewriter, index_note_links, remove_note_links, rewrite_moved_links};
+use crate::tag_index::remove_note_tags;
+use crate::trash_reducers::{deleted_directly, purge_expired_trash, trash_folder, trash_note};
+
+// =============================================================================
+// Folder Helpers
+// =============================================================================
+
+/// Parent of a normalized folder path ("Projects/Archive" -> "Projects", "Projects" -> "")
+pub(crate) fn parent_folder_path(path: &str) -> String {
+    match path.rsplit_once('/') {
+        Some((parent, _)) => parent.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Folder row for a normalized path, with name, depth and parent derived from it
+fn folder_row(path: &str) -> Folder {
+    Folder {
+        path: path.to_string(),
+        name: path.rsplit('/').next().unwrap_or(path).to_string(),
+        depth: path.matches('/').count() as u32,
+        parent_path: parent_folder_path(path),
+    }
+}
+
+/// Whether a note or attachment whose `folder_path` ("Projects/Archive/") is given sits
+/// anywhere below the normalized folder `path` ("Projects", but not "Projects2")
+fn is_under(folder_path: &str, path: &str) -> bool {
+    folder_path.strip_prefix(path).is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// `path` with the folder prefix `old_prefix` replaced by `new_prefix` (both with trailing slash)
+fn moved_path(path: &str, old_prefix: &str, new_prefix: &str) -> String {
+    match path.strip_prefix(old_prefix) {
+        Some(rest) => format!("{}{}", new_prefix, rest),
+        None => path.to_string(),
+    }
+}
+
+/// Every folder below `path` (children, grandchildren, ...), found by following `parent_path`
+fn descendant_folders(ctx: &ReducerContext, path: &str) -> Vec<Folder> {
+    descendants(path, |parent| ctx.db.folder().parent_path().filter(parent).collect())
+}
+
+/// Walk down from `path`, asking `children` for the folders whose parent is a given path
+fn descendants(path: &str, children: impl Fn(&str) -> Vec<Folder>) -> Vec<Folder> {
+    let mut descendants = Vec::new();
+    let mut pending = vec![path.to_string()];
+
+    while let Some(parent) = pending.pop() {
+        for child in children(&parent) {
+            pending.push(child.path.clone());
+            descendants.push(child);
+        }
+    }
+
+    descendants
+}
+
+/// Create `path` and any missing ancestors. Returns the number of folders created.
+pub(crate) fn ensure_folder(ctx: &ReducerContext, path: &str) -> usize {
+    let mut created = 0;
+    let mut current = path.trim_end_matches('/').to_string();
+
+    while !current.is_empty() && ctx.db.folder().path().find(&current).is_none() {
+        ctx.db.folder().insert(folder_row(&current));
+        created += 1;
+        current = parent_folder_path(&current);
+    }
+
+    created
+}
 
 // =============================================================================
 // Folder Reducers
 // =============================================================================
 
 #[spacetimedb::reducer]
-pub fn create_folder(ctx: &ReducerContext, path: String, name: String, depth: u32) {
+pub fn create_folder(ctx: &ReducerContext, path: String, name: String, depth: u32) -> Result<(), String> {
     // Normalize: strip trailing slash to match storage standard
     let normalized_path = path.trim_end_matches('/').to_string();
 
     if ctx.db.folder().path().find(&normalized_path).is_some() {
-        log::warn!("Folder already exists: {}", normalized_path);
-        return;
+        return Err(format!("Folder already exists: {}", normalized_path));
     }
 
     ctx.db.folder().insert(Folder {
         path: normalized_path.clone(),
         name,
         depth,
+        parent_path: parent_folder_path(&normalized_path),
     });
     log::info!("Created folder: {}", normalized_path);
+    Ok(())
 }
 
 #[spacetimedb::reducer]
-pub fn delete_folder(ctx: &ReducerContext, path: String) {
+pub fn delete_folder(ctx: &ReducerContext, path: String) -> Result<(), String> {
     // Normalize: strip trailing slash to match storage standard
     let normalized_path = path.trim_end_matches('/').to_string();
 
-    if ctx.db.folder().path().find(&normalized_path).is_none() {
-        log::warn!("Folder not found for deletion: {}", normalized_path);
-        return;
-    }
+    let Some(existing) = ctx.db.folder().path().find(&normalized_path) else {
+        // Deleted already (e.g. the same delete, resent after a reconnect)
+        if deleted_directly(ctx, &normalized_path, true) {
+            log::info!("Folder already deleted: {}", normalized_path);
+            return Ok(());
+        }
+        return Err(format!("Folder not found for deletion: {}", normalized_path));
+    };
 
     // For cascade operations, use path with slash to match note.folder_path
     let path_with_slash = format!("{}/", normalized_path);
 
-    // CASCADE: Delete all notes inside this folder (and subfolders)
-    let notes_to_delete: Vec<String> = ctx
+    // CASCADE: Move all notes inside this folder (and subfolders) to the trash
+    let notes_to_delete: Vec<_> = ctx
         .db
         .note()
         .iter()
-        .filter(|note| note.folder_path.starts_with(&path_with_slash))
-        .map(|note| note.id.clone())
+        .filter(|note| is_under(&note.folder_path, &normalized_path))
         .collect();
 
-    for note_id in &notes_to_delete {
-        ctx.db.note().id().delete(note_id);
+    for note in &notes_to_delete {
+        trash_note(ctx, note, &normalized_path);
+        ctx.db.note().id().delete(&note.id);
+        remove_note_tags(ctx, &note.id);
+        remove_note_links(ctx, &note.id);
     }
 
     if !notes_to_delete.is_empty() {
         log::info!("Cascade deleted {} notes from folder: {}", notes_to_delete.len(), normalized_path);
     }
 
-    // CASCADE: Delete all subfolders (use normalized path for comparison)
-    let subfolders_to_delete: Vec<String> = ctx
-        .db
-        .folder()
-        .iter()
-        .filter(|f| f.path.starts_with(&normalized_path) && f.path != normalized_path)
-        .map(|f| f.path.clone())
-        .collect();
+    // CASCADE: Move all attachments inside this folder to the trash
+    let attachments_count = trash_attachments_under(ctx, &path_with_slash, &normalized_path);
+    if attachments_count > 0 {
+        log::info!("Cascade deleted {} attachments from folder: {}", attachments_count, normalized_path);
+    }
+
+    // CASCADE: Move all subfolders to the trash (walks parent_path, so "Projects2" is not
+    // mistaken for a child of "Projects")
+    let subfolders_to_delete = descendant_folders(ctx, &normalized_path);
 
-    for subfolder_path in &subfolders_to_delete {
-        ctx.db.folder().path().delete(subfolder_path);
+    for subfolder in &subfolders_to_delete {
+        trash_folder(ctx, subfolder, &normalized_path);
+        ctx.db.folder().path().delete(&subfolder.path);
     }
 
     if !subfolders_to_delete.is_empty() {
         log::info!("Cascade deleted {} subfolders from: {}", subfolders_to_delete.len(), normalized_path);
     }
 
-    // Delete the folder itself
+    // Delete the folder itself (restoring it from the trash brings the cascade back too)
+    trash_folder(ctx, &existing, "");
     ctx.db.folder().path().delete(&normalized_path);
-    log::info!("Deleted folder: {}", normalized_path);
+    purge_expired_trash(ctx);
+    log::info!("Moved folder to trash: {}", normalized_path);
+    Ok(())
 }
 
 #[spacetimedb::reducer]
-pub fn move_folder(ctx: &ReducerContext, old_path: String, new_path: String) {
+pub fn move_folder(
+    ctx: &ReducerContext,
+    old_path: String,
+    new_path: String,
+    rewrite_links: bool,
+) -> Result<(), String> {
     // Normalize: strip trailing slashes
     let old_normalized = old_path.trim_end_matches('/').to_string();
     let new_normalized = new_path.trim_end_matches('/').to_string();
 
     // Verify source folder exists
     if ctx.db.folder().path().find(&old_normalized).is_none() {
-        log::warn!("Folder not found for move: {}", old_normalized);
-        return;
+        return Err(format!("Folder not found for move: {}", old_normalized));
     }
 
     // Check if destination already exists
     if ctx.db.folder().path().find(&new_normalized).is_some() {
-        log::error!("Cannot move: Destination folder already exists: {}", new_normalized);
-        return;
+        return Err(format!("Cannot move: Destination folder already exists: {}", new_normalized));
     }
 
-    // Calculate new metadata for the folder
-    let new_name = new_normalized
-        .rsplit('/')
-        .next()
-        .unwrap_or(&new_normalized)
-        .to_string();
-    let new_depth = new_normalized.matches('/').count() as u32;
-
     // For cascade operations, use paths with slashes
     let old_path_with_slash = format!("{}/", old_normalized);
     let new_path_with_slash = format!("{}/", new_normalized);
 
+    if new_path_with_slash.starts_with(&old_path_with_slash) {
+        return Err(format!("Cannot move folder {} into itself ({})", old_normalized, new_normalized));
+    }
+
     // CASCADE 1: Update all notes inside this folder
     let notes_to_update: Vec<_> = ctx
         .db
         .note()
         .iter()
-        .filter(|note| note.folder_path.starts_with(&old_path_with_slash))
+        .filter(|note| is_under(&note.folder_path, &old_normalized))
         .collect();
 
+    // Notes without a folder row can already sit at the destination
+    for note in &notes_to_update {
+        let new_note_path = moved_path(&note.path, &old_path_with_slash, &new_path_with_slash);
+        if ctx.db.note().path().find(&new_note_path).is_some() {
+            return Err(format!("Cannot move: Note already exists at {}", new_note_path));
+        }
+    }
+    check_attachment_moves(ctx, &old_path_with_slash, &new_path_with_slash)?;
+
+    let moved_ids: Vec<String> = notes_to_update.iter().map(|note| note.id.clone()).collect();
+    let rewriter = rewrite_links.then(|| LinkRewriter::prepare(ctx, &moved_ids));
+
     let notes_count = notes_to_update.len();
     for note in notes_to_update {
         // Calculate new paths for the note
-        let new_note_folder_path = note.folder_path.replacen(&old_path_with_slash, &new_path_with_slash, 1);
-        let new_note_path = note.path.replacen(&old_path_with_slash, &new_path_with_slash, 1);
+        let new_note_folder_path = moved_path(&note.folder_path, &old_path_with_slash, &new_path_with_slash);
+        let new_note_path = moved_path(&note.path, &old_path_with_slash, &new_path_with_slash);
         let new_note_depth = new_note_path.matches('/').count() as u32;
 
         // Delete old entry and insert with updated paths
+        capture_revision(ctx, &note);
         ctx.db.note().id().delete(&note.id);
-        ctx.db.note().insert(crate::Note {
+        let moved = ctx.db.note().insert(crate::Note {
             id: note.id.clone(),
+            safe_path: encode_path(&new_note_path),
             path: new_note_path,
             name: note.name,
             content: note.content,
@@ -134,56 +235,50 @@ pub fn move_folder(ctx: &ReducerContext, old_path: String, new_path: String) {
             size: note.size,
             created_time: note.created_time,
             modified_time: note.modified_time,
+            version: note.version + 1,
             db_updated_at: ctx.timestamp,
         });
+        index_note_links(ctx, &moved);
     }
 
     if notes_count > 0 {
         log::info!("Cascade updated {} notes in folder move", notes_count);
     }
 
-    // CASCADE 2: Update all subfolders
-    let subfolders_to_update: Vec<_> = ctx
-        .db
-        .folder()
-        .iter()
-        .filter(|f| f.path.starts_with(&old_normalized) && f.path != old_normalized)
-        .collect();
+    // Links are rewritten once every note has its new path, so notes moving
+    // together keep their relative links to each other
+    rewrite_moved_links(ctx, rewriter, &new_normalized);
+
+    // CASCADE: Re-path attachments (their bytes are keyed by hash and stay put)
+    let attachments_count = move_attachments_under(ctx, &old_path_with_slash, &new_path_with_slash);
+    if attachments_count > 0 {
+        log::info!("Cascade updated {} attachments in folder move", attachments_count);
+    }
+
+    // CASCADE 2: Update all subfolders (found through the hierarchy, not by path prefix)
+    let subfolders_to_update = descendant_folders(ctx, &old_normalized);
 
     let subfolders_count = subfolders_to_update.len();
     for subfolder in subfolders_to_update {
         // Calculate new path for subfolder
-        let new_subfolder_path = subfolder.path.replacen(&old_normalized, &new_normalized, 1);
-        let new_subfolder_name = new_subfolder_path
-            .rsplit('/')
-            .next()
-            .unwrap_or(&new_subfolder_path)
-            .to_string();
-        let new_subfolder_depth = new_subfolder_path.matches('/').count() as u32;
+        let new_subfolder_path = moved_path(&subfolder.path, &old_path_with_slash, &new_path_with_slash);
 
         // Delete old entry and insert with updated path
         ctx.db.folder().path().delete(&subfolder.path);
-        ctx.db.folder().insert(Folder {
-            path: new_subfolder_path,
-            name: new_subfolder_name,
-            depth: new_subfolder_depth,
-        });
+        ctx.db.folder().insert(folder_row(&new_subfolder_path));
     }
 
     if subfolders_count > 0 {
         log::info!("Cascade updated {} subfolders in folder move", subfolders_count);
     }
 
-    // Move the folder itself
+    // Move the folder itself, creating the destination's ancestors if needed
     ctx.db.folder().path().delete(&old_normalized);
-    ctx.db.folder().insert(Folder {
-        path: new_normalized.clone(),
-        name: new_name,
-        depth: new_depth,
-    });
+    ensure_folder(ctx, &new_normalized);
 
     log::info!("Moved folder: {} -> {} (with {} notes, {} subfolders)",
                old_normalized, new_normalized, notes_count, subfolders_count);
+    Ok(())
 }
 
 #[spacetimedb::reducer]
@@ -196,8 +291,119 @@ pub fn upsert_folder(ctx: &ReducerContext, path: String, name: String, depth: u3
         ctx.db.folder().path().delete(&normalized_path);
     }
     ctx.db.folder().insert(Folder {
+        parent_path: parent_folder_path(&normalized_path),
         path: normalized_path,
         name,
         depth
     });
 }
+
+/// Integrity check: create any folder that a note lives in (or a folder hangs under)
+/// but that has no row of its own, and fix `parent_path` on rows that disagree with their path
+///
+/// Run once by `run_migrations`, so folders from before `parent_path` existed (the column
+/// has no default) are found by the delete and move cascades.
+#[spacetimedb::reducer]
+pub fn repair_folder_hierarchy(ctx: &ReducerContext) {
+    let mut fixed = 0;
+    let folders: Vec<Folder> = ctx.db.folder().iter().collect();
+    for folder in folders {
+        let parent_path = parent_folder_path(&folder.path);
+        if folder.parent_path != parent_path {
+            ctx.db.folder().path().delete(&folder.path);
+            ctx.db.folder().insert(Folder { parent_path, ..folder });
+            fixed += 1;
+        }
+    }
+
+    let mut wanted: Vec<String> = ctx.db.note().iter().map(|note| note.folder_path).collect();
+    wanted.extend(ctx.db.attachment().iter().map(|attachment| attachment.folder_path));
+    wanted.extend(ctx.db.folder().iter().map(|folder| folder.parent_path));
+    wanted.sort();
+    wanted.dedup();
+
+    let created: usize = wanted.iter().map(|path| ensure_folder(ctx, path)).sum();
+
+    log::info!("Folder hierarchy repaired: {} folders created, {} parent links fixed", created, fixed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TrashEntry;
+    use crate::trash_reducers::restores_with;
+    use spacetimedb::{Identity, Timestamp};
+
+    fn folders(paths: &[&str]) -> Vec<Folder> {
+        paths.iter().map(|path| folder_row(path)).collect()
+    }
+
+    fn paths_below(all: &[Folder], path: &str) -> Vec<String> {
+        let mut paths: Vec<String> = descendants(path, |parent| {
+            all.iter().filter(|f| f.parent_path == parent).cloned().collect()
+        })
+        .into_iter()
+        .map(|f| f.path)
+        .collect();
+        paths.sort();
+        paths
+    }
+
+    fn trashed(path: &str, cascade_root: &str, deleted_at: i64) -> TrashEntry {
+        TrashEntry {
+            id: 0,
+            original_path: path.to_string(),
+            deleted_at: Timestamp::from_micros_since_unix_epoch(deleted_at),
+            deleted_by: Identity::ZERO,
+            cascade_root: cascade_root.to_string(),
+            note: None,
+            folder: None,
+            attachment: None,
+        }
+    }
+
+    #[test]
+    fn test_descendants_skip_sibling_with_shared_prefix() {
+        let all = folders(&["Projects", "Projects/Archive", "Projects/Archive/2024", "Projects2", "Projects2/Drafts"]);
+
+        assert_eq!(paths_below(&all, "Projects"), ["Projects/Archive", "Projects/Archive/2024"]);
+        assert_eq!(paths_below(&all, "Projects2"), ["Projects2/Drafts"]);
+        assert!(paths_below(&all, "Projects/Archive/2024").is_empty());
+    }
+
+    #[test]
+    fn test_notes_of_sibling_with_shared_prefix_are_not_under() {
+        assert!(is_under("Projects/", "Projects"));
+        assert!(is_under("Projects/Archive/", "Projects"));
+        assert!(!is_under("Projects2/", "Projects"));
+        assert!(!is_under("", "Projects"));
+    }
+
+    #[test]
+    fn test_moved_path_replaces_only_the_prefix() {
+        assert_eq!(moved_path("Projects/Projects/Note.md", "Projects/", "Work/"), "Work/Projects/Note.md");
+        assert_eq!(moved_path("Projects2/Note.md", "Projects/", "Work/"), "Projects2/Note.md");
+    }
+
+    #[test]
+    fn test_restore_takes_only_its_own_cascade() {
+        // `delete_folder("Projects")` at t=1, and an unrelated `delete_folder("Projects2")` at t=1
+        let root = trashed("Projects", "", 1);
+        let nested = trashed("Projects/Archive", "Projects", 1);
+        let nested_note = trashed("Projects/Archive/Note.md", "Projects", 1);
+        let top_note = trashed("Projects/Note.md", "Projects", 1);
+        let sibling_note = trashed("Projects2/Note.md", "Projects2", 1);
+
+        assert!(restores_with(&root, &nested));
+        assert!(restores_with(&root, &nested_note));
+        assert!(restores_with(&root, &top_note));
+        assert!(!restores_with(&root, &sibling_note));
+
+        // The nested folder restored on its own brings back what's below it, nothing else
+        assert!(restores_with(&nested, &nested_note));
+        assert!(!restores_with(&nested, &top_note));
+
+        // The same folder trashed by a later delete is a different cascade
+        assert!(!restores_with(&root, &trashed("Projects/Archive/Note.md", "Projects", 2)));
+    }
+}
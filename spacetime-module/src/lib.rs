@@ -1,18 +1,26 @@
-use spacetimedb::{ReducerContext, Table, Timestamp};
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
 
 mod note_reducers;
 mod folder_reducers;
+mod attachment_reducers;
+mod conflict_reducers;
+mod link_index;
+mod revision_reducers;
+mod safe_path;
+mod tag_index;
+mod trash_reducers;
 
 // =============================================================================
 // Tables
 // =============================================================================
 
 #[spacetimedb::table(name = note, public)]
+#[derive(Clone)]
 pub struct Note {
     #[primary_key]
     pub id: String,          // UUID (e.g., "550e8400-e29b...")
     #[unique]
-    pub path: String,        // "Projects/my-note.md"
+    pub path: String,        // "Projects/my-note.md" - the exact file name (NFC), any Unicode
     pub name: String,        // "my-note"
     pub content: String,
     pub folder_path: String, // "Projects/"
@@ -23,14 +31,182 @@ pub struct Note {
     pub modified_time: u64,  // ms since epoch (filesystem)
     #[index(btree)]
     pub db_updated_at: Timestamp, // SpacetimeDB transaction time
+    // Columns added after the first release go last, with a default for existing rows
+    #[default(1)]
+    pub version: u64,        // Starts at 1, incremented by every write (see `*_if_version` reducers)
+    // No `#[default]`: the table macro binds it inside a `const` block, where a `String` can't be dropped (E0493).
+    // Existing rows are backfilled by the `safe_path` migration
+    pub safe_path: String,   // `path` percent-encoded, safe in URIs ("Caf%C3%A9.md"); see `repair_safe_paths`
+}
+
+/// Prior version of a note, captured before each write that changes its content or path
+#[spacetimedb::table(name = note_revision, public)]
+pub struct NoteRevision {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub note_id: String,
+    pub path: String,        // Path at the time the revision was captured
+    pub content: String,
+    pub frontmatter: String, // JSON-serialized Map
+    pub size: u64,
+    pub modified_time: u64,  // ms since epoch (of the captured version)
+    pub captured_at: Timestamp,
+}
+
+/// One row per (note, tag), derived from frontmatter `tags:` and inline `#tags`
+#[spacetimedb::table(name = note_tag, public)]
+pub struct NoteTag {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub note_id: String,
+    #[index(btree)]
+    pub tag: String,         // Lowercased, without leading '#'
+}
+
+/// One row per link found in a note: `[[wikilinks]]`, `[markdown](links.md)` and embedded attachments
+#[spacetimedb::table(name = note_link, public)]
+pub struct NoteLink {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub source_id: String,
+    pub kind: String,        // "wikilink", "markdown" or "attachment"
+    pub target_path: String, // Wikilink target as written, or vault-relative path for markdown links
+    #[index(btree)]
+    pub target_key: String,  // Lowercased file name of `target_path` without ".md", to find links a new note may satisfy
+    #[index(btree)]
+    pub target_id: String,   // Resolved note ID, or attachment path for "attachment" ("" if the target doesn't exist)
+    pub anchor: String,      // Heading/block after '#' ("" if none)
+}
+
+/// Every note and attachment a link can resolve to, indexed by path and by file name
+#[spacetimedb::table(name = link_target)]
+pub struct LinkTarget {
+    #[primary_key]
+    pub target_id: String,   // Note ID, or attachment path
+    pub is_attachment: bool,
+    #[index(btree)]
+    pub path_key: String,    // Lowercased path
+    #[index(btree)]
+    pub name_key: String,    // Lowercased file name without ".md"
+}
+
+/// Note whose links a move rewrote, kept for a while so the client that made the move can report it
+#[spacetimedb::table(name = link_rewrite, public)]
+pub struct LinkRewrite {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub moved_path: String,      // New path of the note or folder that moved
+    pub note_path: String,       // Note whose links were rewritten
+    pub caller: Identity,        // Client that made the move
+    pub rewritten_at: Timestamp, // Time of the move
+}
+
+/// Non-markdown file in the vault (images, PDFs, ...). The bytes live in `attachment_chunk`.
+#[spacetimedb::table(name = attachment, public)]
+#[derive(Clone)]
+pub struct Attachment {
+    #[primary_key]
+    pub path: String,        // "Projects/img/diagram.png"
+    pub name: String,        // "diagram.png"
+    #[index(btree)]
+    pub folder_path: String, // "Projects/img/"
+    #[index(btree)]
+    pub hash: String,        // SHA-256 of the file bytes (hex)
+    pub mime_type: String,   // "image/png"
+    pub size: u64,
+    pub chunk_count: u32,
+    pub modified_time: u64,  // ms since epoch (filesystem)
+    pub db_updated_at: Timestamp,
+}
+
+/// Piece of an attachment's bytes, keyed by content hash so identical files share storage
+#[spacetimedb::table(name = attachment_chunk, public)]
+pub struct AttachmentChunk {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub hash: String,
+    pub chunk_index: u32,
+    pub data: Vec<u8>,
+}
+
+/// Hash whose chunks are being uploaded; cleared by `upsert_attachment`, so the chunks
+/// of an upload that never finished can be found and dropped
+#[spacetimedb::table(name = chunk_upload)]
+pub struct ChunkUpload {
+    #[primary_key]
+    pub hash: String,
+    pub started_at: Timestamp,
 }
 
 #[spacetimedb::table(name = folder, public)]
+#[derive(Clone)]
 pub struct Folder {
     #[primary_key]
-    pub path: String,
-    pub name: String,
+    pub path: String,        // "Projects/Archive" (no trailing slash)
+    pub name: String,        // "Archive"
     pub depth: u32,
+    // No `#[default]`, for the same reason as `Note::safe_path`.
+    // Existing rows are backfilled by the `folder_parent_path` migration
+    #[index(btree)]
+    pub parent_path: String, // "Projects" ("" for top-level folders)
+}
+
+/// Deleted note, remembered so clients that were offline delete their copy instead of re-uploading it
+#[spacetimedb::table(name = tombstone, public)]
+pub struct Tombstone {
+    #[primary_key]
+    pub note_id: String,
+    pub path: String,           // Path at the time of deletion
+    pub deleted_at: Timestamp,
+    pub origin: Identity,       // Client that deleted the note
+}
+
+/// Note edited in two places at once; the losing version was saved as a separate note
+#[spacetimedb::table(name = conflict, public)]
+pub struct Conflict {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub note_id: String,          // Note that kept the winning version
+    pub conflict_note_id: String, // Copy holding the losing version
+    pub path: String,             // "Projects/Note.md"
+    pub conflict_path: String,    // "Projects/Note (conflict 2026-10-16 laptop).md"
+    pub device: String,           // Device that detected the conflict and wrote the copy
+    pub created_at: Timestamp,
+}
+
+/// Soft-deleted note, folder or attachment, kept until restored, emptied or purged by age
+#[spacetimedb::table(name = trash, public)]
+pub struct TrashEntry {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub original_path: String,
+    pub deleted_at: Timestamp,
+    pub deleted_by: Identity,
+    pub cascade_root: String,   // Folder whose deletion trashed this row ("" if deleted directly)
+    pub note: Option<Note>,     // Full row, set when a note was deleted
+    pub folder: Option<Folder>, // Full row, set when a folder was deleted
+    pub attachment: Option<Attachment>, // Full row, set when an attachment was deleted (chunks are kept)
+}
+
+/// One-time backfill that has already run on this database (see `run_migrations`)
+#[spacetimedb::table(name = migration)]
+pub struct Migration {
+    #[primary_key]
+    pub name: String,
+    pub applied_at: Timestamp,
 }
 
 // =============================================================================
@@ -43,8 +219,38 @@ pub fn init(_ctx: &ReducerContext) {
 }
 
 #[spacetimedb::reducer(client_connected)]
-pub fn identity_connected(_ctx: &ReducerContext) {
+pub fn identity_connected(ctx: &ReducerContext) {
     log::info!("Client connected");
+    trash_reducers::purge_expired_trash(ctx);
+    trash_reducers::purge_expired_tombstones(ctx);
+    revision_reducers::purge_expired_revisions(ctx);
+    attachment_reducers::purge_abandoned_uploads(ctx);
+    run_migrations(ctx);
+}
+
+/// A named backfill, recorded in `migration` once it has run
+type MigrationStep = (&'static str, fn(&ReducerContext));
+
+/// Backfills for rows written before an index or column existed, in the order they run
+const MIGRATIONS: &[MigrationStep] = &[
+    ("folder_parent_path", folder_reducers::repair_folder_hierarchy),
+    ("tag_index", tag_index::rebuild_tag_index),
+    ("link_index", link_index::rebuild_link_index),
+    ("safe_path", safe_path::repair_safe_paths),
+];
+
+/// Run each backfill once per database
+///
+/// There is no hook for publishing over existing data, so they run on the first client
+/// connection after an upgrade, before that client can call any other reducer.
+fn run_migrations(ctx: &ReducerContext) {
+    for (name, migrate) in MIGRATIONS {
+        if ctx.db.migration().name().find(name.to_string()).is_none() {
+            log::info!("Running migration {}", name);
+            migrate(ctx);
+            ctx.db.migration().insert(Migration { name: name.to_string(), applied_at: ctx.timestamp });
+        }
+    }
 }
 
 #[spacetimedb::reducer(client_disconnected)]
@@ -62,13 +268,71 @@ pub fn clear_all(ctx: &ReducerContext) {
         ctx.db.note().id().delete(&id);
     }
 
+    // Clear all tags
+    let tag_ids: Vec<u64> = ctx.db.note_tag().iter().map(|t| t.id).collect();
+    for id in tag_ids {
+        ctx.db.note_tag().id().delete(id);
+    }
+
+    // Clear all links
+    let link_ids: Vec<u64> = ctx.db.note_link().iter().map(|l| l.id).collect();
+    for id in link_ids {
+        ctx.db.note_link().id().delete(id);
+    }
+    let target_ids: Vec<String> = ctx.db.link_target().iter().map(|t| t.target_id.clone()).collect();
+    for id in target_ids {
+        ctx.db.link_target().target_id().delete(&id);
+    }
+    let rewrite_ids: Vec<u64> = ctx.db.link_rewrite().iter().map(|r| r.id).collect();
+    for id in rewrite_ids {
+        ctx.db.link_rewrite().id().delete(id);
+    }
+
+    // Clear all revisions
+    let revision_ids: Vec<u64> = ctx.db.note_revision().iter().map(|r| r.id).collect();
+    for id in revision_ids {
+        ctx.db.note_revision().id().delete(id);
+    }
+
+    // Clear all trash
+    let trash_ids: Vec<u64> = ctx.db.trash().iter().map(|e| e.id).collect();
+    for id in trash_ids {
+        ctx.db.trash().id().delete(id);
+    }
+
+    // Clear all attachments and their chunks
+    let attachment_paths: Vec<String> = ctx.db.attachment().iter().map(|a| a.path.clone()).collect();
+    for path in attachment_paths {
+        ctx.db.attachment().path().delete(&path);
+    }
+    let chunk_ids: Vec<u64> = ctx.db.attachment_chunk().iter().map(|c| c.id).collect();
+    for id in chunk_ids {
+        ctx.db.attachment_chunk().id().delete(id);
+    }
+    let upload_hashes: Vec<String> = ctx.db.chunk_upload().iter().map(|u| u.hash.clone()).collect();
+    for hash in upload_hashes {
+        ctx.db.chunk_upload().hash().delete(&hash);
+    }
+
+    // Clear all tombstones
+    let tombstone_ids: Vec<String> = ctx.db.tombstone().iter().map(|t| t.note_id.clone()).collect();
+    for id in tombstone_ids {
+        ctx.db.tombstone().note_id().delete(&id);
+    }
+
+    // Clear all conflicts
+    let conflict_ids: Vec<u64> = ctx.db.conflict().iter().map(|c| c.id).collect();
+    for id in conflict_ids {
+        ctx.db.conflict().id().delete(id);
+    }
+
     // Clear all folders
     let folder_paths: Vec<String> = ctx.db.folder().iter().map(|f| f.path.clone()).collect();
     for path in folder_paths {
         ctx.db.folder().path().delete(&path);
     }
 
-    log::info!("Cleared all notes and folders");
+    log::info!("Cleared all notes, revisions, trash, attachments and folders");
 }
 
 // =============================================================================
@@ -0,0 +1,810 @@
+use std::collections::{HashMap, HashSet};
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::{LinkRewrite, LinkTarget, Note, NoteLink, attachment, link_rewrite, link_target, note, note_link};
+use crate::revision_reducers::capture_revision;
+
+pub(crate) const LINK_KIND_WIKI: &str = "wikilink";
+pub(crate) const LINK_KIND_MARKDOWN: &str = "markdown";
+pub(crate) const LINK_KIND_ATTACHMENT: &str = "attachment";
+
+/// Link targets with these extensions are embeds of attachments, not notes
+const ATTACHMENT_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "svg", "webp", "bmp", "avif", "heic", "pdf",
+    "mp3", "m4a", "wav", "ogg", "mp4", "mov", "webm",
+];
+
+/// A link as written in a note, before resolution
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ParsedLink {
+    pub kind: &'static str,
+    pub target: String, // Wikilink target as written, or vault-relative path for markdown links/embeds
+    pub anchor: String, // Heading/block after '#', empty if none
+}
+
+// =============================================================================
+// Link Index
+// =============================================================================
+
+/// Rebuild a note's outgoing links and re-resolve links pointing at it
+///
+/// Called after every insert of a note row, including path-only changes:
+/// a rename changes both how this note's relative links resolve and
+/// which wikilinks in other notes now find it.
+pub(crate) fn index_note_links(ctx: &ReducerContext, note: &Note) {
+    ctx.db.link_target().target_id().delete(&note.id);
+    ctx.db.link_target().insert(LinkTarget {
+        target_id: note.id.clone(),
+        is_attachment: false,
+        path_key: note.path.to_lowercase(),
+        name_key: link_key(&note.path),
+    });
+
+    ctx.db.note_link().source_id().delete(&note.id);
+
+    let resolver = LinkResolver::new(ctx);
+    insert_outgoing_links(ctx, &resolver, note);
+    refresh_inbound_links(ctx, &resolver, &note.id, &link_key(&note.path));
+}
+
+/// Drop a deleted note's outgoing links and unresolve links that pointed at it
+///
+/// Must be called after the note row has been deleted.
+pub(crate) fn remove_note_links(ctx: &ReducerContext, note_id: &String) {
+    ctx.db.link_target().target_id().delete(note_id);
+    ctx.db.note_link().source_id().delete(note_id);
+
+    let resolver = LinkResolver::new(ctx);
+    refresh_inbound_links(ctx, &resolver, note_id, "");
+}
+
+fn insert_outgoing_links(ctx: &ReducerContext, resolver: &LinkResolver, note: &Note) {
+    for link in extract_links(&note.folder_path, &note.content) {
+        let target_id = resolver.resolve(&note.folder_path, link.kind, &link.target).unwrap_or_default();
+        ctx.db.note_link().insert(NoteLink {
+            id: 0, // auto_inc
+            source_id: note.id.clone(),
+            kind: link.kind.to_string(),
+            target_key: link_key(&link.target),
+            target_path: link.target,
+            target_id,
+            anchor: link.anchor,
+        });
+    }
+}
+
+/// Re-resolve embeds after an attachment at `path` was created, moved or deleted
+pub(crate) fn refresh_attachment_links(ctx: &ReducerContext, path: &String) {
+    ctx.db.link_target().target_id().delete(path);
+    if ctx.db.attachment().path().find(path).is_some() {
+        ctx.db.link_target().insert(LinkTarget {
+            target_id: path.clone(),
+            is_attachment: true,
+            path_key: path.to_lowercase(),
+            name_key: link_key(path),
+        });
+    }
+
+    let resolver = LinkResolver::new(ctx);
+    refresh_inbound_links(ctx, &resolver, path, &link_key(path));
+}
+
+/// Re-resolve the links that point at `target_id` (a note ID or attachment path)
+/// or whose target has the file name `key`, i.e. the only links a write to that
+/// note or attachment can change
+fn refresh_inbound_links(ctx: &ReducerContext, resolver: &LinkResolver, target_id: &String, key: &str) {
+    let key = key.to_string();
+    let mut candidates: Vec<NoteLink> = ctx.db.note_link().target_id().filter(target_id).collect();
+    if !key.is_empty() {
+        candidates.extend(ctx.db.note_link().target_key().filter(&key).filter(|l| l.target_id != *target_id));
+    }
+
+    for link in candidates {
+        // Source folder is needed for relative resolution
+        let Some(source) = ctx.db.note().id().find(&link.source_id) else {
+            continue;
+        };
+
+        let kind = match link.kind.as_str() {
+            LINK_KIND_WIKI => LINK_KIND_WIKI,
+            LINK_KIND_ATTACHMENT => LINK_KIND_ATTACHMENT,
+            _ => LINK_KIND_MARKDOWN,
+        };
+        let target_id = resolver.resolve(&source.folder_path, kind, &link.target_path).unwrap_or_default();
+
+        if target_id != link.target_id {
+            ctx.db.note_link().id().update(NoteLink { target_id, ..link });
+        }
+    }
+}
+
+/// Lowercased file name a link target or path ends in, without `.md`
+/// ("Projects/Plan.md" and "[[plan]]" both give "plan")
+pub(crate) fn link_key(target: &str) -> String {
+    let name = target.rsplit('/').next().unwrap_or(target);
+    name.trim().trim_end_matches(".md").to_lowercase()
+}
+
+/// Resolves link targets against the `link_target` index as it is now
+struct LinkResolver<'a> {
+    ctx: &'a ReducerContext,
+}
+
+impl<'a> LinkResolver<'a> {
+    fn new(ctx: &'a ReducerContext) -> Self {
+        Self { ctx }
+    }
+
+    /// Note ID (or attachment path) at `path`, ignoring case
+    fn at_path(&self, path: &str, attachment: bool) -> Option<String> {
+        let key = path.to_lowercase();
+        let id = self
+            .ctx
+            .db
+            .link_target()
+            .path_key()
+            .filter(&key)
+            .find(|t| t.is_attachment == attachment)
+            .map(|t| t.target_id);
+        id
+    }
+
+    /// Candidates named `target` (the last path segment): prefer the source's folder, then the shortest path
+    fn closest_named(&self, source_folder: &str, target: &str, attachment: bool) -> Option<String> {
+        let key = link_key(target);
+        self.ctx
+            .db
+            .link_target()
+            .name_key()
+            .filter(&key)
+            .filter(|t| t.is_attachment == attachment)
+            .min_by_key(|t| {
+                let same_folder = t.path_key.strip_prefix(&source_folder.to_lowercase()).is_some_and(|rest| !rest.contains('/'));
+                (!same_folder, t.path_key.matches('/').count(), t.path_key.clone())
+            })
+            .map(|t| t.target_id)
+    }
+
+    /// Resolve an embed to an attachment path: exact path, then relative to the source, then by file name
+    fn resolve_attachment(&self, source_folder: &str, target: &str) -> Option<String> {
+        let exact = normalize_path(target).and_then(|p| self.at_path(&p, true));
+        if exact.is_some() {
+            return exact;
+        }
+
+        let relative = normalize_path(&format!("{}{}", source_folder, target)).and_then(|p| self.at_path(&p, true));
+        if relative.is_some() || target.contains('/') {
+            return relative;
+        }
+
+        // ![[image.png]]
+        self.closest_named(source_folder, target, true)
+    }
+
+    /// Resolve a link target as written in a note in `source_folder` (markdown targets as-is, not yet joined)
+    fn resolve_written(&self, source_folder: &str, kind: &str, written: &str) -> Option<String> {
+        if kind == LINK_KIND_MARKDOWN {
+            let target = markdown_target(source_folder, written)?;
+            return self.resolve(source_folder, kind, &target);
+        }
+        self.resolve(source_folder, kind, written)
+    }
+
+    /// Shortest wikilink target that resolves to `target` from `source_folder`
+    fn wikilink_for(&self, source_folder: &str, target: &Note) -> String {
+        if self.resolve(source_folder, LINK_KIND_WIKI, &target.name).as_ref() == Some(&target.id) {
+            target.name.clone()
+        } else {
+            target.path.trim_end_matches(".md").to_string()
+        }
+    }
+
+    /// Resolve a link target to a note ID (or attachment path), if the target exists
+    fn resolve(&self, source_folder: &str, kind: &str, target: &str) -> Option<String> {
+        if kind == LINK_KIND_ATTACHMENT {
+            return self.resolve_attachment(source_folder, target);
+        }
+
+        if kind == LINK_KIND_MARKDOWN {
+            return self.at_path(target, false);
+        }
+
+        let target = target.trim_end_matches(".md");
+
+        // [[Folder/Note]] is a path: try relative to the source, then from the vault root
+        if target.contains('/') {
+            let relative = normalize_path(&format!("{}{}.md", source_folder, target));
+            return relative
+                .and_then(|p| self.at_path(&p, false))
+                .or_else(|| normalize_path(&format!("{}.md", target)).and_then(|p| self.at_path(&p, false)));
+        }
+
+        // [[Note]] matches by name
+        self.closest_named(source_folder, target, false)
+    }
+}
+
+// =============================================================================
+// Link Rewriting
+// =============================================================================
+
+/// How long `link_rewrite` rows are kept (the client that made the move reads them right away)
+const LINK_REWRITE_RETENTION_MICROS: i64 = 60 * 60 * 1_000_000;
+
+/// (link kind, target as written) -> moved note it pointed at
+type MovedTargets = HashMap<(&'static str, String), String>;
+
+/// Links pointing at notes that are about to move, and the moved notes' own links,
+/// resolved before the move
+///
+/// Take it with `prepare` before the note rows change, then call `apply` once
+/// every row has its new path to rewrite the references that no longer resolve.
+/// Only note links are rewritten; embedded attachments (`![](image.png)`) are left as written.
+pub(crate) struct LinkRewriter {
+    // Source note id -> its links to moved notes
+    sources: Vec<(String, MovedTargets)>,
+}
+
+impl LinkRewriter {
+    pub(crate) fn prepare(ctx: &ReducerContext, moved_ids: &[String]) -> Self {
+        let resolver = LinkResolver::new(ctx);
+        let moved: HashSet<&String> = moved_ids.iter().collect();
+        let mut seen = HashSet::new();
+        let mut sources = Vec::new();
+
+        // A moved note's relative links are written from its old folder: any of them may break
+        for id in moved_ids {
+            if let Some(source) = ctx.db.note().id().find(id) {
+                seen.insert(source.id.clone());
+                sources.push(Self::links_of(&resolver, source, |_| true));
+            }
+        }
+
+        for id in moved_ids {
+            for link in ctx.db.note_link().target_id().filter(id) {
+                if !seen.insert(link.source_id.clone()) {
+                    continue;
+                }
+                let Some(source) = ctx.db.note().id().find(&link.source_id) else {
+                    continue;
+                };
+                sources.push(Self::links_of(&resolver, source, |target_id| moved.contains(target_id)));
+            }
+        }
+
+        Self { sources }
+    }
+
+    /// Links in `source` to the targets `wanted` picks, keyed by (kind, target as written)
+    fn links_of(
+        resolver: &LinkResolver,
+        source: Note,
+        wanted: impl Fn(&String) -> bool,
+    ) -> (String, MovedTargets) {
+        let mut targets = HashMap::new();
+        rewrite_links(&source.content, |kind, written| {
+            if let Some(target_id) = resolver.resolve_written(&source.folder_path, kind, written) {
+                if wanted(&target_id) {
+                    targets.insert((kind, written.to_string()), target_id);
+                }
+            }
+            None
+        });
+        (source.id, targets)
+    }
+
+    /// Rewrite stale links in every source note. Returns the paths of the notes that changed.
+    pub(crate) fn apply(self, ctx: &ReducerContext) -> Vec<String> {
+        let after = LinkResolver::new(ctx);
+        let now = ctx.timestamp.to_micros_since_unix_epoch() as u64 / 1_000;
+        let mut touched = Vec::new();
+
+        for (source_id, targets) in &self.sources {
+            // Looked up after the move: the source may have moved along with its targets
+            let Some(source) = ctx.db.note().id().find(source_id) else {
+                continue;
+            };
+
+            let content = rewrite_links(&source.content, |kind, written| {
+                let target_id = targets.get(&(kind, written.to_string()))?;
+                // Still points at the same note (e.g. both moved together): leave it alone
+                if after.resolve_written(&source.folder_path, kind, written).as_ref() == Some(target_id) {
+                    return None;
+                }
+
+                let target = ctx.db.note().id().find(target_id)?;
+                Some(if kind == LINK_KIND_WIKI {
+                    after.wikilink_for(&source.folder_path, &target)
+                } else if written.starts_with('/') {
+                    format!("/{}", target.path)
+                } else {
+                    relative_path(&source.folder_path, &target.path)
+                })
+            });
+
+            if content == source.content {
+                continue;
+            }
+
+            capture_revision(ctx, &source);
+
+            let size = (source.size as i64 + content.len() as i64 - source.content.len() as i64).max(0) as u64;
+
+            ctx.db.note().id().delete(&source.id);
+            let note = ctx.db.note().insert(Note {
+                content,
+                size,
+                modified_time: now,
+                version: source.version + 1,
+                db_updated_at: ctx.timestamp,
+                ..source
+            });
+            index_note_links(ctx, &note);
+            touched.push(note.path);
+        }
+
+        touched
+    }
+}
+
+/// Apply a prepared link rewrite (if one was requested) and record the notes it changed
+/// in `link_rewrite`, where the client that made the move reads them back
+pub(crate) fn rewrite_moved_links(ctx: &ReducerContext, rewriter: Option<LinkRewriter>, new_path: &str) {
+    let Some(rewriter) = rewriter else {
+        return;
+    };
+
+    purge_old_link_rewrites(ctx);
+
+    let touched = rewriter.apply(ctx);
+    for note_path in &touched {
+        ctx.db.link_rewrite().insert(LinkRewrite {
+            id: 0,
+            moved_path: new_path.to_string(),
+            note_path: note_path.clone(),
+            caller: ctx.sender,
+            rewritten_at: ctx.timestamp,
+        });
+    }
+    if !touched.is_empty() {
+        log::info!("Rewrote links for {} in {} notes: {}", new_path, touched.len(), touched.join(", "));
+    }
+}
+
+/// Drop `link_rewrite` rows past the retention window
+fn purge_old_link_rewrites(ctx: &ReducerContext) {
+    let now = ctx.timestamp.to_micros_since_unix_epoch();
+    let expired: Vec<u64> = ctx
+        .db
+        .link_rewrite()
+        .iter()
+        .filter(|r| now - r.rewritten_at.to_micros_since_unix_epoch() > LINK_REWRITE_RETENTION_MICROS)
+        .map(|r| r.id)
+        .collect();
+
+    for id in expired {
+        ctx.db.link_rewrite().id().delete(id);
+    }
+}
+
+/// Replace link targets in note content, keeping anchors, aliases and titles
+///
+/// `replace` receives the link kind and the target as written (wikilink text,
+/// or the decoded markdown path without its anchor) and returns the new target,
+/// or None to keep the link as is. Links inside code are left untouched.
+pub(crate) fn rewrite_links(
+    content: &str,
+    mut replace: impl FnMut(&'static str, &str) -> Option<String>,
+) -> String {
+    let mut out = String::with_capacity(content.len());
+
+    let mut in_code_block = false;
+    for line in content.split_inclusive('\n') {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+        }
+        if in_code_block || line.trim_start().starts_with("```") {
+            out.push_str(line);
+            continue;
+        }
+
+        // Spans are found on the masked line, whose byte offsets match the original
+        let masked = strip_inline_code(line);
+        let mut edits: Vec<(usize, usize, String)> = Vec::new();
+
+        let mut offset = 0;
+        while let Some(start) = masked[offset..].find("[[") {
+            let inner_start = offset + start + 2;
+            let Some(end) = masked[inner_start..].find("]]") else {
+                break;
+            };
+            let inner = &masked[inner_start..inner_start + end];
+            offset = inner_start + end + 2;
+
+            let target_len = inner.find(['#', '|']).unwrap_or(inner.len());
+            let target = inner[..target_len].trim();
+            if target.is_empty() {
+                continue;
+            }
+            if let Some(new_target) = replace(LINK_KIND_WIKI, target) {
+                edits.push((inner_start, inner_start + target_len, new_target));
+            }
+        }
+
+        let mut offset = 0;
+        while let Some(start) = masked[offset..].find("](") {
+            let raw_start = offset + start + 2;
+            let Some(end) = masked[raw_start..].find(')') else {
+                break;
+            };
+            let inner = &masked[raw_start..raw_start + end];
+            offset = raw_start + end + 1;
+
+            let leading = inner.len() - inner.trim_start().len();
+            let trimmed = inner.trim_start();
+            let (url_start, url_len, bracketed) = match trimmed.strip_prefix('<') {
+                Some(rest) => (raw_start + leading + 1, rest.find('>').unwrap_or(rest.len()), true),
+                None => (raw_start + leading, trimmed.find(char::is_whitespace).unwrap_or(trimmed.len()), false),
+            };
+
+            let raw = &masked[url_start..url_start + url_len];
+            if raw.is_empty() || raw.starts_with('#') || raw.contains("://") || raw.starts_with("mailto:") {
+                continue;
+            }
+
+            let decoded = percent_decode(raw);
+            let (path, anchor) = split_anchor(&decoded);
+            if !path.to_lowercase().ends_with(".md") {
+                continue;
+            }
+
+            if let Some(new_path) = replace(LINK_KIND_MARKDOWN, path) {
+                let mut new_raw = if bracketed { new_path } else { new_path.replace(' ', "%20") };
+                if !anchor.is_empty() {
+                    new_raw.push('#');
+                    new_raw.push_str(anchor);
+                }
+                edits.push((url_start, url_start + url_len, new_raw));
+            }
+        }
+
+        edits.sort_by_key(|(start, _, _)| *start);
+        let mut cursor = 0;
+        for (start, end, replacement) in edits {
+            if start < cursor {
+                continue; // Overlapping spans (e.g. "[[a]](b.md)"): keep the first
+            }
+            out.push_str(&line[cursor..start]);
+            out.push_str(&replacement);
+            cursor = end;
+        }
+        out.push_str(&line[cursor..]);
+    }
+
+    out
+}
+
+/// Path of `target` relative to the folder `from_folder` (e.g. "Projects/" -> "../Inbox.md")
+fn relative_path(from_folder: &str, target: &str) -> String {
+    let from: Vec<&str> = from_folder.split('/').filter(|s| !s.is_empty()).collect();
+    let to: Vec<&str> = target.split('/').collect();
+
+    // Only folder segments of the target may be shared, never its file name
+    let common = from
+        .iter()
+        .zip(&to[..to.len() - 1])
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut parts: Vec<&str> = vec![".."; from.len() - common];
+    parts.extend(&to[common..]);
+    parts.join("/")
+}
+
+// =============================================================================
+// Link Parsing
+// =============================================================================
+
+/// Extract `[[wikilinks]]`, `[markdown](links.md)` and attachment embeds from note content
+///
+/// Markdown link targets are resolved against `folder_path` (e.g. "Projects/")
+/// into vault-relative paths. Targets with an attachment extension (`![[image.png]]`,
+/// `![alt](img/image.png)`) are returned as attachment links. External URLs,
+/// other non-markdown targets and links inside code are skipped.
+pub(crate) fn extract_links(folder_path: &str, content: &str) -> Vec<ParsedLink> {
+    let mut links = Vec::new();
+
+    let mut in_code_block = false;
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        let line = strip_inline_code(line);
+        extract_wikilinks(&line, &mut links);
+        extract_markdown_links(folder_path, &line, &mut links);
+    }
+
+    links
+}
+
+/// Blank out `inline code` spans so links inside them are ignored
+///
+/// Every char is replaced by as many spaces as it has bytes, so offsets into
+/// the result are valid offsets into the original line.
+fn strip_inline_code(line: &str) -> String {
+    let mut in_code = false;
+    let mut out = String::with_capacity(line.len());
+    for c in line.chars() {
+        if c == '`' {
+            in_code = !in_code;
+            out.push(' ');
+        } else if in_code {
+            out.push_str(&" ".repeat(c.len_utf8()));
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn extract_wikilinks(line: &str, links: &mut Vec<ParsedLink>) {
+    let mut rest = line;
+    while let Some(start) = rest.find("[[") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("]]") else {
+            break;
+        };
+
+        // [[target#anchor|alias]]
+        let inner = &after[..end];
+        let target_part = inner.split('|').next().unwrap_or("");
+        let (target, anchor) = split_anchor(target_part);
+
+        let kind = if has_attachment_extension(target) { LINK_KIND_ATTACHMENT } else { LINK_KIND_WIKI };
+
+        // [[#Heading]] points into the same note
+        if !target.trim().is_empty() {
+            links.push(ParsedLink {
+                kind,
+                target: target.trim().to_string(),
+                anchor: anchor.trim().to_string(),
+            });
+        }
+
+        rest = &after[end + 2..];
+    }
+}
+
+fn extract_markdown_links(folder_path: &str, line: &str, links: &mut Vec<ParsedLink>) {
+    let mut rest = line;
+    while let Some(start) = rest.find("](") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find(')') else {
+            break;
+        };
+        rest = &after[end + 1..];
+
+        // [text](<path with spaces.md> "title")
+        let raw = after[..end].trim();
+        let raw = if let Some(bracketed) = raw.strip_prefix('<') {
+            bracketed.split('>').next().unwrap_or("")
+        } else {
+            raw.split_whitespace().next().unwrap_or("")
+        };
+
+        if raw.is_empty() || raw.starts_with('#') || raw.contains("://") || raw.starts_with("mailto:") {
+            continue;
+        }
+
+        let decoded = percent_decode(raw);
+        let (path, anchor) = split_anchor(&decoded);
+        let kind = if path.to_lowercase().ends_with(".md") {
+            LINK_KIND_MARKDOWN
+        } else if has_attachment_extension(path) {
+            LINK_KIND_ATTACHMENT
+        } else {
+            continue;
+        };
+
+        if let Some(target) = markdown_target(folder_path, path) {
+            links.push(ParsedLink {
+                kind,
+                target,
+                anchor: anchor.to_string(),
+            });
+        }
+    }
+}
+
+fn has_attachment_extension(target: &str) -> bool {
+    target
+        .rsplit_once('.')
+        .is_some_and(|(_, ext)| ATTACHMENT_EXTENSIONS.contains(&ext.trim().to_lowercase().as_str()))
+}
+
+/// Vault-relative path of a markdown link target written in a note in `folder_path`
+fn markdown_target(folder_path: &str, path: &str) -> Option<String> {
+    match path.strip_prefix('/') {
+        Some(from_root) => normalize_path(from_root),
+        None => normalize_path(&format!("{}{}", folder_path, path)),
+    }
+}
+
+fn split_anchor(target: &str) -> (&str, &str) {
+    match target.find('#') {
+        Some(idx) => (&target[..idx], &target[idx + 1..]),
+        None => (target, ""),
+    }
+}
+
+/// Resolve `.` and `..` segments. Returns None if the path escapes the vault root.
+pub(crate) fn normalize_path(path: &str) -> Option<String> {
+    let mut parts: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            _ => parts.push(segment),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// Decode %XX escapes (e.g. "%20" in markdown links to files with spaces)
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8(out).unwrap_or_else(|_| s.to_string())
+}
+
+// =============================================================================
+// Link Reducers
+// =============================================================================
+
+/// Rebuild the whole link graph from the current notes and attachments
+///
+/// Run once by `run_migrations` for databases that held notes before the index
+/// existed; afterwards every note and attachment reducer keeps `note_link` and `link_target` current.
+#[spacetimedb::reducer]
+pub fn rebuild_link_index(ctx: &ReducerContext) {
+    let link_ids: Vec<u64> = ctx.db.note_link().iter().map(|l| l.id).collect();
+    for id in link_ids {
+        ctx.db.note_link().id().delete(id);
+    }
+    let target_ids: Vec<String> = ctx.db.link_target().iter().map(|t| t.target_id).collect();
+    for id in target_ids {
+        ctx.db.link_target().target_id().delete(&id);
+    }
+
+    let notes: Vec<Note> = ctx.db.note().iter().collect();
+    for note in &notes {
+        ctx.db.link_target().insert(LinkTarget {
+            target_id: note.id.clone(),
+            is_attachment: false,
+            path_key: note.path.to_lowercase(),
+            name_key: link_key(&note.path),
+        });
+    }
+    for attachment in ctx.db.attachment().iter() {
+        ctx.db.link_target().insert(LinkTarget {
+            target_id: attachment.path.clone(),
+            is_attachment: true,
+            path_key: attachment.path.to_lowercase(),
+            name_key: link_key(&attachment.path),
+        });
+    }
+
+    let resolver = LinkResolver::new(ctx);
+    for note in &notes {
+        insert_outgoing_links(ctx, &resolver, note);
+    }
+
+    log::info!("Rebuilt link index for {} notes ({} links)", notes.len(), ctx.db.note_link().count());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wikilinks() {
+        let links = extract_links("", "See [[Note Name]], [[Other#Intro|the intro]] and ![[Diagram]].");
+        let targets: Vec<(&str, &str)> = links.iter().map(|l| (l.target.as_str(), l.anchor.as_str())).collect();
+        assert_eq!(targets, vec![("Note Name", ""), ("Other", "Intro"), ("Diagram", "")]);
+        assert!(links.iter().all(|l| l.kind == LINK_KIND_WIKI));
+    }
+
+    #[test]
+    fn test_markdown_links_relative_to_folder() {
+        let links = extract_links("Projects/Active/", "[a](Plan.md) [b](../Archive/Old%20Plan.md#Summary) [c](/Inbox.md)");
+        let targets: Vec<(&str, &str)> = links.iter().map(|l| (l.target.as_str(), l.anchor.as_str())).collect();
+        assert_eq!(
+            targets,
+            vec![
+                ("Projects/Active/Plan.md", ""),
+                ("Projects/Archive/Old Plan.md", "Summary"),
+                ("Inbox.md", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_skips_external_and_non_markdown() {
+        let links = extract_links("", "[web](https://example.com/a.md) [zip](archive.zip) [sec](#heading)");
+        assert!(links.is_empty());
+    }
+
+    #[test]
+    fn test_attachment_embeds() {
+        let links = extract_links("Projects/", "![[photo.PNG]] ![diagram](img/Flow%20Chart.svg) [spec](../Specs/v2.pdf)");
+        let targets: Vec<&str> = links.iter().map(|l| l.target.as_str()).collect();
+        assert_eq!(targets, vec!["photo.PNG", "Projects/img/Flow Chart.svg", "Specs/v2.pdf"]);
+        assert!(links.iter().all(|l| l.kind == LINK_KIND_ATTACHMENT));
+    }
+
+    #[test]
+    fn test_skips_code() {
+        let links = extract_links("", "```\n[[Hidden]]\n```\nUse `[[Literal]]` but [[Real]]");
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target, "Real");
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("a/./b/../c.md"), Some("a/c.md".to_string()));
+        assert_eq!(normalize_path("../outside.md"), None);
+    }
+
+    #[test]
+    fn test_rewrite_links_keeps_anchor_and_alias() {
+        let content = "See [[Old#Intro|intro]] and [plan](../Old%20Plan.md#Goals \"title\").\n`[[Old]]` stays\n";
+        let rewritten = rewrite_links(content, |kind, written| match (kind, written) {
+            (LINK_KIND_WIKI, "Old") => Some("Archive/New".to_string()),
+            (LINK_KIND_MARKDOWN, "../Old Plan.md") => Some("../Archive/New Plan.md".to_string()),
+            _ => None,
+        });
+        assert_eq!(
+            rewritten,
+            "See [[Archive/New#Intro|intro]] and [plan](../Archive/New%20Plan.md#Goals \"title\").\n`[[Old]]` stays\n"
+        );
+    }
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(relative_path("", "Projects/Plan.md"), "Projects/Plan.md");
+        assert_eq!(relative_path("Projects/Active/", "Projects/Plan.md"), "../Plan.md");
+        assert_eq!(relative_path("Projects/", "Archive/Projects/Plan.md"), "../Archive/Projects/Plan.md");
+        assert_eq!(relative_path("Projects/", "Projects/Plan.md"), "Plan.md");
+    }
+
+    #[test]
+    fn test_link_key() {
+        // Notes, wikilinks and markdown links to the same note share a key
+        assert_eq!(link_key("Projects/Plan.md"), "plan");
+        assert_eq!(link_key("plan"), "plan");
+        assert_eq!(link_key("Archive/PLAN"), "plan");
+        // Attachments keep their extension
+        assert_eq!(link_key("img/Diagram.png"), "diagram.png");
+    }
+}
@@ -1,6 +1,23 @@
 use spacetimedb::{ReducerContext, Table};
 
-use crate::{Note, note};
+use crate::{Note, note, tombstone};
+use crate::revision_reducers::capture_revision;
+use crate::safe_path::encode_path;
+use crate::link_index::{LinkRewriter, index_note_links, remove_note_links, rewrite_moved_links};
+use crate::tag_index::{index_note_tags, remove_note_tags};
+use crate::trash_reducers::{clear_tombstone, purge_expired_trash, trash_note};
+
+// =============================================================================
+// Note Helpers
+// =============================================================================
+
+/// Fail if `path` already belongs to a note other than `id`
+fn ensure_path_free(ctx: &ReducerContext, path: &str, id: &str) -> Result<(), String> {
+    match ctx.db.note().path().find(path.to_string()) {
+        Some(other) if other.id != id => Err(format!("Path '{}' already exists", path)),
+        _ => Ok(()),
+    }
+}
 
 // =============================================================================
 // Note Reducers
@@ -19,21 +36,21 @@ pub fn create_note(
     size: u64,
     created_time: u64,
     modified_time: u64,
-) {
+) -> Result<(), String> {
     // Check if note already exists by ID
     if ctx.db.note().id().find(&id).is_some() {
-        log::warn!("Note already exists with ID: {}", id);
-        return;
+        return Err(format!("Note already exists with ID: {}", id));
     }
 
     // Check if path already exists (unique constraint)
     if ctx.db.note().path().find(&path).is_some() {
-        log::warn!("Note already exists with path: {}", path);
-        return;
+        return Err(format!("Note already exists with path: {}", path));
     }
 
-    ctx.db.note().insert(Note {
+    clear_tombstone(ctx, &id);
+    let note = ctx.db.note().insert(Note {
         id,
+        safe_path: encode_path(&path),
         path: path.clone(),
         name,
         content,
@@ -43,9 +60,13 @@ pub fn create_note(
         size,
         created_time,
         modified_time,
+        version: 1,
         db_updated_at: ctx.timestamp,
     });
+    index_note_tags(ctx, &note);
+    index_note_links(ctx, &note);
     log::info!("Created note: {}", path);
+    Ok(())
 }
 
 /// Update only the content of a note (path stays the same)
@@ -57,170 +78,219 @@ pub fn update_note_content(
     frontmatter: String,
     size: u64,
     modified_time: u64,
-) {
-    if let Some(existing) = ctx.db.note().id().find(&id) {
-        // Only update content-related fields, path remains unchanged
-        ctx.db.note().id().delete(&id);
-        ctx.db.note().insert(Note {
-            id: id.clone(),
-            path: existing.path.clone(),
-            name: existing.name.clone(),
-            content,
-            folder_path: existing.folder_path.clone(),
-            depth: existing.depth,
-            frontmatter,
-            size,
-            created_time: existing.created_time,
-            modified_time,
-            db_updated_at: ctx.timestamp,
-        });
-        log::info!("Updated content for note: {} (ID: {})", existing.path, id);
-    } else {
-        log::warn!("Note not found for content update: {}", id);
-    }
+) -> Result<(), String> {
+    let existing = ctx.db.note().id().find(&id)
+        .ok_or_else(|| format!("Note not found for content update: {}", id))?;
+
+    capture_revision(ctx, &existing);
+
+    // Only update content-related fields, path remains unchanged
+    ctx.db.note().id().delete(&id);
+    let note = ctx.db.note().insert(Note {
+        id: id.clone(),
+        safe_path: encode_path(&existing.path),
+        path: existing.path.clone(),
+        name: existing.name.clone(),
+        content,
+        folder_path: existing.folder_path.clone(),
+        depth: existing.depth,
+        frontmatter,
+        size,
+        created_time: existing.created_time,
+        modified_time,
+        version: existing.version + 1,
+        db_updated_at: ctx.timestamp,
+    });
+    index_note_tags(ctx, &note);
+    index_note_links(ctx, &note);
+    log::info!("Updated content for note: {} (ID: {})", existing.path, id);
+    Ok(())
 }
 
 /// Rename/move a note (path changes, content stays the same)
+///
+/// With `rewrite_links`, links in other notes that would no longer find this
+/// note are updated to its new name/path in the same transaction.
 #[spacetimedb::reducer]
 pub fn rename_note(
     ctx: &ReducerContext,
     id: String,
     new_path: String,
-) {
-    if let Some(existing) = ctx.db.note().id().find(&id) {
-        // Check if new path already exists
-        if let Some(collision) = ctx.db.note().path().find(&new_path) {
-            if collision.id != id {
-                log::error!("Cannot rename: Path '{}' already exists", new_path);
-                return;
-            }
-        }
+    rewrite_links: bool,
+) -> Result<(), String> {
+    let existing = ctx.db.note().id().find(&id)
+        .ok_or_else(|| format!("Note not found for rename: {}", id))?;
 
-        // Calculate new metadata from new path
-        let new_name = new_path
-            .trim_end_matches(".md")
-            .rsplit('/')
-            .next()
-            .unwrap_or(&new_path)
-            .to_string();
+    // Check if new path already exists
+    ensure_path_free(ctx, &new_path, &id).map_err(|e| format!("Cannot rename: {}", e))?;
 
-        let new_folder_path = if let Some(idx) = new_path.rfind('/') {
-            format!("{}/", &new_path[..idx])
-        } else {
-            String::new()
-        };
+    // Calculate new metadata from new path
+    let new_name = new_path
+        .trim_end_matches(".md")
+        .rsplit('/')
+        .next()
+        .unwrap_or(&new_path)
+        .to_string();
 
-        let new_depth = new_path.matches('/').count() as u32;
-
-        ctx.db.note().id().delete(&id);
-        ctx.db.note().insert(Note {
-            id: id.clone(),
-            path: new_path.clone(),
-            name: new_name,
-            content: existing.content,
-            folder_path: new_folder_path,
-            depth: new_depth,
-            frontmatter: existing.frontmatter,
-            size: existing.size,
-            created_time: existing.created_time,
-            modified_time: existing.modified_time,
-            db_updated_at: ctx.timestamp,
-        });
-        log::info!("Renamed note: {} -> {} (ID: {})", existing.path, new_path, id);
+    let new_folder_path = if let Some(idx) = new_path.rfind('/') {
+        format!("{}/", &new_path[..idx])
     } else {
-        log::warn!("Note not found for rename: {}", id);
-    }
+        String::new()
+    };
+
+    let new_depth = new_path.matches('/').count() as u32;
+
+    let rewriter = rewrite_links.then(|| LinkRewriter::prepare(ctx, std::slice::from_ref(&id)));
+
+    capture_revision(ctx, &existing);
+    ctx.db.note().id().delete(&id);
+    let note = ctx.db.note().insert(Note {
+        id: id.clone(),
+        safe_path: encode_path(&new_path),
+        path: new_path.clone(),
+        name: new_name,
+        content: existing.content,
+        folder_path: new_folder_path,
+        depth: new_depth,
+        frontmatter: existing.frontmatter,
+        size: existing.size,
+        created_time: existing.created_time,
+        modified_time: existing.modified_time,
+        version: existing.version + 1,
+        db_updated_at: ctx.timestamp,
+    });
+    index_note_links(ctx, &note);
+    rewrite_moved_links(ctx, rewriter, &new_path);
+    log::info!("Renamed note: {} -> {} (ID: {})", existing.path, new_path, id);
+    Ok(())
 }
 
 #[spacetimedb::reducer]
-pub fn delete_note(ctx: &ReducerContext, id: String) {
-    if ctx.db.note().id().find(&id).is_some() {
-        ctx.db.note().id().delete(&id);
-        log::info!("Deleted note with ID: {}", id);
-    } else {
-        log::warn!("Note not found for deletion: {}", id);
-    }
+pub fn delete_note(ctx: &ReducerContext, id: String) -> Result<(), String> {
+    let Some(existing) = ctx.db.note().id().find(&id) else {
+        // Deleted already (e.g. the same delete, resent after a reconnect)
+        if ctx.db.tombstone().note_id().find(&id).is_some() {
+            log::info!("Note already deleted: {}", id);
+            return Ok(());
+        }
+        return Err(format!("Note not found for deletion: {}", id));
+    };
+
+    // Soft delete: keep the full row in the trash so it can be restored
+    trash_note(ctx, &existing, "");
+    ctx.db.note().id().delete(&id);
+    remove_note_tags(ctx, &id);
+    remove_note_links(ctx, &id);
+    purge_expired_trash(ctx);
+    log::info!("Moved note to trash: {} (ID: {})", existing.path, id);
+    Ok(())
 }
 
 #[spacetimedb::reducer]
-pub fn update_note_path(ctx: &ReducerContext, id: String, new_path: String) {
-    if let Some(existing) = ctx.db.note().id().find(&id) {
-        // Calculate new metadata from new path
-        let new_name = new_path
-            .trim_end_matches(".md")
-            .rsplit('/')
-            .next()
-            .unwrap_or(&new_path)
-            .to_string();
-
-        let new_folder_path = if let Some(idx) = new_path.rfind('/') {
-            format!("{}/", &new_path[..idx])
-        } else {
-            String::new()
-        };
-
-        let new_depth = new_path.matches('/').count() as u32;
+pub fn update_note_path(
+    ctx: &ReducerContext,
+    id: String,
+    new_path: String,
+    rewrite_links: bool,
+) -> Result<(), String> {
+    let existing = ctx.db.note().id().find(&id)
+        .ok_or_else(|| format!("Note not found for path update: {}", id))?;
+    ensure_path_free(ctx, &new_path, &id).map_err(|e| format!("Cannot update path: {}", e))?;
 
-        ctx.db.note().id().delete(&id);
-        ctx.db.note().insert(Note {
-            id: id.clone(),
-            path: new_path.clone(),
-            name: new_name,
-            content: existing.content,
-            folder_path: new_folder_path,
-            depth: new_depth,
-            frontmatter: existing.frontmatter,
-            size: existing.size,
-            created_time: existing.created_time,
-            modified_time: existing.modified_time,
-            db_updated_at: ctx.timestamp,
-        });
-        log::info!("Updated path for note {}: {}", id, new_path);
+    // Calculate new metadata from new path
+    let new_name = new_path
+        .trim_end_matches(".md")
+        .rsplit('/')
+        .next()
+        .unwrap_or(&new_path)
+        .to_string();
+
+    let new_folder_path = if let Some(idx) = new_path.rfind('/') {
+        format!("{}/", &new_path[..idx])
     } else {
-        log::warn!("Note not found for path update: {}", id);
-    }
+        String::new()
+    };
+
+    let new_depth = new_path.matches('/').count() as u32;
+
+    let rewriter = rewrite_links.then(|| LinkRewriter::prepare(ctx, std::slice::from_ref(&id)));
+
+    capture_revision(ctx, &existing);
+    ctx.db.note().id().delete(&id);
+    let note = ctx.db.note().insert(Note {
+        id: id.clone(),
+        safe_path: encode_path(&new_path),
+        path: new_path.clone(),
+        name: new_name,
+        content: existing.content,
+        folder_path: new_folder_path,
+        depth: new_depth,
+        frontmatter: existing.frontmatter,
+        size: existing.size,
+        created_time: existing.created_time,
+        modified_time: existing.modified_time,
+        version: existing.version + 1,
+        db_updated_at: ctx.timestamp,
+    });
+    index_note_links(ctx, &note);
+    rewrite_moved_links(ctx, rewriter, &new_path);
+    log::info!("Updated path for note {}: {}", id, new_path);
+    Ok(())
 }
 
 // DEPRECATED: Use update_note_path instead
 // Kept for backwards compatibility during migration
 #[spacetimedb::reducer]
-pub fn move_note(ctx: &ReducerContext, old_path: String, new_path: String) {
-    if let Some(existing) = ctx.db.note().path().find(&old_path) {
-        // Calculate new metadata
-        let new_name = new_path
-            .trim_end_matches(".md")
-            .rsplit('/')
-            .next()
-            .unwrap_or(&new_path)
-            .to_string();
-
-        let new_folder_path = if let Some(idx) = new_path.rfind('/') {
-            format!("{}/", &new_path[..idx])
-        } else {
-            String::new()
-        };
-
-        let new_depth = new_path.matches('/').count() as u32;
-
-        let id = existing.id.clone();
-        ctx.db.note().id().delete(&id);
-        ctx.db.note().insert(Note {
-            id,
-            path: new_path.clone(),
-            name: new_name,
-            content: existing.content,
-            folder_path: new_folder_path,
-            depth: new_depth,
-            frontmatter: existing.frontmatter,
-            size: existing.size,
-            created_time: existing.created_time,
-            modified_time: existing.modified_time,
-            db_updated_at: ctx.timestamp,
-        });
-        log::info!("Moved note: {} -> {}", old_path, new_path);
+pub fn move_note(
+    ctx: &ReducerContext,
+    old_path: String,
+    new_path: String,
+    rewrite_links: bool,
+) -> Result<(), String> {
+    let existing = ctx.db.note().path().find(&old_path)
+        .ok_or_else(|| format!("Note not found for move: {}", old_path))?;
+    ensure_path_free(ctx, &new_path, &existing.id).map_err(|e| format!("Cannot move: {}", e))?;
+
+    // Calculate new metadata
+    let new_name = new_path
+        .trim_end_matches(".md")
+        .rsplit('/')
+        .next()
+        .unwrap_or(&new_path)
+        .to_string();
+
+    let new_folder_path = if let Some(idx) = new_path.rfind('/') {
+        format!("{}/", &new_path[..idx])
     } else {
-        log::warn!("Note not found for move: {}", old_path);
-    }
+        String::new()
+    };
+
+    let new_depth = new_path.matches('/').count() as u32;
+
+    let id = existing.id.clone();
+    let rewriter = rewrite_links.then(|| LinkRewriter::prepare(ctx, std::slice::from_ref(&id)));
+
+    capture_revision(ctx, &existing);
+    ctx.db.note().id().delete(&id);
+    let note = ctx.db.note().insert(Note {
+        id,
+        safe_path: encode_path(&new_path),
+        path: new_path.clone(),
+        name: new_name,
+        content: existing.content,
+        folder_path: new_folder_path,
+        depth: new_depth,
+        frontmatter: existing.frontmatter,
+        size: existing.size,
+        created_time: existing.created_time,
+        modified_time: existing.modified_time,
+        version: existing.version + 1,
+        db_updated_at: ctx.timestamp,
+    });
+    index_note_links(ctx, &note);
+    rewrite_moved_links(ctx, rewriter, &new_path);
+    log::info!("Moved note: {} -> {}", old_path, new_path);
+    Ok(())
 }
 
 #[spacetimedb::reducer]
@@ -236,13 +306,25 @@ pub fn upsert_note(
     size: u64,
     created_time: u64,
     modified_time: u64,
-) {
+) -> Result<(), String> {
+    // Another note at this path would violate the unique constraint
+    ensure_path_free(ctx, &path, &id)?;
+
     // Delete if exists (by ID), then insert
-    if ctx.db.note().id().find(&id).is_some() {
+    let mut version = 1;
+    if let Some(existing) = ctx.db.note().id().find(&id) {
+        // Keep the previous version if this write changes what the note says or where it is
+        if existing.content != content || existing.frontmatter != frontmatter || existing.path != path {
+            capture_revision(ctx, &existing);
+        }
+        version = existing.version + 1;
         ctx.db.note().id().delete(&id);
+    } else {
+        clear_tombstone(ctx, &id);
     }
-    ctx.db.note().insert(Note {
+    let note = ctx.db.note().insert(Note {
         id,
+        safe_path: encode_path(&path),
         path,
         name,
         content,
@@ -252,64 +334,80 @@ pub fn upsert_note(
         size,
         created_time,
         modified_time,
+        version,
         db_updated_at: ctx.timestamp,
     });
+    index_note_tags(ctx, &note);
+    index_note_links(ctx, &note);
+    Ok(())
 }
 
 /// Append content to an existing note (by path)
 #[spacetimedb::reducer]
-pub fn append_to_note(ctx: &ReducerContext, path: String, content: String) {
-    if let Some(existing) = ctx.db.note().path().find(&path) {
-        let new_content = format!("{}{}", existing.content, content);
-        let new_size = new_content.len() as u64;
-        let now = ctx.timestamp.to_micros_since_unix_epoch() as u64 / 1_000;
-
-        ctx.db.note().id().delete(&existing.id);
-        ctx.db.note().insert(Note {
-            id: existing.id.clone(),
-            path: existing.path,
-            name: existing.name,
-            content: new_content,
-            folder_path: existing.folder_path,
-            depth: existing.depth,
-            frontmatter: existing.frontmatter,
-            size: new_size,
-            created_time: existing.created_time,
-            modified_time: now,
-            db_updated_at: ctx.timestamp,
-        });
-        log::info!("Appended {} bytes to note: {}", content.len(), path);
-    } else {
-        log::warn!("Note not found for append: {}", path);
-    }
+pub fn append_to_note(ctx: &ReducerContext, path: String, content: String) -> Result<(), String> {
+    let existing = ctx.db.note().path().find(&path)
+        .ok_or_else(|| format!("Note not found for append: {}", path))?;
+
+    let new_content = format!("{}{}", existing.content, content);
+    let new_size = new_content.len() as u64;
+    let now = ctx.timestamp.to_micros_since_unix_epoch() as u64 / 1_000;
+
+    capture_revision(ctx, &existing);
+
+    ctx.db.note().id().delete(&existing.id);
+    let note = ctx.db.note().insert(Note {
+        id: existing.id.clone(),
+        safe_path: encode_path(&existing.path),
+        path: existing.path,
+        name: existing.name,
+        content: new_content,
+        folder_path: existing.folder_path,
+        depth: existing.depth,
+        frontmatter: existing.frontmatter,
+        size: new_size,
+        created_time: existing.created_time,
+        modified_time: now,
+        version: existing.version + 1,
+        db_updated_at: ctx.timestamp,
+    });
+    index_note_tags(ctx, &note);
+    index_note_links(ctx, &note);
+    log::info!("Appended {} bytes to note: {}", content.len(), path);
+    Ok(())
 }
 
 /// Prepend content to an existing note (by path)
 #[spacetimedb::reducer]
-pub fn prepend_to_note(ctx: &ReducerContext, path: String, content: String) {
-    if let Some(existing) = ctx.db.note().path().find(&path) {
-        let new_content = format!("{}{}", content, existing.content);
-        let new_size = new_content.len() as u64;
-        let now = ctx.timestamp.to_micros_since_unix_epoch() as u64 / 1_000;
-
-        ctx.db.note().id().delete(&existing.id);
-        ctx.db.note().insert(Note {
-            id: existing.id.clone(),
-            path: existing.path,
-            name: existing.name,
-            content: new_content,
-            folder_path: existing.folder_path,
-            depth: existing.depth,
-            frontmatter: existing.frontmatter,
-            size: new_size,
-            created_time: existing.created_time,
-            modified_time: now,
-            db_updated_at: ctx.timestamp,
-        });
-        log::info!("Prepended {} bytes to note: {}", content.len(), path);
-    } else {
-        log::warn!("Note not found for prepend: {}", path);
-    }
+pub fn prepend_to_note(ctx: &ReducerContext, path: String, content: String) -> Result<(), String> {
+    let existing = ctx.db.note().path().find(&path)
+        .ok_or_else(|| format!("Note not found for prepend: {}", path))?;
+
+    let new_content = format!("{}{}", content, existing.content);
+    let new_size = new_content.len() as u64;
+    let now = ctx.timestamp.to_micros_since_unix_epoch() as u64 / 1_000;
+
+    capture_revision(ctx, &existing);
+
+    ctx.db.note().id().delete(&existing.id);
+    let note = ctx.db.note().insert(Note {
+        id: existing.id.clone(),
+        safe_path: encode_path(&existing.path),
+        path: existing.path,
+        name: existing.name,
+        content: new_content,
+        folder_path: existing.folder_path,
+        depth: existing.depth,
+        frontmatter: existing.frontmatter,
+        size: new_size,
+        created_time: existing.created_time,
+        modified_time: now,
+        version: existing.version + 1,
+        db_updated_at: ctx.timestamp,
+    });
+    index_note_tags(ctx, &note);
+    index_note_links(ctx, &note);
+    log::info!("Prepended {} bytes to note: {}", content.len(), path);
+    Ok(())
 }
 
 /// Find and replace text in a note (by path)
@@ -320,39 +418,133 @@ pub fn find_replace_in_note(
     old_text: String,
     new_text: String,
     replace_all: bool,
-) {
-    if let Some(existing) = ctx.db.note().path().find(&path) {
-        let new_content = if replace_all {
-            existing.content.replace(&old_text, &new_text)
-        } else {
-            existing.content.replacen(&old_text, &new_text, 1)
-        };
-
-        // Check if anything changed
-        if new_content == existing.content {
-            log::warn!("No match found for replacement in note: {}", path);
-            return;
-        }
+) -> Result<(), String> {
+    let existing = ctx.db.note().path().find(&path)
+        .ok_or_else(|| format!("Note not found for find/replace: {}", path))?;
 
-        let new_size = new_content.len() as u64;
-        let now = ctx.timestamp.to_micros_since_unix_epoch() as u64 / 1_000;
-
-        ctx.db.note().id().delete(&existing.id);
-        ctx.db.note().insert(Note {
-            id: existing.id.clone(),
-            path: existing.path,
-            name: existing.name,
-            content: new_content,
-            folder_path: existing.folder_path,
-            depth: existing.depth,
-            frontmatter: existing.frontmatter,
-            size: new_size,
-            created_time: existing.created_time,
-            modified_time: now,
-            db_updated_at: ctx.timestamp,
-        });
-        log::info!("Replaced text in note: {}", path);
+    let new_content = if replace_all {
+        existing.content.replace(&old_text, &new_text)
     } else {
-        log::warn!("Note not found for find/replace: {}", path);
+        existing.content.replacen(&old_text, &new_text, 1)
+    };
+
+    // Check if anything changed
+    if new_content == existing.content {
+        return Err(format!("No match found for replacement in note: {}", path));
+    }
+
+    let new_size = new_content.len() as u64;
+    let now = ctx.timestamp.to_micros_since_unix_epoch() as u64 / 1_000;
+
+    capture_revision(ctx, &existing);
+
+    ctx.db.note().id().delete(&existing.id);
+    let note = ctx.db.note().insert(Note {
+        id: existing.id.clone(),
+        safe_path: encode_path(&existing.path),
+        path: existing.path,
+        name: existing.name,
+        content: new_content,
+        folder_path: existing.folder_path,
+        depth: existing.depth,
+        frontmatter: existing.frontmatter,
+        size: new_size,
+        created_time: existing.created_time,
+        modified_time: now,
+        version: existing.version + 1,
+        db_updated_at: ctx.timestamp,
+    });
+    index_note_tags(ctx, &note);
+    index_note_links(ctx, &note);
+    log::info!("Replaced text in note: {}", path);
+    Ok(())
+}
+
+// =============================================================================
+// Versioned Writes (optimistic concurrency)
+// =============================================================================
+
+/// Reject a write that was prepared against an older version of the note
+fn check_version(path: &str, current_version: u64, expected_version: u64) -> Result<(), String> {
+    if current_version != expected_version {
+        return Err(format!(
+            "Version conflict on '{}': expected version {}, but it is at version {}. Re-read the note and retry.",
+            path, expected_version, current_version
+        ));
+    }
+    Ok(())
+}
+
+/// `upsert_note` that only applies if the note is still at `expected_version`
+///
+/// An `expected_version` of 0 means the caller believes the note doesn't exist yet.
+#[spacetimedb::reducer]
+#[allow(clippy::too_many_arguments)]
+pub fn upsert_note_if_version(
+    ctx: &ReducerContext,
+    id: String,
+    path: String,
+    name: String,
+    content: String,
+    folder_path: String,
+    depth: u32,
+    frontmatter: String,
+    size: u64,
+    created_time: u64,
+    modified_time: u64,
+    expected_version: u64,
+) -> Result<(), String> {
+    let existing = ctx.db.note().id().find(&id);
+
+    // Resent after a reconnect, but the server applied it before the connection dropped
+    if let Some(note) = &existing {
+        let applied = note.version == expected_version + 1
+            && note.path == path
+            && note.content == content
+            && note.frontmatter == frontmatter;
+        if applied {
+            log::info!("Upload of {} already applied at version {}", path, note.version);
+            return Ok(());
+        }
     }
+
+    let current_version = existing.map_or(0, |n| n.version);
+    check_version(&path, current_version, expected_version)?;
+
+    upsert_note(ctx, id, path, name, content, folder_path, depth, frontmatter, size, created_time, modified_time)
+}
+
+/// `update_note_content` that only applies if the note is still at `expected_version`
+#[spacetimedb::reducer]
+pub fn update_note_content_if_version(
+    ctx: &ReducerContext,
+    id: String,
+    content: String,
+    frontmatter: String,
+    size: u64,
+    modified_time: u64,
+    expected_version: u64,
+) -> Result<(), String> {
+    let existing = ctx.db.note().id().find(&id)
+        .ok_or_else(|| format!("Note not found: {}", id))?;
+    check_version(&existing.path, existing.version, expected_version)?;
+
+    update_note_content(ctx, id, content, frontmatter, size, modified_time)
+}
+
+/// `find_replace_in_note` that only applies if the note is still at `expected_version`
+#[spacetimedb::reducer]
+pub fn find_replace_in_note_if_version(
+    ctx: &ReducerContext,
+    path: String,
+    old_text: String,
+    new_text: String,
+    replace_all: bool,
+    expected_version: u64,
+) -> Result<(), String> {
+    let existing = ctx.db.note().path().find(&path)
+        .ok_or_else(|| format!("Note not found: {}", path))?;
+    check_version(&existing.path, existing.version, expected_version)?;
+
+    find_replace_in_note(ctx, path, old_text, new_text, replace_all)
 }
@@ -0,0 +1,144 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::{Note, NoteRevision, note, note_revision};
+use crate::link_index::index_note_links;
+use crate::safe_path::encode_path;
+use crate::tag_index::index_note_tags;
+
+/// Maximum number of revisions kept per note (oldest are pruned first)
+const MAX_REVISIONS_PER_NOTE: usize = 50;
+
+/// Revisions older than this are pruned regardless of count (30 days)
+const MAX_REVISION_AGE_MICROS: i64 = 30 * 24 * 60 * 60 * 1_000_000;
+
+// =============================================================================
+// Revision Helpers
+// =============================================================================
+
+/// Capture the current state of a note before it is overwritten.
+///
+/// Called by every reducer that changes a note's content or path (renames, moves and
+/// the `move_folder` cascade included) right before the delete+insert.
+/// Also prunes revisions of the same note that exceed the retention limits.
+pub(crate) fn capture_revision(ctx: &ReducerContext, existing: &Note) {
+    ctx.db.note_revision().insert(NoteRevision {
+        id: 0, // auto_inc
+        note_id: existing.id.clone(),
+        path: existing.path.clone(),
+        content: existing.content.clone(),
+        frontmatter: existing.frontmatter.clone(),
+        size: existing.size,
+        modified_time: existing.modified_time,
+        captured_at: ctx.timestamp,
+    });
+
+    prune_revisions(ctx, &existing.id);
+}
+
+/// Drop revisions of every note that are past the age limit
+///
+/// `prune_revisions` only sees notes that are written again, so revisions of deleted
+/// or untouched notes are swept here, on each client connection.
+pub(crate) fn purge_expired_revisions(ctx: &ReducerContext) {
+    let now = ctx.timestamp.to_micros_since_unix_epoch();
+
+    let expired: Vec<u64> = ctx
+        .db
+        .note_revision()
+        .iter()
+        .filter(|r| now - r.captured_at.to_micros_since_unix_epoch() > MAX_REVISION_AGE_MICROS)
+        .map(|r| r.id)
+        .collect();
+
+    for id in &expired {
+        ctx.db.note_revision().id().delete(id);
+    }
+
+    if !expired.is_empty() {
+        log::info!("Purged {} expired revisions", expired.len());
+    }
+}
+
+/// Drop revisions of a note that are too old or beyond the per-note limit
+fn prune_revisions(ctx: &ReducerContext, note_id: &String) {
+    let now = ctx.timestamp.to_micros_since_unix_epoch();
+
+    let mut revisions: Vec<NoteRevision> = ctx.db.note_revision().note_id().filter(note_id).collect();
+
+    // Newest first, so everything past the limit is the oldest
+    revisions.sort_by(|a, b| b.captured_at.cmp(&a.captured_at).then(b.id.cmp(&a.id)));
+
+    let mut pruned = 0;
+    for (i, revision) in revisions.iter().enumerate() {
+        let age = now - revision.captured_at.to_micros_since_unix_epoch();
+        if i >= MAX_REVISIONS_PER_NOTE || age > MAX_REVISION_AGE_MICROS {
+            ctx.db.note_revision().id().delete(revision.id);
+            pruned += 1;
+        }
+    }
+
+    if pruned > 0 {
+        log::info!("Pruned {} old revisions for note: {}", pruned, note_id);
+    }
+}
+
+// =============================================================================
+// Revision Reducers
+// =============================================================================
+
+/// Restore a note's content and frontmatter from a previous revision
+///
+/// The note keeps its current path; a revision's `path` records where it was, so a
+/// move is undone by moving it back. The content being replaced is itself captured
+/// as a revision, so a restore can be undone.
+#[spacetimedb::reducer]
+pub fn restore_note_revision(ctx: &ReducerContext, revision_id: u64) -> Result<(), String> {
+    let revision = ctx.db.note_revision().id().find(revision_id)
+        .ok_or_else(|| format!("Revision not found for restore: {}", revision_id))?;
+
+    let existing = ctx.db.note().id().find(&revision.note_id)
+        .ok_or_else(|| format!("Note {} no longer exists, cannot restore revision {}", revision.note_id, revision_id))?;
+
+    capture_revision(ctx, &existing);
+
+    let now = ctx.timestamp.to_micros_since_unix_epoch() as u64 / 1_000;
+
+    ctx.db.note().id().delete(&existing.id);
+    let note = ctx.db.note().insert(Note {
+        id: existing.id.clone(),
+        safe_path: encode_path(&existing.path),
+        path: existing.path.clone(),
+        name: existing.name,
+        content: revision.content,
+        folder_path: existing.folder_path,
+        depth: existing.depth,
+        frontmatter: revision.frontmatter,
+        size: revision.size,
+        created_time: existing.created_time,
+        modified_time: now,
+        version: existing.version + 1,
+        db_updated_at: ctx.timestamp,
+    });
+    index_note_tags(ctx, &note);
+    index_note_links(ctx, &note);
+    log::info!("Restored note {} to revision {}", existing.path, revision_id);
+    Ok(())
+}
+
+/// List the stored revisions of a note, newest first
+///
+/// Like `get_recent_notes`, this has no side effects and reports via log output.
+/// Clients that need the data should subscribe to the `note_revision` table.
+#[spacetimedb::reducer]
+pub fn list_note_revisions(ctx: &ReducerContext, note_id: String) {
+    let mut revisions: Vec<NoteRevision> = ctx.db.note_revision().note_id().filter(&note_id).collect();
+
+    revisions.sort_by(|a, b| b.captured_at.cmp(&a.captured_at).then(b.id.cmp(&a.id)));
+
+    for revision in revisions {
+        log::info!(
+            "Revision {}: {} ({} bytes, captured: {:?})",
+            revision.id, revision.path, revision.size, revision.captured_at
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::{Note, note};
+
+/// A note path in a form that's safe to put in a URI: every byte outside the
+/// unreserved set (and `/`) becomes `%XX`. Unlike the ASCII replacement the
+/// daemon used to apply, this is lossless: URI-decoding gives back `path`.
+pub(crate) fn encode_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+/// Set `safe_path` on notes written before the column existed (or whose path changed
+/// without it), so clients can rely on it
+///
+/// Run once by `run_migrations`, since the column has no `#[default]` to fill it (see `Note`).
+#[spacetimedb::reducer]
+pub fn repair_safe_paths(ctx: &ReducerContext) {
+    let stale: Vec<Note> = ctx
+        .db
+        .note()
+        .iter()
+        .filter(|note| note.safe_path != encode_path(&note.path))
+        .collect();
+
+    for existing in &stale {
+        ctx.db.note().id().update(Note { safe_path: encode_path(&existing.path), ..existing.clone() });
+    }
+
+    log::info!("Safe paths repaired: {} notes fixed", stale.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_path_unchanged() {
+        assert_eq!(encode_path("Projects/my-note_v2.md"), "Projects/my-note_v2.md");
+    }
+
+    #[test]
+    fn test_unicode_and_reserved_characters() {
+        assert_eq!(encode_path("Café ☕.md"), "Caf%C3%A9%20%E2%98%95.md");
+        assert_eq!(encode_path("Q&A #1 (50%).md"), "Q%26A%20%231%20%2850%25%29.md");
+    }
+
+    #[test]
+    fn test_distinct_names_stay_distinct() {
+        assert_ne!(encode_path("Café ☕.md"), encode_path("Caf_ _.md"));
+    }
+}
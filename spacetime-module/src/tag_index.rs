@@ -0,0 +1,175 @@
+use std::collections::BTreeSet;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::{Note, NoteTag, note, note_tag};
+
+// =============================================================================
+// Tag Index
+// =============================================================================
+
+/// Rebuild the `note_tag` rows for a note from its frontmatter and body
+///
+/// Called after every insert of a note row whose content or frontmatter may have changed.
+pub(crate) fn index_note_tags(ctx: &ReducerContext, note: &Note) {
+    remove_note_tags(ctx, &note.id);
+
+    for tag in extract_tags(&note.frontmatter, &note.content) {
+        ctx.db.note_tag().insert(NoteTag {
+            id: 0, // auto_inc
+            note_id: note.id.clone(),
+            tag,
+        });
+    }
+}
+
+/// Drop all `note_tag` rows of a note (e.g. when it is deleted)
+pub(crate) fn remove_note_tags(ctx: &ReducerContext, note_id: &String) {
+    ctx.db.note_tag().note_id().delete(note_id);
+}
+
+/// Collect the normalized tags of a note: frontmatter `tags:`/`tag:` plus inline `#tags`
+///
+/// Tags are lowercased and stored without the leading `#`, so `#Project` and
+/// `tags: [project]` index to the same value.
+pub(crate) fn extract_tags(frontmatter: &str, content: &str) -> BTreeSet<String> {
+    let mut tags = BTreeSet::new();
+
+    if let Ok(serde_json::Value::Object(map)) = serde_json::from_str::<serde_json::Value>(frontmatter) {
+        for key in ["tags", "tag"] {
+            match map.get(key) {
+                Some(serde_json::Value::Array(values)) => {
+                    for value in values {
+                        match value {
+                            serde_json::Value::String(s) => insert_tag(&mut tags, s),
+                            serde_json::Value::Number(n) => insert_tag(&mut tags, &n.to_string()),
+                            _ => {}
+                        }
+                    }
+                }
+                // "tags: a, b" and "tags: a b" are both common in the wild
+                Some(serde_json::Value::String(s)) => {
+                    for part in s.split(|c: char| c == ',' || c.is_whitespace()) {
+                        insert_tag(&mut tags, part);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut in_code_block = false;
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        extract_inline_tags(line, &mut tags);
+    }
+
+    tags
+}
+
+/// Find `#tag` tokens in a single line, skipping inline code spans
+fn extract_inline_tags(line: &str, tags: &mut BTreeSet<String>) {
+    let chars: Vec<char> = line.chars().collect();
+    let mut in_code = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '`' {
+            in_code = !in_code;
+        } else if c == '#' && !in_code && (i == 0 || chars[i - 1].is_whitespace()) {
+            let start = i + 1;
+            let mut end = start;
+            while end < chars.len() && is_tag_char(chars[end]) {
+                end += 1;
+            }
+            let tag: String = chars[start..end].iter().collect();
+            // Headings ("# Title") have no tag chars; "#123" is an issue ref, not a tag
+            if tag.chars().any(|c| !c.is_ascii_digit()) {
+                insert_tag(tags, &tag);
+            }
+            i = end;
+            continue;
+        }
+        i += 1;
+    }
+}
+
+fn is_tag_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || c == '/'
+}
+
+fn insert_tag(tags: &mut BTreeSet<String>, raw: &str) {
+    let tag = raw.trim().trim_start_matches('#').trim_end_matches('/').to_lowercase();
+    if !tag.is_empty() {
+        tags.insert(tag);
+    }
+}
+
+// =============================================================================
+// Tag Reducers
+// =============================================================================
+
+/// Rebuild the whole tag index from the current notes
+///
+/// Run once by `run_migrations` for databases that held notes before the index
+/// existed; afterwards every note reducer keeps `note_tag` current.
+#[spacetimedb::reducer]
+pub fn rebuild_tag_index(ctx: &ReducerContext) {
+    let tag_ids: Vec<u64> = ctx.db.note_tag().iter().map(|t| t.id).collect();
+    for id in tag_ids {
+        ctx.db.note_tag().id().delete(id);
+    }
+
+    let notes: Vec<Note> = ctx.db.note().iter().collect();
+    for note in &notes {
+        index_note_tags(ctx, note);
+    }
+
+    log::info!("Rebuilt tag index for {} notes ({} tags)", notes.len(), ctx.db.note_tag().count());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frontmatter_tag_list() {
+        let tags = extract_tags(r##"{"tags":["Project","#work",2024]}"##, "");
+        let expected: BTreeSet<String> = ["project", "work", "2024"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(tags, expected);
+    }
+
+    #[test]
+    fn test_frontmatter_tag_string() {
+        let tags = extract_tags(r#"{"tags":"alpha, beta gamma"}"#, "");
+        assert_eq!(tags.len(), 3);
+        assert!(tags.contains("gamma"));
+    }
+
+    #[test]
+    fn test_inline_tags() {
+        let tags = extract_tags("{}", "# Heading\nSome #idea and #area/sub-topic.\nIssue #42");
+        let expected: BTreeSet<String> = ["idea", "area/sub-topic"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(tags, expected);
+    }
+
+    #[test]
+    fn test_ignores_code() {
+        let tags = extract_tags("{}", "```\n#include <stdio.h>\n```\nUse `#define` here, #real");
+        let expected: BTreeSet<String> = ["real"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(tags, expected);
+    }
+
+    #[test]
+    fn test_ignores_mid_word_hash() {
+        let tags = extract_tags("{}", "C# and url.com/#anchor");
+        assert!(tags.is_empty());
+    }
+}
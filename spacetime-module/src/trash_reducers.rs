@@ -0,0 +1,284 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::{Attachment, Folder, Note, Tombstone, TrashEntry, attachment, folder, note, tombstone, trash};
+use crate::attachment_reducers::release_chunks;
+use crate::folder_reducers::ensure_folder;
+use crate::link_index::{index_note_links, refresh_attachment_links};
+use crate::tag_index::index_note_tags;
+
+/// Trash entries older than this are purged automatically (30 days)
+const TRASH_RETENTION_MICROS: i64 = 30 * 24 * 60 * 60 * 1_000_000;
+
+/// Tombstones outlive the trash, so a device can stay offline this long (90 days)
+/// and still learn about deletions when it comes back
+const TOMBSTONE_RETENTION_MICROS: i64 = 90 * 24 * 60 * 60 * 1_000_000;
+
+// =============================================================================
+// Trash Helpers
+// =============================================================================
+
+/// Move a note row into the trash. The caller is responsible for deleting it from `note`.
+///
+/// `cascade_root` is the folder whose deletion took this note with it (empty for direct deletes).
+pub(crate) fn trash_note(ctx: &ReducerContext, existing: &Note, cascade_root: &str) {
+    ctx.db.trash().insert(TrashEntry {
+        id: 0, // auto_inc
+        original_path: existing.path.clone(),
+        deleted_at: ctx.timestamp,
+        deleted_by: ctx.sender,
+        cascade_root: cascade_root.to_string(),
+        note: Some(existing.clone()),
+        folder: None,
+        attachment: None,
+    });
+
+    // Replace any older tombstone for this ID (deleted, restored, deleted again)
+    ctx.db.tombstone().note_id().delete(&existing.id);
+    ctx.db.tombstone().insert(Tombstone {
+        note_id: existing.id.clone(),
+        path: existing.path.clone(),
+        deleted_at: ctx.timestamp,
+        origin: ctx.sender,
+    });
+}
+
+/// Forget a deletion because the note exists again (restored or re-created with its ID)
+pub(crate) fn clear_tombstone(ctx: &ReducerContext, note_id: &String) {
+    ctx.db.tombstone().note_id().delete(note_id);
+}
+
+/// Move a folder row into the trash. The caller is responsible for deleting it from `folder`.
+pub(crate) fn trash_folder(ctx: &ReducerContext, existing: &Folder, cascade_root: &str) {
+    ctx.db.trash().insert(TrashEntry {
+        id: 0, // auto_inc
+        original_path: existing.path.clone(),
+        deleted_at: ctx.timestamp,
+        deleted_by: ctx.sender,
+        cascade_root: cascade_root.to_string(),
+        note: None,
+        folder: Some(existing.clone()),
+        attachment: None,
+    });
+}
+
+/// Move an attachment row into the trash. The caller is responsible for deleting it from `attachment`.
+///
+/// Its chunks stay stored for as long as the trash entry exists.
+pub(crate) fn trash_attachment(ctx: &ReducerContext, existing: &Attachment, cascade_root: &str) {
+    ctx.db.trash().insert(TrashEntry {
+        id: 0, // auto_inc
+        original_path: existing.path.clone(),
+        deleted_at: ctx.timestamp,
+        deleted_by: ctx.sender,
+        cascade_root: cascade_root.to_string(),
+        note: None,
+        folder: None,
+        attachment: Some(existing.clone()),
+    });
+}
+
+/// Whether a directly deleted folder (or attachment) at `path` is in the trash, i.e. a
+/// delete of it is one that was applied already
+pub(crate) fn deleted_directly(ctx: &ReducerContext, path: &str, folder: bool) -> bool {
+    ctx.db
+        .trash()
+        .original_path()
+        .filter(path)
+        .any(|e| e.cascade_root.is_empty() && if folder { e.folder.is_some() } else { e.attachment.is_some() })
+}
+
+/// Permanently drop trash entries that are past the retention window
+pub(crate) fn purge_expired_trash(ctx: &ReducerContext) {
+    let now = ctx.timestamp.to_micros_since_unix_epoch();
+
+    let expired: Vec<TrashEntry> = ctx
+        .db
+        .trash()
+        .iter()
+        .filter(|entry| now - entry.deleted_at.to_micros_since_unix_epoch() > TRASH_RETENTION_MICROS)
+        .collect();
+
+    for entry in &expired {
+        ctx.db.trash().id().delete(entry.id);
+    }
+    for entry in &expired {
+        if let Some(attachment) = &entry.attachment {
+            release_chunks(ctx, &attachment.hash);
+        }
+    }
+
+    if !expired.is_empty() {
+        log::info!("Purged {} expired trash entries", expired.len());
+    }
+}
+
+/// Drop tombstones that are past the retention window
+pub(crate) fn purge_expired_tombstones(ctx: &ReducerContext) {
+    let now = ctx.timestamp.to_micros_since_unix_epoch();
+
+    let expired: Vec<String> = ctx
+        .db
+        .tombstone()
+        .iter()
+        .filter(|t| now - t.deleted_at.to_micros_since_unix_epoch() > TOMBSTONE_RETENTION_MICROS)
+        .map(|t| t.note_id)
+        .collect();
+
+    for note_id in &expired {
+        ctx.db.tombstone().note_id().delete(note_id);
+    }
+
+    if !expired.is_empty() {
+        log::info!("Purged {} expired tombstones", expired.len());
+    }
+}
+
+/// Whether restoring the trashed folder `folder_entry` brings `entry` back too: it was
+/// trashed by the same `delete_folder` call and sits below the folder
+pub(crate) fn restores_with(folder_entry: &TrashEntry, entry: &TrashEntry) -> bool {
+    let cascade_root = if folder_entry.cascade_root.is_empty() {
+        &folder_entry.original_path
+    } else {
+        &folder_entry.cascade_root
+    };
+    let below = entry
+        .original_path
+        .strip_prefix(folder_entry.original_path.as_str())
+        .is_some_and(|rest| rest.starts_with('/'));
+
+    entry.cascade_root == *cascade_root && entry.deleted_at == folder_entry.deleted_at && below
+}
+
+/// Put a trashed note back into the `note` table
+fn restore_note_row(ctx: &ReducerContext, trashed: Note) -> Result<(), String> {
+    if ctx.db.note().id().find(&trashed.id).is_some() {
+        return Err(format!("Cannot restore: Note already exists with ID: {}", trashed.id));
+    }
+
+    if ctx.db.note().path().find(&trashed.path).is_some() {
+        return Err(format!("Cannot restore: Path '{}' is now taken by another note", trashed.path));
+    }
+
+    clear_tombstone(ctx, &trashed.id);
+    let note = ctx.db.note().insert(Note {
+        version: trashed.version + 1,
+        db_updated_at: ctx.timestamp,
+        ..trashed
+    });
+    index_note_tags(ctx, &note);
+    index_note_links(ctx, &note);
+    Ok(())
+}
+
+/// Put a trashed attachment back (fails if its path is taken by a newer attachment)
+fn restore_attachment_row(ctx: &ReducerContext, trashed: Attachment) -> Result<(), String> {
+    if ctx.db.attachment().path().find(&trashed.path).is_some() {
+        return Err(format!("Cannot restore: Path '{}' is now taken by another attachment", trashed.path));
+    }
+
+    let attachment = ctx.db.attachment().insert(Attachment {
+        db_updated_at: ctx.timestamp,
+        ..trashed
+    });
+    refresh_attachment_links(ctx, &attachment.path);
+    Ok(())
+}
+
+/// Put a trashed folder back into the `folder` table (no-op if it was recreated since)
+fn restore_folder_row(ctx: &ReducerContext, trashed: Folder) {
+    if ctx.db.folder().path().find(&trashed.path).is_none() {
+        ctx.db.folder().insert(trashed);
+    }
+}
+
+// =============================================================================
+// Trash Reducers
+// =============================================================================
+
+/// Restore an entry from the trash
+///
+/// Restoring a folder also restores every subfolder and note under it that was
+/// trashed by the same `delete_folder` cascade, whether the folder was the one
+/// deleted or a subfolder taken with it (whose missing parents are recreated).
+#[spacetimedb::reducer]
+pub fn restore_from_trash(ctx: &ReducerContext, id: u64) -> Result<(), String> {
+    let entry = ctx.db.trash().id().find(id)
+        .ok_or_else(|| format!("Trash entry not found for restore: {}", id))?;
+
+    if let Some(trashed_note) = entry.note {
+        restore_note_row(ctx, trashed_note)?;
+        ctx.db.trash().id().delete(id);
+        log::info!("Restored note from trash: {}", entry.original_path);
+        return Ok(());
+    }
+
+    if let Some(trashed_attachment) = entry.attachment {
+        restore_attachment_row(ctx, trashed_attachment)?;
+        ctx.db.trash().id().delete(id);
+        log::info!("Restored attachment from trash: {}", entry.original_path);
+        return Ok(());
+    }
+
+    let Some(trashed_folder) = entry.folder.clone() else {
+        return Err(format!("Trash entry {} holds neither a note, a folder nor an attachment", id));
+    };
+
+    ensure_folder(ctx, &trashed_folder.parent_path);
+    restore_folder_row(ctx, trashed_folder);
+    ctx.db.trash().id().delete(id);
+
+    // CASCADE: Restore everything under this folder that was trashed by the same delete
+    let cascaded: Vec<TrashEntry> = ctx
+        .db
+        .trash()
+        .iter()
+        .filter(|e| restores_with(&entry, e))
+        .collect();
+
+    let mut restored_notes = 0;
+    let mut restored_folders = 0;
+    let mut restored_attachments = 0;
+    // Rows that can't come back (e.g. their path was taken since) stay in the trash,
+    // rather than failing the restore of everything else
+    for cascaded_entry in cascaded {
+        if let Some(trashed_note) = cascaded_entry.note {
+            if let Err(e) = restore_note_row(ctx, trashed_note) {
+                log::warn!("{}", e);
+                continue;
+            }
+            restored_notes += 1;
+        } else if let Some(trashed_attachment) = cascaded_entry.attachment {
+            if let Err(e) = restore_attachment_row(ctx, trashed_attachment) {
+                log::warn!("{}", e);
+                continue;
+            }
+            restored_attachments += 1;
+        } else if let Some(trashed_folder) = cascaded_entry.folder {
+            restore_folder_row(ctx, trashed_folder);
+            restored_folders += 1;
+        }
+        ctx.db.trash().id().delete(cascaded_entry.id);
+    }
+
+    log::info!("Restored folder from trash: {} (with {} notes, {} subfolders, {} attachments)",
+               entry.original_path, restored_notes, restored_folders, restored_attachments);
+    Ok(())
+}
+
+/// Permanently delete everything in the trash
+#[spacetimedb::reducer]
+pub fn empty_trash(ctx: &ReducerContext) -> Result<(), String> {
+    let entries: Vec<TrashEntry> = ctx.db.trash().iter().collect();
+    for entry in &entries {
+        ctx.db.trash().id().delete(entry.id);
+    }
+
+    // Only now that no trash entry references them can chunks be released
+    for entry in &entries {
+        if let Some(attachment) = &entry.attachment {
+            release_chunks(ctx, &attachment.hash);
+        }
+    }
+    log::info!("Emptied trash ({} entries)", entries.len());
+    Ok(())
+}
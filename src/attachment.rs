@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// Bytes per `attachment_chunk` row (keeps each reducer call well under message limits)
+pub const CHUNK_SIZE: usize = 256 * 1024;
+
+/// Files larger than this are not synced
+pub const MAX_ATTACHMENT_SIZE: u64 = 100 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attachment {
+    pub path: String,
+    pub name: String,
+    pub folder_path: String,
+    pub hash: String,
+    pub mime_type: String,
+    pub size: u64,
+    pub modified_time: u64,
+}
+
+impl Attachment {
+    pub fn new(path: String, hash: String, size: u64, modified_time: u64) -> Self {
+        let name = path.rsplit('/').next().unwrap_or("").to_string();
+
+        let folder_path = match path.rfind('/') {
+            Some(idx) => format!("{}/", &path[..idx]),
+            None => String::new(),
+        };
+
+        let mime_type = mime_type_for(&path).to_string();
+
+        Self {
+            path,
+            name,
+            folder_path,
+            hash,
+            mime_type,
+            size,
+            modified_time,
+        }
+    }
+}
+
+/// Non-markdown files with an extension are attachments, except our own temp files
+pub fn is_attachment_path(path: &Path) -> bool {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => !ext.eq_ignore_ascii_case("md") && !ext.eq_ignore_ascii_case("tmp"),
+        None => false,
+    }
+}
+
+/// SHA-256 of raw file bytes (hex), the attachment's content address on the server
+pub fn hash_bytes(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hex::encode(hasher.finalize())
+}
+
+/// Whether `hash` looks like one `hash_bytes` made (the server refuses anything else)
+pub fn is_valid_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+pub fn mime_type_for(path: &str) -> &'static str {
+    let ext = path.rsplit_once('.').map(|(_, e)| e.to_lowercase()).unwrap_or_default();
+
+    match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "avif" => "image/avif",
+        "heic" => "image/heic",
+        "pdf" => "application/pdf",
+        "mp3" => "audio/mpeg",
+        "m4a" => "audio/mp4",
+        "wav" => "audio/wav",
+        "ogg" => "audio/ogg",
+        "mp4" => "video/mp4",
+        "mov" => "video/quicktime",
+        "webm" => "video/webm",
+        "txt" => "text/plain",
+        "csv" => "text/csv",
+        "json" => "application/json",
+        "zip" => "application/zip",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_own_hashes_are_valid() {
+        assert!(is_valid_hash(&hash_bytes(b"")));
+        assert!(is_valid_hash(&hash_bytes(b"image bytes")));
+    }
+
+    #[test]
+    fn test_hash_that_could_break_a_query_is_invalid() {
+        assert!(!is_valid_hash("abc"));
+        assert!(!is_valid_hash(&format!("{}' OR '1'='1", "a".repeat(52))));
+    }
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Last-synced body of every note, kept under `<vault>/.spacenotes/base/`
+///
+/// This is the common ancestor for three-way merges when a note was edited both
+/// locally and on the server while the daemon wasn't running.
+#[derive(Clone)]
+pub struct BaseStore {
+    dir: PathBuf,
+    read_only: bool,
+}
+
+impl BaseStore {
+    pub fn open(vault_path: &Path) -> Result<Self> {
+        let dir = vault_path.join(".spacenotes").join("base");
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir, read_only: false })
+    }
+
+    /// Bases can be loaded, but saving and removing them does nothing
+    pub fn open_read_only(vault_path: &Path) -> Self {
+        Self { dir: vault_path.join(".spacenotes").join("base"), read_only: true }
+    }
+
+    /// IDs come from frontmatter, so they're hashed rather than used as file names
+    fn file_for(&self, id: &str) -> PathBuf {
+        let mut hasher = Sha256::new();
+        hasher.update(id.as_bytes());
+        self.dir.join(hex::encode(hasher.finalize()))
+    }
+
+    pub fn load(&self, id: &str) -> Option<String> {
+        std::fs::read_to_string(self.file_for(id)).ok()
+    }
+
+    pub fn save(&self, id: &str, content: &str) {
+        if self.read_only {
+            return;
+        }
+        let path = self.file_for(id);
+        let tmp_path = path.with_extension("tmp");
+
+        let result = std::fs::write(&tmp_path, content).and_then(|_| std::fs::rename(&tmp_path, &path));
+        if let Err(e) = result {
+            tracing::warn!("Failed to save merge base for {}: {}", id, e);
+        }
+    }
+
+    pub fn remove(&self, id: &str) {
+        if self.read_only {
+            return;
+        }
+        let _ = std::fs::remove_file(self.file_for(id));
+    }
+}
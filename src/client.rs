@@ -1,58 +1,215 @@
 use anyhow::Result;
-use spacetimedb_sdk::{DbContext, Table, TableWithPrimaryKey};
-use std::sync::{Arc, Mutex};
+use spacetimedb_sdk::{DbContext, Status, SubscriptionHandle as _, Table, TableWithPrimaryKey};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Duration;
 
+use crate::attachment::{hash_bytes, is_valid_hash, Attachment as LocalAttachment, CHUNK_SIZE};
 use crate::folder::Folder as LocalFolder;
+use crate::metrics::{self, Counter, METRICS};
 use crate::note::Note as LocalNote;
+use crate::outbox::{Change, Outbox};
 use crate::spacetime_bindings::{
+    attachment_chunk_table::AttachmentChunkTableAccess,
+    attachment_chunk_type::AttachmentChunk as DbAttachmentChunk,
+    attachment_table::AttachmentTableAccess,
+    attachment_type::Attachment as DbAttachment,
+    delete_attachment_reducer::delete_attachment,
     delete_folder_reducer::delete_folder,
     delete_note_reducer::delete_note,
     folder_table::FolderTableAccess,
     folder_type::Folder as DbFolder,
     note_table::NoteTableAccess,
     note_type::Note as DbNote,
+    record_conflict_reducer::record_conflict,
+    tombstone_table::TombstoneTableAccess,
+    upload_attachment_chunk_reducer::upload_attachment_chunk,
+    upsert_attachment_reducer::upsert_attachment,
     upsert_folder_reducer::upsert_folder,
-    upsert_note_reducer::upsert_note,
+    upsert_note_if_version_reducer::upsert_note_if_version,
     DbConnection,
+    ReducerEventContext,
 };
 
+/// Time between reconnection attempts, doubled after each failure up to the maximum
+const RECONNECT_INITIAL_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Registers one of our callbacks on a connection (replayed on every reconnect)
+type Registration = Box<dyn Fn(&DbConnection) + Send + Sync>;
+
 pub struct SpacetimeClient {
-    conn: DbConnection,
+    host: String,
+    db_name: String,
+    // Replaced wholesale when we reconnect
+    conn: RwLock<Arc<DbConnection>>,
     synced: Arc<Mutex<bool>>,
+    // Bumped per connection, so a stale connection's disconnect is ignored
+    generation: AtomicU64,
+    disconnects: Mutex<Option<Receiver<u64>>>,
+    disconnect_tx: Sender<u64>,
+    registrations: Mutex<Vec<Registration>>,
+    // Note, folder and attachment changes waiting for the server (survives disconnects and restarts)
+    outbox: Arc<Mutex<Outbox>>,
 }
 
 impl SpacetimeClient {
-    pub fn connect(host: &str, db_name: &str) -> Result<Self> {
+    pub fn connect(host: &str, db_name: &str, outbox: Outbox) -> Result<Self> {
         let synced = Arc::new(Mutex::new(false));
+        let outbox = Arc::new(Mutex::new(outbox));
+        let (disconnect_tx, disconnects) = channel();
 
-        let conn = DbConnection::builder()
-            .with_uri(host)
-            .with_module_name(db_name)
-            .build()?;
+        let conn = open_connection(host, db_name, &synced, &outbox, 0, disconnect_tx.clone())?;
 
-        // Start the background thread first
-        conn.run_threaded();
+        METRICS.set_connected(true);
+        tracing::info!("Connected to SpacetimeDB at {}/{}", host, db_name);
+        Ok(Self {
+            host: host.to_string(),
+            db_name: db_name.to_string(),
+            conn: RwLock::new(Arc::new(conn)),
+            synced,
+            generation: AtomicU64::new(0),
+            disconnects: Mutex::new(Some(disconnects)),
+            disconnect_tx,
+            registrations: Mutex::new(Vec::new()),
+            outbox,
+        })
+    }
 
-        // Subscribe to all notes and folders (separate queries)
-        let synced_clone = synced.clone();
-        conn.subscription_builder()
-            .on_applied(move |_ctx| {
-                let mut s = synced_clone.lock().unwrap();
-                *s = true;
-                tracing::info!("Subscription sync complete");
-            })
-            .on_error(|_ctx, err| {
-                tracing::error!("Subscription error: {:?}", err);
-            })
-            .subscribe(vec![
-                "SELECT * FROM note",
-                "SELECT * FROM folder"
-            ]);
+    fn conn(&self) -> Arc<DbConnection> {
+        self.conn.read().unwrap().clone()
+    }
 
-        tracing::debug!("Subscription registered for note and folder tables");
-        tracing::info!("Connected to SpacetimeDB at {}/{}", host, db_name);
-        Ok(Self { conn, synced })
+    /// Register a callback on the current connection and on every future one
+    fn register(&self, registration: Registration) {
+        registration(&self.conn());
+        self.registrations.lock().unwrap().push(registration);
+    }
+
+    /// Reconnect (with exponential backoff) whenever the connection drops
+    ///
+    /// Once the new connection's subscription is synced, the registered callbacks are
+    /// attached to it and `on_reconnect` runs, so the vault can be reconciled with
+    /// whatever changed while we were away.
+    pub fn keep_connected<F>(self: &Arc<Self>, on_reconnect: F)
+    where
+        F: Fn(&SpacetimeClient) + Send + 'static,
+    {
+        let Some(disconnects) = self.disconnects.lock().unwrap().take() else {
+            tracing::warn!("Reconnection is already being handled");
+            return;
+        };
+
+        let client = self.clone();
+        std::thread::spawn(move || {
+            while wait_for_disconnect(&disconnects, &client.generation).is_some() {
+                client.reconnect();
+                client.replay_outbox();
+                on_reconnect(&client);
+            }
+        });
+    }
+
+    /// Open connections until one syncs, waiting longer after each failure
+    fn reconnect(&self) {
+        for delay in backoff() {
+            tracing::info!("Reconnecting to SpacetimeDB in {:?}...", delay);
+            std::thread::sleep(delay);
+
+            let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+            *self.synced.lock().unwrap() = false;
+
+            let conn = match open_connection(
+                &self.host,
+                &self.db_name,
+                &self.synced,
+                &self.outbox,
+                generation,
+                self.disconnect_tx.clone(),
+            ) {
+                Ok(conn) => Arc::new(conn),
+                Err(e) => {
+                    tracing::warn!("Reconnection failed: {}", e);
+                    continue;
+                }
+            };
+
+            *self.conn.write().unwrap() = conn.clone();
+            METRICS.set_connected(true);
+            if let Err(e) = self.wait_for_sync() {
+                tracing::warn!("Reconnection failed: {}", e);
+                let _ = conn.disconnect();
+                continue;
+            }
+
+            for registration in self.registrations.lock().unwrap().iter() {
+                registration(&conn);
+            }
+
+            tracing::info!("Reconnected to SpacetimeDB at {}/{}", self.host, self.db_name);
+            return;
+        }
+    }
+
+    /// Send every queued change again (after connecting) and wait until the server has
+    /// applied them, so reconciliation sees the server with our offline edits in place
+    pub fn replay_outbox(&self) {
+        self.outbox.lock().unwrap().mark_all_unsent();
+        self.send_pending();
+        self.wait_for_outbox();
+    }
+
+    /// Wait (up to 30s) until the server has confirmed every queued change
+    pub fn wait_for_outbox(&self) {
+        let timeout = Duration::from_secs(30);
+        let start = std::time::Instant::now();
+        loop {
+            {
+                let outbox = self.outbox.lock().unwrap();
+                if outbox.is_empty() {
+                    return;
+                }
+                if start.elapsed() > timeout {
+                    tracing::warn!("{} queued changes not confirmed by the server yet", outbox.len());
+                    return;
+                }
+            }
+
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    /// Queue a change and send whatever is pending; returns the version an upsert is
+    /// based on (see `Outbox::push`)
+    fn enqueue(&self, change: Change) -> u64 {
+        let mut outbox = self.outbox.lock().unwrap();
+        let based_on = outbox.push(change);
+        if let Err(e) = outbox.save() {
+            tracing::error!("Failed to save outbox: {}", e);
+        }
+        drop(outbox);
+
+        self.send_pending();
+        based_on
+    }
+
+    /// Hand every unsent change to the connection, in order
+    fn send_pending(&self) {
+        let conn = self.conn();
+        let mut outbox = self.outbox.lock().unwrap();
+
+        let (seqs, changes): (Vec<u64>, Vec<Change>) = outbox.take_unsent_batch().into_iter().unzip();
+        for (i, change) in changes.into_iter().enumerate() {
+            if let Err(e) = send_change(&conn, change) {
+                metrics::count(Counter::ReducerFailures);
+                outbox.mark_unsent(&seqs[i..]);
+                tracing::warn!("Not connected ({}), {} changes queued for later", e, outbox.len());
+                return;
+            }
+        }
     }
 
     /// Wait for initial subscription data to be synced
@@ -78,7 +235,7 @@ impl SpacetimeClient {
 
     /// Get all notes from the local cache
     pub fn get_all_notes(&self) -> Vec<LocalNote> {
-        self.conn
+        self.conn()
             .db
             .note()
             .iter()
@@ -93,13 +250,14 @@ impl SpacetimeClient {
                 size: db_note.size,
                 created_time: db_note.created_time,
                 modified_time: db_note.modified_time,
+                version: db_note.version,
             })
             .collect()
     }
 
     /// Get all folders from the local cache
     pub fn get_all_folders(&self) -> Vec<LocalFolder> {
-        self.conn
+        self.conn()
             .db
             .folder()
             .iter()
@@ -111,9 +269,46 @@ impl SpacetimeClient {
             .collect()
     }
 
+    /// Whether the folder at `path` exists in the local cache
+    pub fn has_folder(&self, path: &str) -> bool {
+        self.conn().db.folder().path().find(&path.to_string()).is_some()
+    }
+
+    /// IDs of deleted notes, with their deletion time (ms since epoch)
+    pub fn get_tombstones(&self) -> HashMap<String, u64> {
+        self.conn()
+            .db
+            .tombstone()
+            .iter()
+            .map(|t| (t.note_id, t.deleted_at.to_micros_since_unix_epoch() as u64 / 1_000))
+            .collect()
+    }
+
+    /// Get a note by its ID from the local cache
+    pub fn get_note(&self, id: &str) -> Option<LocalNote> {
+        self.conn()
+            .db
+            .note()
+            .id()
+            .find(&id.to_string())
+            .map(|db_note| LocalNote {
+                id: db_note.id,
+                path: db_note.path,
+                name: db_note.name,
+                content: db_note.content,
+                folder_path: db_note.folder_path,
+                depth: db_note.depth,
+                frontmatter: db_note.frontmatter,
+                size: db_note.size,
+                created_time: db_note.created_time,
+                modified_time: db_note.modified_time,
+                version: db_note.version,
+            })
+    }
+
     /// Get a note by its relative path from the local cache
     pub fn get_note_by_path(&self, path: &str) -> Option<LocalNote> {
-        self.conn
+        self.conn()
             .db
             .note()
             .iter()
@@ -129,90 +324,321 @@ impl SpacetimeClient {
                 size: db_note.size,
                 created_time: db_note.created_time,
                 modified_time: db_note.modified_time,
+                version: db_note.version,
             })
     }
 
+    /// Get all attachments from the local cache
+    pub fn get_all_attachments(&self) -> Vec<LocalAttachment> {
+        self.conn()
+            .db
+            .attachment()
+            .iter()
+            .map(|db_attachment| to_local_attachment(&db_attachment))
+            .collect()
+    }
+
+    /// Get an attachment by its relative path from the local cache
+    pub fn get_attachment_by_path(&self, path: &str) -> Option<LocalAttachment> {
+        self.conn()
+            .db
+            .attachment()
+            .path()
+            .find(&path.to_string())
+            .map(|db_attachment| to_local_attachment(&db_attachment))
+    }
+
+    /// Get an attachment's bytes from the server (None if unknown or some chunks are missing)
+    pub fn get_attachment_data(&self, path: &str) -> Option<Vec<u8>> {
+        let db_attachment = self.conn().db.attachment().path().find(&path.to_string())?;
+        self.fetch_chunks(&db_attachment.hash, db_attachment.chunk_count)
+    }
+
+    /// Fetch the chunks of `hash` with a subscription of their own and concatenate them
+    ///
+    /// Only attachment metadata is mirrored; the bytes are subscribed to for as long as
+    /// it takes to read them. Blocks until the server answers, so never call this from
+    /// a row or reducer callback.
+    pub fn fetch_chunks(&self, hash: &str, chunk_count: u32) -> Option<Vec<u8>> {
+        // Only a hex hash is safe to put between the quotes of the query
+        if !is_valid_hash(hash) {
+            tracing::warn!("Skipping attachment with invalid hash {:?}", hash);
+            return None;
+        }
+
+        let conn = self.conn();
+        let (applied_tx, applied) = channel();
+        let error_tx = applied_tx.clone();
+
+        let handle = conn
+            .subscription_builder()
+            .on_applied(move |_ctx| {
+                let _ = applied_tx.send(true);
+            })
+            .on_error(move |_ctx, err| {
+                tracing::warn!("Failed to subscribe to attachment chunks: {:?}", err);
+                let _ = error_tx.send(false);
+            })
+            .subscribe(format!("SELECT * FROM attachment_chunk WHERE hash = '{}'", hash));
+
+        let data = match applied.recv_timeout(Duration::from_secs(30)) {
+            Ok(true) => assemble_chunks(conn.db.attachment_chunk().iter(), hash, chunk_count),
+            Ok(false) => None,
+            Err(_) => {
+                tracing::warn!("Timeout fetching chunks of {}", hash);
+                None
+            }
+        };
+
+        if let Err(e) = handle.unsubscribe() {
+            tracing::debug!("Failed to unsubscribe from chunks of {}: {}", hash, e);
+        }
+        data
+    }
+
+    /// Register callback for attachment inserts (fetch the bytes with `fetch_chunks`)
+    pub fn on_attachment_inserted<F>(&self, callback: F)
+    where
+        F: FnMut(&DbAttachment) + Send + 'static,
+    {
+        let callback = Arc::new(Mutex::new(callback));
+        self.register(Box::new(move |conn: &DbConnection| {
+            let callback = callback.clone();
+            conn.db.attachment().on_insert(move |_ctx, new| {
+                (callback.lock().unwrap())(new);
+            });
+        }));
+    }
+
+    /// Register callback for attachment updates (same path, new bytes)
+    pub fn on_attachment_updated<F>(&self, callback: F)
+    where
+        F: FnMut(&DbAttachment, &DbAttachment) + Send + 'static,
+    {
+        let callback = Arc::new(Mutex::new(callback));
+        self.register(Box::new(move |conn: &DbConnection| {
+            let callback = callback.clone();
+            conn.db.attachment().on_update(move |_ctx, old, new| {
+                (callback.lock().unwrap())(old, new);
+            });
+        }));
+    }
+
+    /// Register callback for attachment deletions
+    pub fn on_attachment_deleted<F>(&self, callback: F)
+    where
+        F: FnMut(&DbAttachment) + Send + 'static,
+    {
+        let callback = Arc::new(Mutex::new(callback));
+        self.register(Box::new(move |conn: &DbConnection| {
+            let callback = callback.clone();
+            conn.db.attachment().on_delete(move |_ctx, old| {
+                (callback.lock().unwrap())(old);
+            });
+        }));
+    }
+
     /// Register callback for note updates
-    pub fn on_note_updated<F>(&self, mut callback: F)
+    pub fn on_note_updated<F>(&self, callback: F)
     where
         F: FnMut(&DbNote, &DbNote) + Send + 'static,
     {
-        self.conn.db.note().on_update(move |_ctx, old, new| {
-            callback(old, new);
-        });
+        let callback = Arc::new(Mutex::new(callback));
+        self.register(Box::new(move |conn: &DbConnection| {
+            let callback = callback.clone();
+            conn.db.note().on_update(move |_ctx, old, new| {
+                (callback.lock().unwrap())(old, new);
+            });
+        }));
+    }
+
+    /// Register callback for our own note uploads the server applied (`version` is the
+    /// version each one produced)
+    pub fn on_note_uploaded<F>(&self, callback: F)
+    where
+        F: FnMut(&LocalNote) + Send + 'static,
+    {
+        self.on_own_upload(true, callback);
+    }
+
+    /// Register callback for our own note uploads the server rejected because the note
+    /// moved on without them (`version` is the version each one was based on)
+    pub fn on_note_rejected<F>(&self, callback: F)
+    where
+        F: FnMut(&LocalNote) + Send + 'static,
+    {
+        self.on_own_upload(false, callback);
+    }
+
+    /// Register callback for our own attachment uploads the server stored (path, hash)
+    pub fn on_attachment_uploaded<F>(&self, callback: F)
+    where
+        F: FnMut(&str, &str) + Send + 'static,
+    {
+        let callback = Arc::new(Mutex::new(callback));
+        self.register(Box::new(move |conn: &DbConnection| {
+            let callback = callback.clone();
+            conn.reducers().on_upsert_attachment(move |ctx, path, hash, _mime_type, _size, _chunk_count, _modified| {
+                let ours = ctx.event.caller_connection_id == Some(ctx.connection_id());
+                if ours && matches!(ctx.event.status, Status::Committed) {
+                    (callback.lock().unwrap())(path, hash);
+                }
+            });
+        }));
+    }
+
+    fn on_own_upload<F>(&self, committed: bool, callback: F)
+    where
+        F: FnMut(&LocalNote) + Send + 'static,
+    {
+        let callback = Arc::new(Mutex::new(callback));
+        self.register(Box::new(move |conn: &DbConnection| {
+            let callback = callback.clone();
+            conn.reducers().on_upsert_note_if_version(
+                move |ctx, id, path, name, content, folder_path, depth, frontmatter, size, created, modified, expected| {
+                    let ours = ctx.event.caller_connection_id == Some(ctx.connection_id());
+                    let wanted = match &ctx.event.status {
+                        Status::Committed => committed,
+                        Status::Failed(_) => !committed,
+                        _ => false,
+                    };
+                    if !ours || !wanted {
+                        return;
+                    }
+
+                    let note = LocalNote {
+                        id: id.clone(),
+                        path: path.clone(),
+                        name: name.clone(),
+                        content: content.clone(),
+                        folder_path: folder_path.clone(),
+                        depth: *depth,
+                        frontmatter: frontmatter.clone(),
+                        size: *size,
+                        created_time: *created,
+                        modified_time: *modified,
+                        version: if committed { expected + 1 } else { *expected },
+                    };
+                    (callback.lock().unwrap())(&note);
+                },
+            );
+        }));
     }
 
     /// Register callback for note inserts
-    pub fn on_note_inserted<F>(&self, mut callback: F)
+    pub fn on_note_inserted<F>(&self, callback: F)
     where
         F: FnMut(&DbNote) + Send + 'static,
     {
-        self.conn.db.note().on_insert(move |_ctx, new| {
-            callback(new);
-        });
+        let callback = Arc::new(Mutex::new(callback));
+        self.register(Box::new(move |conn: &DbConnection| {
+            let callback = callback.clone();
+            conn.db.note().on_insert(move |_ctx, new| {
+                (callback.lock().unwrap())(new);
+            });
+        }));
     }
 
     /// Register callback for note deletions
-    pub fn on_note_deleted<F>(&self, mut callback: F)
+    pub fn on_note_deleted<F>(&self, callback: F)
     where
         F: FnMut(&DbNote) + Send + 'static,
     {
-        self.conn.db.note().on_delete(move |_ctx, old| {
-            callback(old);
-        });
+        let callback = Arc::new(Mutex::new(callback));
+        self.register(Box::new(move |conn: &DbConnection| {
+            let callback = callback.clone();
+            conn.db.note().on_delete(move |_ctx, old| {
+                (callback.lock().unwrap())(old);
+            });
+        }));
     }
 
     /// Register callback for folder updates
-    pub fn on_folder_updated<F>(&self, mut callback: F)
+    pub fn on_folder_updated<F>(&self, callback: F)
     where
         F: FnMut(&DbFolder, &DbFolder) + Send + 'static,
     {
-        self.conn.db.folder().on_update(move |_ctx, old, new| {
-            callback(old, new);
-        });
+        let callback = Arc::new(Mutex::new(callback));
+        self.register(Box::new(move |conn: &DbConnection| {
+            let callback = callback.clone();
+            conn.db.folder().on_update(move |_ctx, old, new| {
+                (callback.lock().unwrap())(old, new);
+            });
+        }));
     }
 
     /// Register callback for folder inserts
-    pub fn on_folder_inserted<F>(&self, mut callback: F)
+    pub fn on_folder_inserted<F>(&self, callback: F)
     where
         F: FnMut(&DbFolder) + Send + 'static,
     {
-        self.conn.db.folder().on_insert(move |_ctx, new| {
-            callback(new);
-        });
+        let callback = Arc::new(Mutex::new(callback));
+        self.register(Box::new(move |conn: &DbConnection| {
+            let callback = callback.clone();
+            conn.db.folder().on_insert(move |_ctx, new| {
+                (callback.lock().unwrap())(new);
+            });
+        }));
     }
 
     /// Register callback for folder deletions
-    pub fn on_folder_deleted<F>(&self, mut callback: F)
+    pub fn on_folder_deleted<F>(&self, callback: F)
     where
         F: FnMut(&DbFolder) + Send + 'static,
     {
-        self.conn.db.folder().on_delete(move |_ctx, old| {
-            callback(old);
+        let callback = Arc::new(Mutex::new(callback));
+        self.register(Box::new(move |conn: &DbConnection| {
+            let callback = callback.clone();
+            conn.db.folder().on_delete(move |_ctx, old| {
+                (callback.lock().unwrap())(old);
+            });
+        }));
+    }
+
+    /// Upload a note; the server rejects it unless the note is still at `expected_version`
+    /// (0 = the note must not exist on the server yet)
+    ///
+    /// Returns the version the upload is actually based on, which is older than
+    /// `expected_version` if it was merged with an earlier upload that's still queued.
+    pub fn upsert_note(&self, note: &LocalNote, expected_version: u64) -> u64 {
+        metrics::count(Counter::Uploads);
+        self.enqueue(Change::UpsertNote { note: note.clone(), expected_version })
+    }
+
+    /// Version a note reaches once our queued uploads of it are applied (None: none queued)
+    pub fn pending_version(&self, id: &str) -> Option<u64> {
+        self.outbox.lock().unwrap().pending_version(id)
+    }
+
+    /// Upload an attachment read from `file`: its bytes in chunks, then its metadata row
+    pub fn upload_attachment(&self, attachment: &LocalAttachment, file: &Path, data: Vec<u8>) {
+        metrics::count(Counter::Uploads);
+        self.enqueue(Change::UpsertAttachment {
+            attachment: attachment.clone(),
+            file: file.to_path_buf(),
+            data: Arc::new(data),
         });
     }
 
-    pub fn upsert_note(&self, note: &LocalNote) {
-        let _ = self.conn.reducers().upsert_note(
-            note.id.clone(),
-            note.path.clone(),
-            note.name.clone(),
-            note.content.clone(),
-            note.folder_path.clone(),
-            note.depth,
-            note.frontmatter.clone(),
-            note.size,
-            note.created_time,
-            note.modified_time,
-        );
+    pub fn delete_attachment(&self, path: &str) {
+        metrics::count(Counter::Deletes);
+        self.enqueue(Change::DeleteAttachment { path: path.to_string() });
+        tracing::debug!("Deleted attachment: {}", path);
     }
 
     pub fn upsert_folder(&self, folder: &LocalFolder) {
-        let _ = self.conn.reducers().upsert_folder(
-            folder.path.clone(),
-            folder.name.clone(),
-            folder.depth,
-        );
+        self.enqueue(Change::UpsertFolder { folder: folder.clone() });
+    }
+
+    /// Record on the server that `conflict_note_id` holds a losing version of `note_id`
+    ///
+    /// Queued behind the copy's own upload, which the server needs to have applied first.
+    pub fn record_conflict(&self, note_id: &str, conflict_note_id: &str, device: &str) {
+        self.enqueue(Change::RecordConflict {
+            note_id: note_id.to_string(),
+            conflict_note_id: conflict_note_id.to_string(),
+            device: device.to_string(),
+        });
     }
 
     pub fn sync_folders(&self, folders: &[LocalFolder]) {
@@ -223,12 +649,290 @@ impl SpacetimeClient {
     }
 
     pub fn delete_note(&self, id: &str) {
-        let _ = self.conn.reducers().delete_note(id.to_string());
+        metrics::count(Counter::Deletes);
+        self.enqueue(Change::DeleteNote { id: id.to_string() });
         tracing::debug!("Deleted note with ID: {}", id);
     }
 
     pub fn delete_folder(&self, path: &str) {
-        let _ = self.conn.reducers().delete_folder(path.to_string());
+        metrics::count(Counter::Deletes);
+        self.enqueue(Change::DeleteFolder { path: path.to_string() });
         tracing::debug!("Deleted folder: {}", path);
     }
 }
+
+/// Connect, subscribe to everything the daemon mirrors, and report (via `disconnect_tx`,
+/// tagged with `generation`) when the connection drops
+fn open_connection(
+    host: &str,
+    db_name: &str,
+    synced: &Arc<Mutex<bool>>,
+    outbox: &Arc<Mutex<Outbox>>,
+    generation: u64,
+    disconnect_tx: Sender<u64>,
+) -> Result<DbConnection> {
+    let conn = DbConnection::builder()
+        .with_uri(host)
+        .with_module_name(db_name)
+        .on_disconnect(move |_ctx, err| {
+            METRICS.set_connected(false);
+            match err {
+                Some(err) => tracing::error!("Disconnected from SpacetimeDB: {}", err),
+                None => tracing::warn!("Disconnected from SpacetimeDB"),
+            }
+            let _ = disconnect_tx.send(generation);
+        })
+        .build()?;
+
+    // Start the background thread first
+    conn.run_threaded();
+
+    // Subscribe to all notes, folders and attachment metadata (separate queries);
+    // attachment bytes are fetched on demand, see `fetch_chunks`
+    let synced_clone = synced.clone();
+    conn.subscription_builder()
+        .on_applied(move |_ctx| {
+            let mut s = synced_clone.lock().unwrap();
+            *s = true;
+            METRICS.set_subscribed(true);
+            tracing::info!("Subscription sync complete");
+        })
+        .on_error(|_ctx, err| {
+            METRICS.set_subscribed(false);
+            tracing::error!("Subscription error: {:?}", err);
+        })
+        .subscribe(vec![
+            "SELECT * FROM note",
+            "SELECT * FROM folder",
+            "SELECT * FROM attachment",
+            "SELECT * FROM tombstone"
+        ]);
+
+    tracing::debug!("Subscription registered for note, folder and attachment tables");
+
+    // Uploads are version-checked; a rejection means someone else edited the note first
+    // (`on_note_rejected` keeps our side as a conflict copy). Either way the change is
+    // done with, so it leaves the outbox.
+    let queue = outbox.clone();
+    conn.reducers().on_upsert_note_if_version(
+        move |ctx, id, path, _name, _content, _folder_path, _depth, _frontmatter, _size, _created, _modified, expected| {
+            if let Status::Failed(err) = &ctx.event.status {
+                metrics::count(Counter::ReducerFailures);
+                tracing::warn!(
+                    "Upload of {} (ID: {}, based on version {}) rejected: {}",
+                    path, id, expected, err
+                );
+            }
+            confirm(&queue, ctx, |c| {
+                matches!(c, Change::UpsertNote { note, expected_version } if note.id == *id && expected_version == expected)
+            });
+        },
+    );
+    let queue = outbox.clone();
+    conn.reducers().on_delete_note(move |ctx, id| {
+        if let Status::Failed(err) = &ctx.event.status {
+            metrics::count(Counter::ReducerFailures);
+            tracing::warn!("Delete of note {} rejected: {}", id, err);
+        }
+        confirm(&queue, ctx, |c| matches!(c, Change::DeleteNote { id: queued } if queued == id));
+    });
+    let queue = outbox.clone();
+    conn.reducers().on_upsert_folder(move |ctx, path, _name, _depth| {
+        if let Status::Failed(err) = &ctx.event.status {
+            metrics::count(Counter::ReducerFailures);
+            tracing::warn!("Upsert of folder {} rejected: {}", path, err);
+        }
+        confirm(&queue, ctx, |c| matches!(c, Change::UpsertFolder { folder } if folder.path == *path));
+    });
+    let queue = outbox.clone();
+    conn.reducers().on_delete_folder(move |ctx, path| {
+        if let Status::Failed(err) = &ctx.event.status {
+            metrics::count(Counter::ReducerFailures);
+            tracing::warn!("Delete of folder {} rejected: {}", path, err);
+        }
+        confirm(&queue, ctx, |c| matches!(c, Change::DeleteFolder { path: queued } if queued == path));
+    });
+    let queue = outbox.clone();
+    conn.reducers().on_upsert_attachment(move |ctx, path, _hash, _mime_type, _size, _chunk_count, _modified| {
+        if let Status::Failed(err) = &ctx.event.status {
+            metrics::count(Counter::ReducerFailures);
+            tracing::warn!("Upload of attachment {} rejected: {}", path, err);
+        }
+        confirm(&queue, ctx, |c| {
+            matches!(c, Change::UpsertAttachment { attachment, .. } if attachment.path == *path)
+        });
+    });
+    let queue = outbox.clone();
+    conn.reducers().on_delete_attachment(move |ctx, path| {
+        if let Status::Failed(err) = &ctx.event.status {
+            metrics::count(Counter::ReducerFailures);
+            tracing::warn!("Delete of attachment {} rejected: {}", path, err);
+        }
+        confirm(&queue, ctx, |c| matches!(c, Change::DeleteAttachment { path: queued } if queued == path));
+    });
+    let queue = outbox.clone();
+    conn.reducers().on_record_conflict(move |ctx, note_id, conflict_note_id, _device| {
+        if let Status::Failed(err) = &ctx.event.status {
+            metrics::count(Counter::ReducerFailures);
+            tracing::warn!("Recording conflict copy {} of note {} rejected: {}", conflict_note_id, note_id, err);
+        }
+        confirm(&queue, ctx, |c| {
+            matches!(c, Change::RecordConflict { conflict_note_id: queued, .. } if queued == conflict_note_id)
+        });
+    });
+
+    Ok(conn)
+}
+
+/// Call the reducer for a queued change
+fn send_change(conn: &DbConnection, change: Change) -> Result<()> {
+    match change {
+        Change::UpsertNote { note, expected_version } => conn.reducers().upsert_note_if_version(
+            note.id,
+            note.path,
+            note.name,
+            note.content,
+            note.folder_path,
+            note.depth,
+            note.frontmatter,
+            note.size,
+            note.created_time,
+            note.modified_time,
+            expected_version,
+        )?,
+        Change::DeleteNote { id } => conn.reducers().delete_note(id)?,
+        Change::UpsertFolder { folder } => conn.reducers().upsert_folder(folder.path, folder.name, folder.depth)?,
+        Change::DeleteFolder { path } => conn.reducers().delete_folder(path)?,
+        Change::UpsertAttachment { attachment, file, data } => send_attachment(conn, attachment, &file, data)?,
+        Change::DeleteAttachment { path } => conn.reducers().delete_attachment(path)?,
+        Change::RecordConflict { note_id, conflict_note_id, device } => {
+            conn.reducers().record_conflict(note_id, conflict_note_id, device)?
+        }
+    }
+    Ok(())
+}
+
+/// Upload an attachment's chunks, then its metadata row
+///
+/// The server keeps a hash's chunks while any attachment uses them, so the bytes
+/// of a copy of a file already on the server (e.g. elsewhere in the vault) are not
+/// sent again. Reducer calls are applied in order, so every chunk is stored before
+/// the metadata row is written.
+fn send_attachment(conn: &DbConnection, mut attachment: LocalAttachment, file: &Path, data: Arc<Vec<u8>>) -> Result<()> {
+    // Queued by a previous run: the bytes weren't persisted, read them again
+    let data = if data.is_empty() && attachment.size > 0 {
+        match std::fs::read(file) {
+            Ok(data) => {
+                attachment.hash = hash_bytes(&data);
+                attachment.size = data.len() as u64;
+                Arc::new(data)
+            }
+            Err(e) => {
+                // Gone since: the watcher queues its deletion
+                tracing::warn!("Dropping queued upload of {}: {}", attachment.path, e);
+                return Ok(());
+            }
+        }
+    } else {
+        data
+    };
+
+    let stored = conn.db.attachment().iter().any(|a| a.hash == attachment.hash);
+    let chunks: Vec<&[u8]> = data.chunks(CHUNK_SIZE).collect();
+    if !stored {
+        for (index, chunk) in chunks.iter().enumerate() {
+            conn.reducers().upload_attachment_chunk(attachment.hash.clone(), index as u32, chunk.to_vec())?;
+        }
+    }
+
+    conn.reducers().upsert_attachment(
+        attachment.path,
+        attachment.hash,
+        attachment.mime_type,
+        attachment.size,
+        chunks.len() as u32,
+        attachment.modified_time,
+    )?;
+    Ok(())
+}
+
+/// Our call to a reducer came back: the matching change leaves the outbox
+fn confirm(outbox: &Mutex<Outbox>, ctx: &ReducerEventContext, pred: impl Fn(&Change) -> bool) {
+    if ctx.event.caller_connection_id != Some(ctx.connection_id()) {
+        return;
+    }
+
+    let mut outbox = outbox.lock().unwrap();
+    outbox.confirm(pred);
+    if let Err(e) = outbox.save() {
+        tracing::error!("Failed to save outbox: {}", e);
+    }
+}
+
+/// Concatenate the chunks of `hash` in order (None if any are missing)
+fn assemble_chunks(
+    chunks: impl Iterator<Item = DbAttachmentChunk>,
+    hash: &str,
+    chunk_count: u32,
+) -> Option<Vec<u8>> {
+    let mut chunks: Vec<_> = chunks.filter(|chunk| chunk.hash == hash).collect();
+
+    chunks.sort_by_key(|chunk| chunk.chunk_index);
+    chunks.dedup_by_key(|chunk| chunk.chunk_index);
+
+    if chunks.len() != chunk_count as usize {
+        return None;
+    }
+
+    Some(chunks.into_iter().flat_map(|chunk| chunk.data).collect())
+}
+
+fn to_local_attachment(db_attachment: &DbAttachment) -> LocalAttachment {
+    LocalAttachment {
+        path: db_attachment.path.clone(),
+        name: db_attachment.name.clone(),
+        folder_path: db_attachment.folder_path.clone(),
+        hash: db_attachment.hash.clone(),
+        mime_type: db_attachment.mime_type.clone(),
+        size: db_attachment.size,
+        modified_time: db_attachment.modified_time,
+    }
+}
+
+/// Delays between reconnection attempts: doubled after each failure, up to the maximum
+fn backoff() -> impl Iterator<Item = Duration> {
+    std::iter::successors(Some(RECONNECT_INITIAL_DELAY), |delay| Some((*delay * 2).min(RECONNECT_MAX_DELAY)))
+}
+
+/// Wait until the current connection drops, skipping disconnects of connections we
+/// already replaced; None once the client is gone
+fn wait_for_disconnect(disconnects: &Receiver<u64>, current: &AtomicU64) -> Option<u64> {
+    disconnects.iter().find(|&generation| generation == current.load(Ordering::SeqCst))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_the_maximum() {
+        let delays: Vec<u64> = backoff().take(9).map(|d| d.as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 32, 60, 60, 60]);
+    }
+
+    #[test]
+    fn test_stale_disconnects_are_skipped() {
+        let (tx, disconnects) = channel();
+        let current = AtomicU64::new(2);
+
+        // A connection replaced before it reported its disconnect doesn't trigger a reconnect
+        tx.send(0).unwrap();
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        assert_eq!(wait_for_disconnect(&disconnects, &current), Some(2));
+
+        tx.send(1).unwrap();
+        drop(tx);
+        assert_eq!(wait_for_disconnect(&disconnects, &current), None);
+    }
+}
@@ -0,0 +1,183 @@
+//! One-shot commands for auditing and recovering a vault (everything but `sync`)
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use walkdir::WalkDir;
+
+use crate::client::SpacetimeClient;
+use crate::folder::Folder;
+use crate::frontmatter::without_spacetime_id;
+use crate::id_index::NoteIds;
+use crate::ignore_rules::IgnoreRules;
+use crate::note::Note;
+use crate::outbox::Outbox;
+use crate::sanitize::normalize_path;
+use crate::scanner::{read_attachment_with_data_at, read_note_at, scan_notes};
+use crate::sync_state::SyncState;
+use crate::tracker::ContentTracker;
+use crate::writer::{write_attachment_to_disk, write_note_to_disk};
+
+/// Connection state, note counts and changes still waiting to be sent
+pub fn status(server: &str, client: Result<SpacetimeClient>, vault_path: Option<&Path>, ids: &NoteIds) -> Result<()> {
+    println!("Server:     {}", server);
+    match client {
+        Ok(client) => {
+            println!("Connection: ok");
+            println!(
+                "Database:   {} notes, {} folders, {} attachments",
+                client.get_all_notes().len(),
+                client.get_all_folders().len(),
+                client.get_all_attachments().len()
+            );
+        }
+        Err(e) => println!("Connection: failed ({:#})", e),
+    }
+
+    if let Some(vault_path) = vault_path {
+        println!("Vault:      {:?}", vault_path);
+        println!("Local:      {} notes", scan_notes(vault_path, ids, &IgnoreRules::load(vault_path))?.len());
+        println!("Synced:     {} notes tracked since the last run", SyncState::load(vault_path)?.notes().len());
+        println!("Pending:    {} changes queued for the server", Outbox::load(vault_path)?.len());
+    }
+
+    Ok(())
+}
+
+/// Compare every note on disk with the server by ID; fails if any differ
+pub fn verify(vault_path: &Path, ids: &NoteIds, client: &SpacetimeClient) -> Result<()> {
+    let ignore = IgnoreRules::load(vault_path);
+    let server: HashMap<String, Note> = client
+        .get_all_notes()
+        .into_iter()
+        .filter(|n| !ignore.is_ignored(&n.path, false))
+        .map(|n| (n.id.clone(), n))
+        .collect();
+    let local: HashMap<String, Note> = scan_notes(vault_path, ids, &ignore)?
+        .into_iter()
+        .map(|n| (n.id.clone(), n))
+        .collect();
+
+    let ids: BTreeSet<&String> = server.keys().chain(local.keys()).collect();
+    let mut mismatched = 0;
+
+    for id in &ids {
+        let problem = match (local.get(*id), server.get(*id)) {
+            (Some(l), Some(s)) if ContentTracker::hash(&l.content) != ContentTracker::hash(&s.content) => {
+                Some(format!("content differs     {}", l.path))
+            }
+            (Some(l), Some(s))
+                if ContentTracker::frontmatter_hash(&l.frontmatter) != ContentTracker::frontmatter_hash(&s.frontmatter) =>
+            {
+                Some(format!("frontmatter differs {}", l.path))
+            }
+            (Some(l), Some(s)) if l.path != s.path => {
+                Some(format!("path differs        {} (server: {})", l.path, s.path))
+            }
+            (Some(_), Some(_)) => None,
+            (Some(l), None) => Some(format!("only on disk        {}", l.path)),
+            (None, Some(s)) => Some(format!("only on server      {}", s.path)),
+            (None, None) => unreachable!(),
+        };
+
+        if let Some(problem) = problem {
+            println!("{} (ID: {})", problem, id);
+            mismatched += 1;
+        }
+    }
+
+    println!("{} notes checked, {} mismatched", ids.len(), mismatched);
+    if mismatched > 0 {
+        anyhow::bail!("{} notes differ between disk and server", mismatched);
+    }
+    Ok(())
+}
+
+/// Write every folder, note and attachment in the database to `dir` as a vault
+pub fn export(client: &SpacetimeClient, dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let dir = std::fs::canonicalize(dir).context("Failed to resolve export directory")?;
+
+    let folders = client.get_all_folders();
+    for folder in &folders {
+        std::fs::create_dir_all(dir.join(&folder.path))?;
+    }
+
+    // IDs go into the frontmatter, so the export can be used as a vault as is
+    let notes = client.get_all_notes();
+    for note in &notes {
+        write_note_to_disk(&dir, note, &NoteIds::frontmatter())?;
+    }
+
+    let mut attachments = 0;
+    for attachment in client.get_all_attachments() {
+        match client.get_attachment_data(&attachment.path) {
+            Some(data) => {
+                write_attachment_to_disk(&dir, &attachment, &data)?;
+                attachments += 1;
+            }
+            None => tracing::warn!("Attachment {} is missing chunks on the server, skipping", attachment.path),
+        }
+    }
+
+    println!(
+        "Exported {} notes, {} folders and {} attachments to {:?}",
+        notes.len(),
+        folders.len(),
+        attachments,
+        dir
+    );
+    Ok(())
+}
+
+/// Upload the markdown files (and attachments) under `dir` as new notes with fresh IDs
+///
+/// Paths are kept relative to `dir`; files whose path is already taken on the server are skipped.
+pub fn import(client: &SpacetimeClient, dir: &Path) -> Result<()> {
+    let dir = std::fs::canonicalize(dir).context("Failed to resolve import directory")?;
+    let ignore = IgnoreRules::load(&dir);
+    let taken: HashSet<String> = client.get_all_notes().into_iter().map(|n| n.path).collect();
+
+    let walker = WalkDir::new(&dir)
+        .into_iter()
+        .filter_entry(|e| !ignore.is_ignored_path(&dir, e.path(), e.file_type().is_dir()));
+
+    let (mut notes, mut folders, mut attachments, mut skipped) = (0, 0, 0, 0);
+
+    for entry in walker.filter_map(|e| e.ok()) {
+        let path = entry.path();
+
+        if entry.file_type().is_dir() {
+            if path != dir {
+                let rel_path = normalize_path(&path.strip_prefix(&dir)?.to_string_lossy());
+                client.upsert_folder(&Folder::new(rel_path));
+                folders += 1;
+            }
+            continue;
+        }
+
+        if let Some(mut note) = read_note_at(&dir, path, &NoteIds::frontmatter())? {
+            if taken.contains(&note.path) {
+                println!("Skipped (path exists on the server): {}", note.path);
+                skipped += 1;
+                continue;
+            }
+
+            note.id = uuid::Uuid::new_v4().to_string();
+            note.frontmatter = without_spacetime_id(&note.frontmatter);
+            client.upsert_note(&note, 0);
+            notes += 1;
+        } else if let Some((attachment, data)) = read_attachment_with_data_at(&dir, path)? {
+            client.upload_attachment(&attachment, path, data);
+            attachments += 1;
+        }
+    }
+
+    client.wait_for_outbox();
+
+    println!(
+        "Imported {} notes, {} folders and {} attachments ({} skipped)",
+        notes, folders, attachments, skipped
+    );
+    Ok(())
+}
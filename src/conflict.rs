@@ -0,0 +1,154 @@
+use anyhow::Result;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::client::SpacetimeClient;
+use crate::frontmatter::without_spacetime_id;
+use crate::note::Note;
+use crate::reconcile::download;
+use crate::sanitize::disk_path;
+use crate::scanner::read_note_at;
+use crate::tracker::ContentTracker;
+use crate::writer::write_note_to_disk;
+
+/// Device name used in conflict copies when none is configured (the hostname)
+pub fn default_device_name() -> String {
+    let hostname = std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .unwrap_or_default();
+
+    let name: String = hostname
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .collect();
+
+    if name.is_empty() { "daemon".to_string() } else { name }
+}
+
+/// Today's date (UTC) as YYYY-MM-DD
+fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86_400)
+        .unwrap_or(0) as i64;
+    date_from_days(days)
+}
+
+/// Civil date for a day count since 1970-01-01 (Howard Hinnant's algorithm)
+fn date_from_days(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// "Projects/Note.md" -> "Projects/Note (conflict 2026-10-16 laptop).md"
+/// A counter is added if that file already exists: "... laptop 2).md"
+fn conflict_copy_path(vault_path: &Path, path: &str, date: &str, device: &str) -> String {
+    let stem = path.strip_suffix(".md").unwrap_or(path);
+
+    let mut candidate = format!("{} (conflict {} {}).md", stem, date, device);
+    let mut counter = 2;
+    while vault_path.join(&candidate).exists() {
+        candidate = format!("{} (conflict {} {} {}).md", stem, date, device, counter);
+        counter += 1;
+    }
+    candidate
+}
+
+/// Keep the losing side of a conflict as a new note next to the original
+///
+/// The copy gets its own ID, is written to disk, uploaded, and recorded in the
+/// server's `conflict` table against `original_id`. Returns the copy.
+pub fn save_conflict_copy(
+    vault_path: &Path,
+    client: &SpacetimeClient,
+    tracker: &ContentTracker,
+    loser: &Note,
+    original_id: &str,
+    device: &str,
+) -> Result<Note> {
+    let path = conflict_copy_path(vault_path, &loser.path, &today(), device);
+    let id = uuid::Uuid::new_v4().to_string();
+
+    // The copy must not claim the original's ID
+    let copy = Note::new(
+        id,
+        path,
+        loser.content.clone(),
+        without_spacetime_id(&loser.frontmatter),
+        loser.size,
+        loser.created_time,
+        loser.modified_time,
+    );
+
+    tracker.uploading(&copy);
+    write_note_to_disk(vault_path, &copy, tracker.ids())?;
+    client.upsert_note(&copy, 0);
+    client.record_conflict(original_id, &copy.id, device);
+
+    tracing::warn!("Conflict on {}: kept the other version, saved ours as {}", loser.path, copy.path);
+    Ok(copy)
+}
+
+/// The server rejected our upload of `rejected`: someone else's edit got there first
+///
+/// If the file still holds what we sent, that becomes a conflict copy and the server's
+/// version takes the file's place. If the file changed since, the newer edit is queued
+/// already (and gets here in turn if it's rejected too).
+pub fn keep_rejected_upload(
+    vault_path: &Path,
+    client: &SpacetimeClient,
+    tracker: &ContentTracker,
+    rejected: &Note,
+    device: &str,
+) -> Result<()> {
+    let Some(server) = client.get_note(&rejected.id) else {
+        // Deleted on the server meanwhile: reconciliation decides with the tombstone
+        tracing::warn!("Upload of {} rejected, and the note is gone from the server", rejected.path);
+        return Ok(());
+    };
+
+    let sent = ContentTracker::note_hash(&rejected.content, &rejected.frontmatter);
+    if ContentTracker::note_hash(&server.content, &server.frontmatter) == sent {
+        return download(vault_path, tracker, rejected, &server);
+    }
+
+    let local = read_note_at(vault_path, &disk_path(vault_path, &rejected.path)?, tracker.ids())?;
+    if local.is_none_or(|local| ContentTracker::note_hash(&local.content, &local.frontmatter) != sent) {
+        return Ok(());
+    }
+
+    save_conflict_copy(vault_path, client, tracker, rejected, &rejected.id, device)?;
+    download(vault_path, tracker, rejected, &server)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_from_days() {
+        assert_eq!(date_from_days(0), "1970-01-01");
+        assert_eq!(date_from_days(11_016), "2000-02-29");
+        assert_eq!(date_from_days(20_742), "2026-10-16");
+    }
+
+    #[test]
+    fn test_conflict_copy_path() {
+        let vault = Path::new("/nonexistent-vault");
+        assert_eq!(
+            conflict_copy_path(vault, "Projects/Note.md", "2026-10-16", "laptop"),
+            "Projects/Note (conflict 2026-10-16 laptop).md"
+        );
+    }
+}
@@ -1,6 +1,5 @@
-use serde_json::Value;
-use serde_yaml::Value as YamlValue;
-use std::collections::BTreeMap;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
 use regex::Regex;
 use once_cell::sync::Lazy;
 
@@ -10,20 +9,13 @@ pub fn parse_frontmatter(content: &str) -> (String, String) {
         return (content.to_string(), "{}".to_string());
     }
 
-    // Slice safely
-    let rest = &content[3..];
-
     // Find the closing delimiter
-    let Some(end_idx) = rest.find("\n---") else {
+    let Some((yaml_str, after_marker)) = split_frontmatter(content) else {
         return (content.to_string(), "{}".to_string());
     };
 
-    let yaml_str = rest[..end_idx].trim();
-    let after_marker = &rest[end_idx + 4..];
-    let body = after_marker
-        .strip_prefix("\n\n")
-        .or_else(|| after_marker.strip_prefix("\n"))
-        .unwrap_or(after_marker);
+    let yaml_str = yaml_str.trim();
+    let body = &after_marker[separator_len(after_marker)..];
 
     // Parse properly using serde_yaml
     let frontmatter = match serde_yaml::from_str::<Value>(yaml_str) {
@@ -48,13 +40,10 @@ static SPACETIME_ID_REGEX: Lazy<Regex> = Lazy::new(|| {
 /// Uses a hybrid approach: strict YAML parsing first, then regex fallback
 pub fn extract_spacetime_id(content: &str) -> Option<String> {
     // STRATEGY 1: Strict YAML Parsing (Preferred)
-    if content.starts_with("---") {
-        if let Some(end_idx) = content[3..].find("\n---") {
-            let yaml_str = &content[3..end_idx + 3];
-            if let Ok(json) = serde_yaml::from_str::<Value>(yaml_str) {
-                if let Some(id) = json.get("spacetime_id").and_then(|v| v.as_str()) {
-                    return Some(id.to_string());
-                }
+    if let Some((yaml_str, _)) = split_frontmatter(content) {
+        if let Ok(json) = serde_yaml::from_str::<Value>(yaml_str) {
+            if let Some(id) = json.get("spacetime_id").and_then(|v| v.as_str()) {
+                return Some(id.to_string());
             }
         }
     }
@@ -72,41 +61,378 @@ pub fn extract_spacetime_id(content: &str) -> Option<String> {
     None
 }
 
-/// Injects or updates spacetime_id in the frontmatter
-/// Returns the modified content
-pub fn inject_spacetime_id(content: &str, id: &str) -> String {
-    // Case 1: No frontmatter exists - create one
-    if !content.starts_with("---") {
-        return format!("---\nspacetime_id: {}\n---\n\n{}", id, content);
+/// Frontmatter JSON (as stored on a note) without its spacetime_id, for a copy of
+/// the note that gets a new ID
+pub fn without_spacetime_id(frontmatter: &str) -> String {
+    match serde_json::from_str::<Value>(frontmatter) {
+        Ok(Value::Object(mut map)) => {
+            map.remove("spacetime_id");
+            Value::Object(map).to_string()
+        }
+        _ => frontmatter.to_string(),
+    }
+}
+
+/// Frontmatter JSON in the form change detection compares: keys sorted, without the
+/// spacetime_id we add to files ourselves, and `{}` for no frontmatter at all
+pub fn canonical_frontmatter(frontmatter: &str) -> String {
+    fn sorted(value: Value) -> Value {
+        match value {
+            Value::Object(map) => {
+                let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(&b.0));
+                Value::Object(entries.into_iter().map(|(k, v)| (k, sorted(v))).collect())
+            }
+            Value::Array(items) => Value::Array(items.into_iter().map(sorted).collect()),
+            other => other,
+        }
     }
 
-    // Case 2: Frontmatter exists - parse and inject ID
-    let rest = &content[3..];
-    let Some(end_idx) = rest.find("\n---") else {
-        // Malformed frontmatter - treat as no frontmatter
+    match serde_json::from_str::<Value>(frontmatter) {
+        Ok(Value::Object(mut map)) => {
+            map.remove("spacetime_id");
+            sorted(Value::Object(map)).to_string()
+        }
+        Ok(Value::Null) => "{}".to_string(),
+        Ok(value) => sorted(value).to_string(),
+        Err(_) if frontmatter.trim().is_empty() => "{}".to_string(),
+        Err(_) => frontmatter.to_string(),
+    }
+}
+
+/// Injects or updates spacetime_id in the frontmatter
+/// Returns the modified content; the rest of the file is left as written
+pub fn inject_spacetime_id(content: &str, id: &str) -> String {
+    // Case 1: No frontmatter exists (or it's never closed) - create one
+    let Some((yaml_str, after_marker)) = split_frontmatter(content) else {
         return format!("---\nspacetime_id: {}\n---\n\n{}", id, content);
     };
 
-    let yaml_str = rest[..end_idx].trim();
-    let body = &rest[end_idx + 4..];
-
-    // Parse existing frontmatter
-    let mut yaml_map: BTreeMap<String, YamlValue> = match serde_yaml::from_str(yaml_str) {
-        Ok(YamlValue::Mapping(map)) => {
-            map.into_iter()
-                .filter_map(|(k, v)| {
-                    k.as_str().map(|s| (s.to_string(), v))
-                })
-                .collect()
+    // Case 2: Frontmatter exists - add or replace just the spacetime_id line
+    let id_value = Value::String(id.to_string());
+    let yaml_out = match RawFrontmatter::parse(yaml_str) {
+        Some(mut raw) => {
+            raw.set("spacetime_id", &id_value);
+            raw.to_yaml()
         }
-        _ => BTreeMap::new(),
+        None => match serde_yaml::from_str::<Value>(yaml_str) {
+            // Valid, but laid out in a way we can't edit line by line: rewrite it
+            Ok(Value::Object(mut map)) => {
+                map.insert("spacetime_id".to_string(), id_value);
+                fresh_yaml(&map)
+            }
+            // Malformed: keep the user's text, the ID line is still found by the regex fallback
+            _ => format!("spacetime_id: {}\n{}", id, yaml_str),
+        },
     };
 
-    // Insert/update spacetime_id
-    yaml_map.insert("spacetime_id".to_string(), YamlValue::String(id.to_string()));
+    format!("---\n{}\n---{}", yaml_out, after_marker)
+}
+
+/// The full text of a note file: `frontmatter` (JSON, as stored on a note) plus
+/// `spacetime_id` (unless `id` is None: sidecar mode), then `body`
+///
+/// If `existing` (the file currently on disk) has a frontmatter block, only the keys
+/// whose values differ are rewritten, so its key order, comments, quoting and block
+/// scalars survive a sync.
+pub fn render_note_file(existing: Option<&str>, frontmatter: &str, id: Option<&str>, body: &str) -> String {
+    let mut target = match serde_json::from_str::<Value>(frontmatter) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new(),
+    };
+    match id {
+        Some(id) => target.insert("spacetime_id".to_string(), Value::String(id.to_string())),
+        None => target.remove("spacetime_id"),
+    };
+
+    if let Some((yaml_str, after_marker)) = existing.and_then(split_frontmatter) {
+        if let Some(mut raw) = RawFrontmatter::parse(yaml_str) {
+            raw.update(&target);
+            let separator = match &after_marker[..separator_len(after_marker)] {
+                "" => "\n\n",
+                separator => separator,
+            };
+            return format!("---\n{}\n---{}{}", raw.to_yaml(), separator, body);
+        }
+    }
+
+    // Nothing to put in a frontmatter block (sidecar mode, no properties)
+    if target.is_empty() {
+        return body.to_string();
+    }
+
+    format!("---\n{}\n---\n\n{}", fresh_yaml(&target), body)
+}
+
+/// The YAML between the delimiters and everything after the closing delimiter
+fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let rest = content.strip_prefix("---")?;
+    let end_idx = rest.find("\n---")?;
+    let yaml_str = &rest[..end_idx];
+    let yaml_str = yaml_str.strip_prefix('\n').unwrap_or(yaml_str);
+    Some((yaml_str, &rest[end_idx + 4..]))
+}
+
+/// Length of the line break(s) between the closing delimiter and the body
+fn separator_len(after_marker: &str) -> usize {
+    if after_marker.starts_with("\n\n") {
+        2
+    } else if after_marker.starts_with('\n') {
+        1
+    } else {
+        0
+    }
+}
+
+/// A mapping serialized from scratch (keys sorted, serde_yaml's formatting)
+fn fresh_yaml(map: &Map<String, Value>) -> String {
+    let yaml_str = serde_yaml::to_string(map).unwrap_or_default();
+    yaml_str.trim_start_matches("---\n").trim().to_string()
+}
+
+/// A frontmatter block as written in the file, edited key by key: everything we
+/// don't change stays byte-identical
+struct RawFrontmatter {
+    segments: Vec<Segment>,
+    values: Map<String, Value>,
+}
+
+enum Segment {
+    // A top-level key with its value, including continuation lines
+    Entry { key: String, lines: Vec<String> },
+    // A blank or comment line between entries
+    Other(String),
+}
+
+impl RawFrontmatter {
+    /// None if the YAML isn't a mapping we can split into top-level entries reliably
+    fn parse(yaml_str: &str) -> Option<Self> {
+        let values = match serde_yaml::from_str::<Value>(yaml_str) {
+            Ok(Value::Object(map)) => map,
+            Ok(Value::Null) => Map::new(), // Empty (or only comments)
+            _ => return None,
+        };
+
+        let lines: Vec<&str> = if yaml_str.is_empty() { Vec::new() } else { yaml_str.split('\n').collect() };
+        let mut segments = Vec::new();
+        let mut i = 0;
+
+        while i < lines.len() {
+            let Some(key) = top_level_key(lines[i]) else {
+                segments.push(Segment::Other(lines[i].to_string()));
+                i += 1;
+                continue;
+            };
+
+            // Indented lines and `- item`s belong to the entry; blank lines only if more of it follows
+            let mut end = i + 1;
+            for (j, line) in lines.iter().enumerate().skip(i + 1) {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                if !line.starts_with([' ', '\t', '-']) {
+                    break;
+                }
+                end = j + 1;
+            }
+
+            segments.push(Segment::Entry {
+                key,
+                lines: lines[i..end].iter().map(|l| l.to_string()).collect(),
+            });
+            i = end;
+        }
+
+        // Only edit in place if we found exactly the keys the YAML parser did
+        let keys: Vec<&str> = segments
+            .iter()
+            .filter_map(|s| match s {
+                Segment::Entry { key, .. } => Some(key.as_str()),
+                Segment::Other(_) => None,
+            })
+            .collect();
+        let unique: HashSet<&str> = keys.iter().copied().collect();
+        if keys.len() != unique.len() || unique.len() != values.len() || !values.keys().all(|k| unique.contains(k.as_str())) {
+            return None;
+        }
+
+        Some(Self { segments, values })
+    }
+
+    /// Set a top-level key, rewriting only its own lines (a new key goes after the last entry)
+    fn set(&mut self, key: &str, value: &Value) {
+        if self.values.get(key) == Some(value) {
+            return;
+        }
+
+        let mut single = Map::new();
+        single.insert(key.to_string(), value.clone());
+        let lines: Vec<String> = fresh_yaml(&single).split('\n').map(String::from).collect();
+
+        let existing = self
+            .segments
+            .iter()
+            .position(|s| matches!(s, Segment::Entry { key: k, .. } if k == key));
+        match existing {
+            Some(index) => self.segments[index] = Segment::Entry { key: key.to_string(), lines },
+            None => {
+                let after_last = self
+                    .segments
+                    .iter()
+                    .rposition(|s| matches!(s, Segment::Entry { .. }))
+                    .map_or(self.segments.len(), |index| index + 1);
+                self.segments.insert(after_last, Segment::Entry { key: key.to_string(), lines });
+            }
+        }
+        self.values.insert(key.to_string(), value.clone());
+    }
+
+    fn remove(&mut self, key: &str) {
+        self.segments
+            .retain(|s| !matches!(s, Segment::Entry { key: k, .. } if k == key));
+        self.values.remove(key);
+    }
+
+    /// Make the block hold exactly `target`, touching only the keys that differ
+    fn update(&mut self, target: &Map<String, Value>) {
+        let stale: Vec<String> = self
+            .values
+            .keys()
+            .filter(|k| !target.contains_key(*k))
+            .cloned()
+            .collect();
+        for key in stale {
+            self.remove(&key);
+        }
+
+        for (key, value) in target {
+            self.set(key, value);
+        }
+    }
+
+    fn to_yaml(&self) -> String {
+        let lines: Vec<&str> = self
+            .segments
+            .iter()
+            .flat_map(|s| match s {
+                Segment::Entry { lines, .. } => lines.iter().map(String::as_str).collect::<Vec<_>>(),
+                Segment::Other(line) => vec![line.as_str()],
+            })
+            .collect();
+        lines.join("\n")
+    }
+}
+
+/// The key of a `key: value` line at the top level of the mapping
+fn top_level_key(line: &str) -> Option<String> {
+    if line.is_empty() || line.starts_with([' ', '\t', '#', '-', '{', '[', '?']) {
+        return None;
+    }
+
+    // Quoted key: up to the closing quote, which has to be followed by the colon
+    if let Some(quote) = line.chars().next().filter(|c| *c == '"' || *c == '\'') {
+        let close = line[1..].find(quote)? + 1;
+        return line[close + 1..]
+            .starts_with(':')
+            .then(|| line[1..close].to_string());
+    }
+
+    // Plain key: up to the first colon followed by whitespace or the end of the line
+    let colon = line
+        .match_indices(':')
+        .map(|(index, _)| index)
+        .find(|&index| index + 1 == line.len() || line[index + 1..].starts_with(char::is_whitespace))?;
+    Some(line[..colon].trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "---\ntitle: 'My Note'   # shown in the app\ntags:\n- rust\n- sync\n\ncreated: 2024-01-05\nsummary: |\n  First line\n\n  Second paragraph\nspacetime_id: abc-123\n---\nOld body\n";
+
+    fn json(file: &str) -> String {
+        parse_frontmatter(file).1
+    }
+
+    #[test]
+    fn test_unchanged_frontmatter_is_kept_verbatim() {
+        let out = render_note_file(Some(FILE), &json(FILE), Some("abc-123"), "Old body\n");
+        assert_eq!(out, FILE);
 
-    // Serialize back to YAML
-    let yaml_out = serde_yaml::to_string(&yaml_map).unwrap_or_default();
+        let out = render_note_file(Some(FILE), &json(FILE), Some("abc-123"), "New body\n");
+        assert_eq!(out, FILE.replace("Old body", "New body"));
+    }
+
+    #[test]
+    fn test_only_changed_keys_are_rewritten() {
+        let mut values: Map<String, Value> = serde_json::from_str(&json(FILE)).unwrap();
+        values.insert("created".to_string(), Value::String("2024-02-01".to_string()));
+        values.remove("tags");
+        values.insert("aliases".to_string(), Value::String("Note".to_string()));
+        let frontmatter = Value::Object(values).to_string();
+
+        let out = render_note_file(Some(FILE), &frontmatter, Some("abc-123"), "Old body\n");
+        assert_eq!(
+            out,
+            "---\ntitle: 'My Note'   # shown in the app\n\ncreated: 2024-02-01\nsummary: |\n  First line\n\n  Second paragraph\nspacetime_id: abc-123\naliases: Note\n---\nOld body\n"
+        );
+    }
+
+    #[test]
+    fn test_new_file_gets_fresh_frontmatter() {
+        let out = render_note_file(None, r#"{"title":"Hi"}"#, Some("abc-123"), "Body");
+        assert_eq!(out, "---\nspacetime_id: abc-123\ntitle: Hi\n---\n\nBody");
+    }
 
-    format!("---\n{}\n---{}", yaml_out, body)
+    #[test]
+    fn test_without_id() {
+        assert_eq!(render_note_file(None, r#"{"spacetime_id":"abc-123"}"#, None, "Body"), "Body");
+        assert_eq!(render_note_file(Some("Old"), "{}", None, "Body"), "Body");
+
+        let file = "---\ntitle: Hi # greeting\n---\nOld";
+        assert_eq!(render_note_file(Some(file), r#"{"title":"Hi"}"#, None, "New"), "---\ntitle: Hi # greeting\n---\nNew");
+    }
+
+    #[test]
+    fn test_canonical_frontmatter() {
+        // Key order and the ID don't matter, values do
+        let a = canonical_frontmatter(r#"{"tags":["x"],"status":"draft","spacetime_id":"abc-123"}"#);
+        let b = canonical_frontmatter(r#"{"status":"draft","tags":["x"]}"#);
+        assert_eq!(a, b);
+        assert_ne!(a, canonical_frontmatter(r#"{"status":"done","tags":["x"]}"#));
+        assert_eq!(canonical_frontmatter(&json(FILE)), canonical_frontmatter(&json(&render_note_file(None, &json(FILE), None, "Body"))));
+
+        for empty in ["", "{}", "null", r#"{"spacetime_id":"abc-123"}"#] {
+            assert_eq!(canonical_frontmatter(empty), "{}");
+        }
+    }
+
+    #[test]
+    fn test_inject_keeps_existing_lines() {
+        let content = "---\nzeta: 1  # keep me\nalpha: \"two\"\n---\n\nBody";
+        assert_eq!(
+            inject_spacetime_id(content, "abc-123"),
+            "---\nzeta: 1  # keep me\nalpha: \"two\"\nspacetime_id: abc-123\n---\n\nBody"
+        );
+        assert_eq!(inject_spacetime_id("Body", "abc-123"), "---\nspacetime_id: abc-123\n---\n\nBody");
+    }
+
+    #[test]
+    fn test_inject_into_malformed_frontmatter() {
+        let content = "---\ntitle: [unclosed\n---\nBody";
+        let out = inject_spacetime_id(content, "abc-123");
+        assert_eq!(out, "---\nspacetime_id: abc-123\ntitle: [unclosed\n---\nBody");
+        assert_eq!(extract_spacetime_id(&out).as_deref(), Some("abc-123"));
+    }
+
+    #[test]
+    fn test_top_level_key() {
+        assert_eq!(top_level_key("title: Note").as_deref(), Some("title"));
+        assert_eq!(top_level_key("url: http://example.com").as_deref(), Some("url"));
+        assert_eq!(top_level_key("\"my key\": 1").as_deref(), Some("my key"));
+        assert_eq!(top_level_key("  nested: 1"), None);
+        assert_eq!(top_level_key("- item"), None);
+        assert_eq!(top_level_key("# comment: no"), None);
+    }
 }
@@ -0,0 +1,30 @@
+use anyhow::Result;
+use axum::{http::header, http::StatusCode, response::IntoResponse, routing::get, Json, Router};
+use std::net::SocketAddr;
+
+use crate::metrics::METRICS;
+
+/// Serve `/healthz` and `/metrics` (Prometheus) for monitoring
+pub async fn run_server(addr: SocketAddr) -> Result<()> {
+    let app = Router::new()
+        .route("/healthz", get(healthz))
+        .route("/metrics", get(metrics));
+
+    tracing::info!("Health and metrics listening on {}", addr);
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
+
+/// 200 while connected, subscribed and watching the vault, 503 otherwise
+async fn healthz() -> impl IntoResponse {
+    let health = METRICS.health();
+    let status = if health.is_healthy() { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status, Json(health))
+}
+
+async fn metrics() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], METRICS.render())
+}
@@ -0,0 +1,410 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::frontmatter::{extract_spacetime_id, inject_spacetime_id};
+use crate::note::Note;
+use crate::sanitize::disk_path;
+use crate::sync_state::{peek_state_file, read_state_file};
+use crate::tracker::ContentTracker;
+
+/// Where a note's ID is kept
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdMode {
+    /// A `spacetime_id` key injected into each file's frontmatter
+    #[default]
+    Frontmatter,
+    /// The daemon's own index in `.spacenotes/ids`; files are never modified to carry an ID
+    Sidecar,
+}
+
+/// How the index recognizes a note file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct IdEntry {
+    path: String,
+    inode: Option<u64>,
+    fingerprint: String, // Hash of the note's content when last seen
+}
+
+/// Note IDs for sidecar mode, persisted to `<vault>/.spacenotes/ids`
+///
+/// A file is looked up by path; if its path is unknown, a note whose file has
+/// disappeared is taken to have moved here when its inode, or else its content,
+/// matches.
+pub struct IdIndex {
+    file: Option<PathBuf>, // None: kept in memory only
+    entries: HashMap<String, IdEntry>,
+    by_path: HashMap<String, String>, // Path -> ID of the entry there
+    moved: Option<Vec<String>>,       // IDs whose files were gone at the first miss since the last prune
+    dirty: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IndexFile {
+    notes: HashMap<String, IdEntry>,
+}
+
+impl IdIndex {
+    fn new(file: Option<PathBuf>, entries: HashMap<String, IdEntry>) -> Self {
+        let by_path = entries.iter().map(|(id, e)| (e.path.clone(), id.clone())).collect();
+        Self { file, entries, by_path, moved: None, dirty: false }
+    }
+
+    /// Load the index file (an empty index if there is none yet, or it was corrupt)
+    pub fn load(vault_path: &Path) -> Result<Self> {
+        let dir = vault_path.join(".spacenotes");
+        std::fs::create_dir_all(&dir)?;
+        let file = dir.join("ids");
+
+        let entries = read_state_file::<IndexFile>(&file, "ID index")?.map_or_else(HashMap::new, |f| f.notes);
+
+        tracing::info!("Loaded IDs for {} notes", entries.len());
+        Ok(Self::new(Some(file), entries))
+    }
+
+    /// Read the index file without creating, moving or ever saving anything
+    pub fn load_read_only(vault_path: &Path) -> Result<Self> {
+        let file = vault_path.join(".spacenotes").join("ids");
+        let entries = peek_state_file::<IndexFile>(&file, "ID index")?.map_or_else(HashMap::new, |f| f.notes);
+        Ok(Self::new(None, entries))
+    }
+
+    /// ID of the note file at `path`, following a rename if the file moved here
+    ///
+    /// `exists` tells whether a vault-relative path is still on disk. Which notes' files
+    /// are gone is worked out once, at the first unknown path, and reused until the next
+    /// `prune` (the end of a scan or a batch of file events).
+    pub fn resolve(
+        &mut self,
+        path: &str,
+        inode: Option<u64>,
+        fingerprint: &str,
+        exists: impl Fn(&str) -> bool,
+    ) -> Option<String> {
+        let id = match self.by_path.get(path) {
+            Some(id) => id.clone(),
+            None => {
+                let entries = &self.entries;
+                let moved = self.moved.get_or_insert_with(|| {
+                    entries.iter().filter(|(_, e)| !exists(&e.path)).map(|(id, _)| id.clone()).collect()
+                });
+                // A note found at its new path (or back at its old one) since isn't moving
+                let still_gone = |id: &&String| !exists(&entries[id.as_str()].path);
+
+                let by_inode = inode.and_then(|inode| {
+                    moved.iter().filter(|id| entries[id.as_str()].inode == Some(inode)).find(still_gone)
+                });
+                // Content only identifies a moved note if no other moved note has the same content
+                let by_fingerprint = || {
+                    let mut same = moved.iter().filter(|id| entries[id.as_str()].fingerprint == fingerprint).filter(still_gone);
+                    match (same.next(), same.next()) {
+                        (Some(id), None) => Some(id),
+                        _ => None,
+                    }
+                };
+
+                let id = by_inode.or_else(by_fingerprint)?.clone();
+                tracing::info!("Note {} moved from {} to {}", id, entries[&id].path, path);
+                id
+            }
+        };
+
+        self.record(&id, path, inode, fingerprint);
+        Some(id)
+    }
+
+    /// Remember where the note with `id` is and what it looks like
+    pub fn record(&mut self, id: &str, path: &str, inode: Option<u64>, fingerprint: &str) {
+        let entry = IdEntry { path: path.to_string(), inode, fingerprint: fingerprint.to_string() };
+        if self.entries.get(id) != Some(&entry) {
+            if let Some(old) = self.entries.insert(id.to_string(), entry) {
+                if self.by_path.get(&old.path).is_some_and(|at_old| at_old == id) {
+                    self.by_path.remove(&old.path);
+                }
+            }
+            self.by_path.insert(path.to_string(), id.to_string());
+            self.dirty = true;
+        }
+    }
+
+    /// Expect the note with `id` at `path` if nothing is known of it or that path yet
+    pub fn adopt(&mut self, id: &str, path: &str) {
+        if !self.entries.contains_key(id) && !self.by_path.contains_key(path) {
+            self.record(id, path, None, "");
+        }
+    }
+
+    /// Forget notes whose files are gone (once any renames among them have been seen)
+    pub fn prune(&mut self, exists: impl Fn(&str) -> bool) {
+        let before = self.entries.len();
+        self.entries.retain(|_, e| exists(&e.path));
+        if self.entries.len() != before {
+            self.by_path.retain(|_, id| self.entries.contains_key(id));
+            self.dirty = true;
+        }
+        self.moved = None;
+    }
+
+    /// Write the index if anything changed since the last save
+    ///
+    /// Written to a temp file and renamed, so a crash never leaves a half-written index.
+    pub fn save(&mut self) -> Result<()> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let json = serde_json::to_string(&IndexFile { notes: self.entries.clone() })?;
+        let tmp_path = file.with_extension("tmp");
+        std::fs::write(&tmp_path, json)?;
+        std::fs::rename(&tmp_path, file)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+/// How note files are identified: by the `spacetime_id` in their frontmatter, or in
+/// sidecar mode by `IdIndex` as well (a `spacetime_id` already in a file still wins)
+#[derive(Clone)]
+pub struct NoteIds {
+    sidecar: Option<Arc<Sidecar>>,
+}
+
+struct Sidecar {
+    vault_path: PathBuf,
+    index: Mutex<IdIndex>,
+}
+
+impl NoteIds {
+    pub fn load(vault_path: &Path, mode: IdMode) -> Result<Self> {
+        let index = match mode {
+            IdMode::Frontmatter => None,
+            IdMode::Sidecar => Some(IdIndex::load(vault_path)?),
+        };
+        Ok(Self::with_index(vault_path, index))
+    }
+
+    /// Like `load`, but renames seen in the vault are never written back to the index
+    pub fn load_read_only(vault_path: &Path, mode: IdMode) -> Result<Self> {
+        let index = match mode {
+            IdMode::Frontmatter => None,
+            IdMode::Sidecar => Some(IdIndex::load_read_only(vault_path)?),
+        };
+        Ok(Self::with_index(vault_path, index))
+    }
+
+    fn with_index(vault_path: &Path, index: Option<IdIndex>) -> Self {
+        let sidecar = index.map(|index| {
+            Arc::new(Sidecar { vault_path: vault_path.to_path_buf(), index: Mutex::new(index) })
+        });
+        Self { sidecar }
+    }
+
+    /// Frontmatter IDs only (for directories other than the vault)
+    pub fn frontmatter() -> Self {
+        Self { sidecar: None }
+    }
+
+    pub fn is_sidecar(&self) -> bool {
+        self.sidecar.is_some()
+    }
+
+    /// ID of the note file at `abs_path` ("" if it doesn't have one yet)
+    pub fn resolve(&self, abs_path: &Path, rel_path: &str, content: &str, body: &str) -> String {
+        if let Some(id) = extract_spacetime_id(content) {
+            return id;
+        }
+
+        let Some(sidecar) = &self.sidecar else {
+            return String::new();
+        };
+
+        let exists = |path: &str| disk_path(&sidecar.vault_path, path).is_ok_and(|p| p.is_file());
+        sidecar
+            .index
+            .lock()
+            .unwrap()
+            .resolve(rel_path, inode(abs_path), &ContentTracker::hash(body), exists)
+            .unwrap_or_default()
+    }
+
+    /// Give a new note the ID `id`: injected into its file, or recorded in the index
+    /// (the file isn't touched)
+    pub fn assign(&self, abs_path: &Path, note: &mut Note, id: String) -> Result<()> {
+        if self.sidecar.is_none() {
+            let raw_content = std::fs::read_to_string(abs_path)?;
+            std::fs::write(abs_path, inject_spacetime_id(&raw_content, &id))?;
+        }
+
+        note.id = id;
+        self.record(note);
+        Ok(())
+    }
+
+    /// Remember where a synced note lives (sidecar mode only)
+    pub fn record(&self, note: &Note) {
+        if let Some(sidecar) = &self.sidecar {
+            let inode = disk_path(&sidecar.vault_path, &note.path).ok().and_then(|p| inode(&p));
+            let fingerprint = ContentTracker::hash(&note.content);
+            sidecar.index.lock().unwrap().record(&note.id, &note.path, inode, &fingerprint);
+        }
+    }
+
+    /// Look for synced notes (ID, path) the index has lost track of at their last synced
+    /// path, so a lost or corrupt index doesn't make them look deleted here
+    pub fn adopt<'a>(&self, synced: impl IntoIterator<Item = (&'a str, &'a str)>) {
+        if let Some(sidecar) = &self.sidecar {
+            let mut index = sidecar.index.lock().unwrap();
+            for (id, path) in synced {
+                index.adopt(id, path);
+            }
+        }
+    }
+
+    /// Drop notes whose files are gone and persist the index
+    pub fn flush(&self) {
+        if let Some(sidecar) = &self.sidecar {
+            let mut index = sidecar.index.lock().unwrap();
+            index.prune(|path| disk_path(&sidecar.vault_path, path).is_ok_and(|p| p.is_file()));
+            if let Err(e) = index.save() {
+                tracing::error!("Failed to save ID index: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn inode(path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::metadata(path).ok().map(|m| m.ino())
+}
+
+#[cfg(not(unix))]
+fn inode(_path: &Path) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn index() -> IdIndex {
+        IdIndex::new(None, HashMap::new())
+    }
+
+    fn on_disk<'a>(paths: &'a [&'a str]) -> impl Fn(&str) -> bool + 'a {
+        let paths: HashSet<&str> = paths.iter().copied().collect();
+        move |path| paths.contains(path)
+    }
+
+    #[test]
+    fn test_resolve_by_path() {
+        let mut index = index();
+        index.record("a", "Note.md", Some(1), "h1");
+        assert_eq!(index.resolve("Note.md", Some(7), "h2", on_disk(&["Note.md"])).as_deref(), Some("a"));
+        assert_eq!(index.resolve("Other.md", Some(8), "h3", on_disk(&["Note.md", "Other.md"])), None);
+
+        // The entry follows the file's current inode and content
+        assert_eq!(index.entries["a"].inode, Some(7));
+        assert_eq!(index.entries["a"].fingerprint, "h2");
+    }
+
+    #[test]
+    fn test_rename_detected_by_inode() {
+        let mut index = index();
+        index.record("a", "Old.md", Some(1), "h1");
+        index.record("b", "Gone.md", Some(2), "h2");
+
+        let id = index.resolve("Archive/New.md", Some(1), "edited", on_disk(&["Archive/New.md"]));
+        assert_eq!(id.as_deref(), Some("a"));
+        assert_eq!(index.entries["a"].path, "Archive/New.md");
+    }
+
+    #[test]
+    fn test_rename_detected_by_content() {
+        let mut index = index();
+        index.record("a", "Old.md", Some(1), "h1");
+        assert_eq!(index.resolve("New.md", Some(9), "h1", on_disk(&["New.md"])).as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn test_copy_is_a_new_note() {
+        let mut index = index();
+        index.record("a", "Note.md", Some(1), "h1");
+        assert_eq!(index.resolve("Copy.md", Some(2), "h1", on_disk(&["Note.md", "Copy.md"])), None);
+    }
+
+    #[test]
+    fn test_ambiguous_content_is_not_a_rename() {
+        let mut index = index();
+        index.record("a", "One.md", None, "empty");
+        index.record("b", "Two.md", None, "empty");
+        assert_eq!(index.resolve("Three.md", None, "empty", on_disk(&["Three.md"])), None);
+    }
+
+    #[test]
+    fn test_adopt_only_fills_gaps() {
+        let mut index = index();
+        index.record("a", "Note.md", Some(1), "h1");
+        index.adopt("a", "Elsewhere.md");
+        index.adopt("b", "Note.md");
+        index.adopt("c", "Lost.md");
+
+        assert_eq!(index.entries["a"].path, "Note.md");
+        assert!(!index.entries.contains_key("b"));
+        assert_eq!(index.resolve("Lost.md", Some(3), "h3", on_disk(&["Note.md", "Lost.md"])).as_deref(), Some("c"));
+    }
+
+    #[test]
+    fn test_corrupt_file_is_set_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        std::fs::create_dir_all(vault.join(".spacenotes")).unwrap();
+        std::fs::write(vault.join(".spacenotes/ids"), "{\"notes\": {").unwrap();
+
+        let index = IdIndex::load(vault).unwrap();
+        assert!(index.entries.is_empty());
+        assert!(vault.join(".spacenotes/ids.corrupt").exists());
+    }
+
+    #[test]
+    fn test_files_gone_are_looked_up_once_per_scan() {
+        let mut index = index();
+        index.record("a", "A.md", Some(1), "h1");
+        index.record("b", "B.md", Some(2), "h2");
+
+        let stats = std::cell::Cell::new(0);
+        let counted = |path: &str| {
+            stats.set(stats.get() + 1);
+            path == "Other.md" || path == "C.md"
+        };
+
+        // Nothing moved: the first miss checks every note, the second reuses that
+        assert_eq!(index.resolve("Other.md", Some(9), "h9", counted), None);
+        let after_first = stats.get();
+        assert_eq!(index.resolve("C.md", Some(8), "h8", counted), None);
+        assert_eq!(stats.get(), after_first);
+
+        // The next scan looks again
+        index.prune(|_| true);
+        assert_eq!(index.resolve("New.md", Some(1), "h1", on_disk(&["New.md", "B.md"])).as_deref(), Some("a"));
+        assert_eq!(index.entries["a"].path, "New.md");
+        assert_eq!(index.resolve("A.md", Some(7), "h7", on_disk(&["New.md", "B.md", "A.md"])), None);
+    }
+
+    #[test]
+    fn test_prune() {
+        let mut index = index();
+        index.record("a", "Kept.md", None, "h1");
+        index.record("b", "Deleted.md", None, "h2");
+        index.prune(on_disk(&["Kept.md"]));
+        assert!(index.entries.contains_key("a"));
+        assert!(!index.entries.contains_key("b"));
+    }
+}
@@ -0,0 +1,158 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Per-vault exclusions, in gitignore syntax, at the vault root
+pub const IGNORE_FILE: &str = ".spacenotesignore";
+
+/// What to keep out of sync: dot-prefixed names and Synology `@eaDir` folders
+/// always, plus whatever `.spacenotesignore` lists
+#[derive(Clone)]
+pub struct IgnoreRules {
+    matcher: Gitignore,
+}
+
+impl IgnoreRules {
+    /// Read `.spacenotesignore` (no extra rules if it doesn't exist)
+    ///
+    /// Fine for one-off scans; the daemon's callbacks share one copy, see `SharedIgnoreRules`.
+    pub fn load(vault_path: &Path) -> Self {
+        let text = std::fs::read_to_string(vault_path.join(IGNORE_FILE)).unwrap_or_default();
+        Self::parse(vault_path, &text)
+    }
+
+    fn parse(vault_path: &Path, text: &str) -> Self {
+        let mut builder = GitignoreBuilder::new(vault_path);
+        for line in text.lines() {
+            if let Err(e) = builder.add_line(None, line) {
+                tracing::warn!("Ignoring invalid line in {}: {}", IGNORE_FILE, e);
+            }
+        }
+
+        let matcher = builder.build().unwrap_or_else(|e| {
+            tracing::warn!("Failed to load {}: {}", IGNORE_FILE, e);
+            Gitignore::empty()
+        });
+        Self { matcher }
+    }
+
+    /// Should the vault-relative `rel_path` (or a folder containing it) stay out of sync?
+    pub fn is_ignored(&self, rel_path: &str, is_dir: bool) -> bool {
+        let builtin = rel_path
+            .split('/')
+            .any(|name| name.starts_with('.') || name == "@eaDir");
+
+        builtin || self.matcher.matched_path_or_any_parents(rel_path, is_dir).is_ignore()
+    }
+
+    /// Same as `is_ignored`, for an absolute path inside the vault
+    pub fn is_ignored_path(&self, vault_path: &Path, abs_path: &Path, is_dir: bool) -> bool {
+        match abs_path.strip_prefix(vault_path) {
+            Ok(rel) if rel.as_os_str().is_empty() => false,
+            Ok(rel) => self.is_ignored(&rel.to_string_lossy().replace('\\', "/"), is_dir),
+            Err(_) => true,
+        }
+    }
+}
+
+/// The vault's rules, shared by the watcher and the server callbacks
+///
+/// Parsed once and re-read only when the watcher sees `.spacenotesignore` change,
+/// so a burst of row callbacks doesn't read the file once per row.
+#[derive(Clone)]
+pub struct SharedIgnoreRules {
+    vault_path: PathBuf,
+    rules: Arc<RwLock<Arc<IgnoreRules>>>,
+}
+
+impl SharedIgnoreRules {
+    pub fn load(vault_path: &Path) -> Self {
+        Self {
+            vault_path: vault_path.to_path_buf(),
+            rules: Arc::new(RwLock::new(Arc::new(IgnoreRules::load(vault_path)))),
+        }
+    }
+
+    /// The rules as of the last (re)load
+    pub fn current(&self) -> Arc<IgnoreRules> {
+        self.rules.read().unwrap().clone()
+    }
+
+    /// Re-read `.spacenotesignore` after it changed
+    pub fn reload(&self) {
+        *self.rules.write().unwrap() = Arc::new(IgnoreRules::load(&self.vault_path));
+        tracing::info!("Reloaded {}", IGNORE_FILE);
+    }
+
+    /// Is `abs_path` the ignore file itself?
+    pub fn is_rules_file(&self, abs_path: &Path) -> bool {
+        abs_path == self.vault_path.join(IGNORE_FILE)
+    }
+
+    pub fn is_ignored(&self, rel_path: &str, is_dir: bool) -> bool {
+        self.current().is_ignored(rel_path, is_dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(text: &str) -> IgnoreRules {
+        IgnoreRules::parse(Path::new("/vault"), text)
+    }
+
+    #[test]
+    fn test_builtin_exclusions() {
+        let rules = rules("");
+        assert!(rules.is_ignored(".obsidian/workspace.json", false));
+        assert!(rules.is_ignored("Photos/@eaDir/thumb.jpg", false));
+        assert!(!rules.is_ignored("Projects/Note.md", false));
+    }
+
+    #[test]
+    fn test_gitignore_patterns() {
+        let rules = rules("# comment\nnode_modules/\nTemplates/private\n*.excalidraw.md\n!Keep.excalidraw.md\n");
+        assert!(rules.is_ignored("node_modules", true));
+        assert!(rules.is_ignored("app/node_modules/pkg/README.md", false));
+        assert!(rules.is_ignored("Templates/private/Secret.md", false));
+        assert!(!rules.is_ignored("Templates/Public.md", false));
+        assert!(rules.is_ignored("Drawings/Plan.excalidraw.md", false));
+        assert!(!rules.is_ignored("Drawings/Keep.excalidraw.md", false));
+    }
+
+    #[test]
+    fn test_anchored_pattern() {
+        let rules = rules("/Inbox.md\n");
+        assert!(rules.is_ignored("Inbox.md", false));
+        assert!(!rules.is_ignored("Archive/Inbox.md", false));
+    }
+
+    #[test]
+    fn test_absolute_paths() {
+        let rules = rules("Private/\n");
+        let vault = Path::new("/vault");
+        assert!(!rules.is_ignored_path(vault, vault, true));
+        assert!(rules.is_ignored_path(vault, Path::new("/vault/Private"), true));
+        assert!(rules.is_ignored_path(vault, Path::new("/vault/Private/Note.md"), false));
+        assert!(rules.is_ignored_path(vault, Path::new("/elsewhere/Note.md"), false));
+    }
+
+    #[test]
+    fn test_shared_rules_change_on_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        std::fs::write(vault.join(IGNORE_FILE), "Private/\n").unwrap();
+
+        let shared = SharedIgnoreRules::load(vault);
+        assert!(shared.is_ignored("Private/Note.md", false));
+        assert!(shared.is_rules_file(&vault.join(IGNORE_FILE)));
+
+        // Edits only apply once reloaded
+        std::fs::write(vault.join(IGNORE_FILE), "Drafts/\n").unwrap();
+        assert!(shared.is_ignored("Private/Note.md", false));
+        shared.reload();
+        assert!(!shared.is_ignored("Private/Note.md", false));
+        assert!(shared.clone().is_ignored("Drafts/Note.md", false));
+    }
+}
@@ -1,119 +1,266 @@
+mod attachment;
+mod base_store;
 mod client;
+mod commands;
+mod conflict;
 mod folder;
 mod frontmatter;
+mod http;
+mod id_index;
+mod ignore_rules;
+mod merge;
+mod metrics;
 mod note;
+mod outbox;
 mod reconcile;
 mod sanitize;
 mod scanner;
 mod spacetime_bindings;
+mod sync_state;
 mod tracker;
 mod watcher;
 mod writer;
 
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::id_index::{IdMode, NoteIds};
+use crate::ignore_rules::{IgnoreRules, SharedIgnoreRules};
+use crate::metrics::Counter;
+use crate::sanitize::disk_path;
 use crate::tracker::ContentTracker;
-use crate::writer::write_note_to_disk;
+use crate::writer::{write_attachment_to_disk, write_note_to_disk};
 
 #[derive(Parser, Debug)]
 #[command(name = "spacenotes")]
 #[command(about = "Sync markdown notes to SpacetimeDB")]
-struct Args {
-    #[arg(short, long, env = "VAULT_PATH")]
-    vault_path: PathBuf,
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
 
-    #[arg(short = 's', long, env = "SPACETIME_HOST",
+    #[arg(short, long, env = "VAULT_PATH", global = true)]
+    vault_path: Option<PathBuf>,
+
+    #[arg(short = 's', long, env = "SPACETIME_HOST", global = true,
           default_value = "http://localhost:3003")]
     spacetime_host: String,
 
-    #[arg(short, long, env = "SPACETIME_DB",
+    #[arg(short, long, env = "SPACETIME_DB", global = true,
           default_value = "spacenotes")]
     database: String,
+
+    /// Name of this device in conflict copies (defaults to the hostname)
+    #[arg(long, env = "SPACENOTES_DEVICE", global = true)]
+    device_name: Option<String>,
+
+    /// Where note IDs are kept: in each file's frontmatter, or in a daemon-side index
+    #[arg(long, value_enum, env = "SPACENOTES_ID_MODE", global = true, default_value_t = IdMode::Frontmatter)]
+    id_mode: IdMode,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Keep the vault and the database in sync (the default)
+    Sync(SyncArgs),
+    /// Show the connection, note counts and changes waiting to be sent
+    Status,
+    /// Compare every note on disk with the server; exits non-zero if any differ
+    Verify,
+    /// Write the whole database to a directory as markdown files
+    Export {
+        /// Directory to write to (created if missing)
+        dir: PathBuf,
+    },
+    /// Upload a directory of markdown files as new notes with fresh IDs
+    Import {
+        /// Directory to read from
+        dir: PathBuf,
+    },
+}
+
+#[derive(clap::Args, Debug, Default)]
+struct SyncArgs {
+    /// Print what reconciliation would change, then exit without changing anything
+    #[arg(long)]
+    dry_run: bool,
+
+    /// With --dry-run, also write the plan as JSON to this file
+    #[arg(long, requires = "dry_run")]
+    plan_json: Option<PathBuf>,
+
+    /// Serve /healthz and /metrics (Prometheus) on this address, e.g. 0.0.0.0:9090
+    #[arg(long, env = "SPACENOTES_METRICS_ADDR")]
+    metrics_addr: Option<SocketAddr>,
+}
+
+impl Cli {
+    /// The vault path, validated and canonicalized
+    fn vault(&self) -> Result<PathBuf> {
+        let Some(vault_path) = &self.vault_path else {
+            anyhow::bail!("No vault given: pass --vault-path or set VAULT_PATH");
+        };
+        if !vault_path.exists() {
+            anyhow::bail!("Vault path does not exist: {:?}", vault_path);
+        }
+        std::fs::canonicalize(vault_path).context("Failed to resolve absolute path for vault")
+    }
+
+    /// Connect and wait for the initial subscription data
+    fn connect(&self, outbox: outbox::Outbox) -> Result<client::SpacetimeClient> {
+        let client = client::SpacetimeClient::connect(&self.spacetime_host, &self.database, outbox)?;
+        tracing::info!("Waiting for subscription sync...");
+        client.wait_for_sync()?;
+        Ok(client)
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::fmt::init();
+    let cli = Cli::parse();
 
-    let args = Args::parse();
+    // The one-shot commands print their results: keep logs off stdout
+    if matches!(cli.command, None | Some(Command::Sync(_))) {
+        tracing_subscriber::fmt::init();
+    } else {
+        tracing_subscriber::fmt().with_writer(std::io::stderr).init();
+    }
 
-    // Validate and canonicalize path
-    if !args.vault_path.exists() {
-        anyhow::bail!("Vault path does not exist: {:?}", args.vault_path);
+    match &cli.command {
+        None => sync(&cli, &SyncArgs::default()).await,
+        Some(Command::Sync(args)) => sync(&cli, args).await,
+        Some(Command::Status) => {
+            let vault_path = cli.vault_path.as_ref().map(|_| cli.vault()).transpose()?;
+            let ids = match &vault_path {
+                Some(vault_path) => NoteIds::load(vault_path, cli.id_mode)?,
+                None => NoteIds::frontmatter(),
+            };
+            let server = format!("{}/{}", cli.spacetime_host, cli.database);
+            commands::status(&server, cli.connect(outbox::Outbox::ephemeral()), vault_path.as_deref(), &ids)
+        }
+        Some(Command::Verify) => {
+            let vault_path = cli.vault()?;
+            let ids = NoteIds::load(&vault_path, cli.id_mode)?;
+            commands::verify(&vault_path, &ids, &cli.connect(outbox::Outbox::ephemeral())?)
+        }
+        Some(Command::Export { dir }) => commands::export(&cli.connect(outbox::Outbox::ephemeral())?, dir),
+        Some(Command::Import { dir }) => commands::import(&cli.connect(outbox::Outbox::ephemeral())?, dir),
     }
-    let absolute_vault_path = std::fs::canonicalize(&args.vault_path)
-        .context("Failed to resolve absolute path for vault")?;
+}
+
+async fn sync(cli: &Cli, args: &SyncArgs) -> Result<()> {
+    let absolute_vault_path = cli.vault()?;
 
     tracing::info!("Vault path: {:?}", absolute_vault_path);
-    tracing::info!("SpacetimeDB: {}/{}", args.spacetime_host, args.database);
-
-    // Initialize content tracker for loop prevention
-    let tracker = Arc::new(ContentTracker::new());
-
-    // Connect to SpacetimeDB
-    let client = Arc::new(
-        client::SpacetimeClient::connect(&args.spacetime_host, &args.database)?
-    );
-
-    // Wait for initial subscription data
-    tracing::info!("Waiting for subscription sync...");
-    client.wait_for_sync()?;
-
-    // Reconcile local vault with server (two-way sync)
-    tracing::info!("Reconciling with server...");
-    reconcile::reconcile_on_startup(&absolute_vault_path, &client, &tracker)?;
-
-    // Reconcile folders (two-way sync)
-    tracing::info!("Reconciling folders...");
-    let local_folders = scanner::scan_folders(&absolute_vault_path)?;
-    let server_folders = client.get_all_folders();
-
-    // Create folders that exist on server but not locally
-    for server_folder in &server_folders {
-        // Skip @eaDir folders (Synology metadata)
-        if server_folder.path.contains("@eaDir") {
-            continue;
-        }
+    tracing::info!("SpacetimeDB: {}/{}", cli.spacetime_host, cli.database);
 
-        let folder_path = absolute_vault_path.join(&server_folder.path);
-        if !folder_path.exists() {
-            if let Err(e) = std::fs::create_dir_all(&folder_path) {
-                tracing::error!("Failed to create folder {}: {}", server_folder.path, e);
-            } else {
-                tracing::info!("Created local folder from server: {}", server_folder.path);
+    // Health and metrics listener (up before connecting, so monitoring sees a stuck startup)
+    if let Some(addr) = args.metrics_addr.filter(|_| !args.dry_run) {
+        tokio::spawn(async move {
+            if let Err(e) = http::run_server(addr).await {
+                tracing::error!("Health and metrics listener failed: {}", e);
             }
+        });
+    }
+
+    // Dry run: report what reconciliation would do and stop before changing anything
+    // (the last run's state is only read; nothing under .spacenotes is created or saved)
+    if args.dry_run {
+        let tracker = ContentTracker::load_read_only(&absolute_vault_path, cli.id_mode)?;
+        let client = cli.connect(outbox::Outbox::ephemeral())?;
+        let ignore = IgnoreRules::load(&absolute_vault_path);
+        let plan = reconcile::plan(&absolute_vault_path, &client, &tracker, &ignore)?;
+        print!("{}", plan.report());
+        if let Some(file) = &args.plan_json {
+            std::fs::write(file, serde_json::to_string_pretty(&plan)?)
+                .with_context(|| format!("Failed to write plan to {:?}", file))?;
         }
+        return Ok(());
     }
 
-    // Upload folders that exist locally but not on server
-    client.sync_folders(&local_folders);
+    // Initialize content tracker for loop prevention (resuming from the last run's sync state)
+    let tracker = Arc::new(ContentTracker::load(&absolute_vault_path, cli.id_mode)?);
 
-    // Register callback for note updates from server
-    let vault_clone = absolute_vault_path.clone();
+    // Connect to SpacetimeDB and wait for initial subscription data
+    let client = Arc::new(cli.connect(outbox::Outbox::load(&absolute_vault_path)?)?);
+
+    let device = cli.device_name.clone().unwrap_or_else(conflict::default_device_name);
+
+    // Our uploads become the synced state (version and merge base) only once the server
+    // has applied them; unless a newer edit from elsewhere already took their place
     let tracker_clone = tracker.clone();
-    client.on_note_updated(move |old_note, new_note| {
-        let path_changed = old_note.path != new_note.path;
-        let content_changed = tracker_clone.is_modified(&new_note.id, &new_note.content);
+    client.on_note_uploaded(move |note| {
+        if note.version >= tracker_clone.version(&note.id) {
+            tracker_clone.set_version(&note.id, note.version);
+            tracker_clone.record_synced(note);
+            tracker_clone.flush();
+        }
+    });
 
-        // Skip if nothing changed (echo from our own update)
-        if !path_changed && !content_changed {
-            tracing::debug!("Skipping update echo: {}", new_note.path);
-            return;
+    // A rejected upload is an edit the server doesn't have: keep it as a conflict copy
+    let vault_clone = absolute_vault_path.clone();
+    let tracker_clone = tracker.clone();
+    let client_clone = client.clone();
+    let device_clone = device.clone();
+    client.on_note_rejected(move |rejected| {
+        if let Err(e) = conflict::keep_rejected_upload(&vault_clone, &client_clone, &tracker_clone, rejected, &device_clone) {
+            tracing::error!("Failed to keep rejected upload of {}: {}", rejected.path, e);
         }
+        tracker_clone.flush();
+    });
+
+    // An attachment counts as synced once the server has stored our upload
+    let tracker_clone = tracker.clone();
+    client.on_attachment_uploaded(move |path, hash| {
+        tracker_clone.update_attachment(path, hash);
+        tracker_clone.flush();
+    });
+
+    // Send changes queued while the server was unreachable (possibly in an earlier run)
+    client.replay_outbox();
+
+    // Ignore rules for reconciliation, the callbacks and the watcher, re-read when
+    // .spacenotesignore changes
+    let ignore = SharedIgnoreRules::load(&absolute_vault_path);
 
-        // If path changed, delete the old file (this is a rename)
-        if old_note.path != new_note.path {
-            let old_path = vault_clone.join(&old_note.path);
-            if old_path.exists() {
+    // Reconcile local vault with server (two-way sync of notes, folders and attachments)
+    reconcile::reconcile_all(&absolute_vault_path, &client, &tracker, &ignore.current(), &device)?;
+
+    // Register callback for note updates from server
+    let vault_clone = absolute_vault_path.clone();
+    let ignore_clone = ignore.clone();
+    let tracker_clone = tracker.clone();
+    let client_clone = client.clone();
+    let device_clone = device.clone();
+    client.on_note_updated(move |old_note, new_note| {
+        // Notes at ignored paths stay out of the vault; one moved there leaves it
+        let ignore = ignore_clone.current();
+        let old_ignored = ignore.is_ignored(&old_note.path, false);
+        if ignore.is_ignored(&new_note.path, false) {
+            // Nothing is ever written at a path disk_path refuses, so there's nothing to delete there
+            let old_path = disk_path(&vault_clone, &old_note.path).ok().filter(|p| !old_ignored && p.exists());
+            if let Some(old_path) = old_path {
                 if let Err(e) = std::fs::remove_file(&old_path) {
-                    tracing::error!("Failed to delete old file {}: {}", old_note.path, e);
+                    tracing::error!("Failed to delete {} (moved to an ignored path): {}", old_note.path, e);
                 } else {
-                    tracing::info!("Deleted old file during rename: {}", old_note.path);
+                    tracker_clone.remove(&old_note.id);
+                    tracker_clone.flush();
+                    tracing::info!("Deleted local file moved to an ignored path: {}", old_note.path);
                 }
             }
+            return;
+        }
+
+        // An update older than our latest upload is the echo of an earlier upload of ours
+        let pending_version = client_clone.pending_version(&new_note.id).unwrap_or(0);
+        let known_version = tracker_clone.version(&new_note.id).max(pending_version);
+        let superseded = new_note.version < known_version;
+
+        // Track the server version even for echoes: it's the base of our next upload
+        if !superseded {
+            tracker_clone.set_version(&new_note.id, new_note.version);
         }
 
         // Convert DbNote to LocalNote for writer
@@ -128,31 +275,74 @@ async fn main() -> Result<()> {
             size: new_note.size,
             created_time: new_note.created_time,
             modified_time: new_note.modified_time,
+            version: new_note.version,
         };
 
-        let content_hash = crate::tracker::ContentTracker::hash(&note.content);
+        let path_changed = old_note.path != new_note.path;
+        let content_changed = tracker_clone.is_modified(&note);
+
+        // Skip if nothing changed, body or frontmatter (echo from our own update)
+        if !path_changed && !content_changed {
+            metrics::count(Counter::EchoSkips);
+            tracing::debug!("Skipping update echo: {}", new_note.path);
+            return;
+        }
+
+        // Local edits the server never saw would be overwritten: keep them as a conflict copy
+        if content_changed && !superseded && !old_ignored {
+            let local = disk_path(&vault_clone, &old_note.path)
+                .and_then(|local_path| scanner::read_note_at(&vault_clone, &local_path, tracker_clone.ids()));
+            if let Ok(Some(local)) = local {
+                let local_hash = ContentTracker::note_hash(&local.content, &local.frontmatter);
+                if local_hash != ContentTracker::note_hash(&old_note.content, &old_note.frontmatter)
+                    && local_hash != ContentTracker::note_hash(&note.content, &note.frontmatter)
+                {
+                    if let Err(e) = conflict::save_conflict_copy(
+                        &vault_clone, &client_clone, &tracker_clone, &local, &new_note.id, &device_clone,
+                    ) {
+                        tracing::error!("Failed to save conflict copy of {}: {}", old_note.path, e);
+                    }
+                }
+            }
+        }
+
+        // If path changed, delete the old file (this is a rename; a file at an ignored path isn't ours)
+        if old_note.path != new_note.path && !old_ignored {
+            if let Some(old_path) = disk_path(&vault_clone, &old_note.path).ok().filter(|p| p.exists()) {
+                if let Err(e) = std::fs::remove_file(&old_path) {
+                    tracing::error!("Failed to delete old file {}: {}", old_note.path, e);
+                } else {
+                    tracing::info!("Deleted old file during rename: {}", old_note.path);
+                }
+            }
+        }
+
+        let content_hash = ContentTracker::note_hash(&note.content, &note.frontmatter);
         tracing::info!(
             "Tracker update: path={}, id={}, content_len={}, hash={}",
             note.path, note.id, note.content.len(), &content_hash[..16]
         );
-        tracker_clone.update(&note.id, &note.content);
-        if let Err(e) = write_note_to_disk(&vault_clone, &note) {
+        tracker_clone.update(&note);
+        if let Err(e) = write_note_to_disk(&vault_clone, &note, tracker_clone.ids()) {
             tracing::error!("Failed to write {}: {}", note.path, e);
         } else {
+            metrics::count(Counter::Downloads);
             tracing::info!("Downloaded update: {}", note.path);
         }
+        tracker_clone.flush();
     });
 
     // Register callback for note inserts from server
     let vault_clone = absolute_vault_path.clone();
+    let ignore_clone = ignore.clone();
     let tracker_clone = tracker.clone();
     client.on_note_inserted(move |db_note| {
-        // Skip if we already have this content (echo from our own upload)
-        if !tracker_clone.is_modified(&db_note.id, &db_note.content) {
-            tracing::debug!("Skipping insert echo: {}", db_note.path);
+        if ignore_clone.is_ignored(&db_note.path, false) {
             return;
         }
 
+        tracker_clone.set_version(&db_note.id, db_note.version);
+
         let note = note::Note {
             id: db_note.id.clone(),
             path: db_note.path.clone(),
@@ -164,45 +354,69 @@ async fn main() -> Result<()> {
             size: db_note.size,
             created_time: db_note.created_time,
             modified_time: db_note.modified_time,
+            version: db_note.version,
         };
 
-        let content_hash = crate::tracker::ContentTracker::hash(&note.content);
+        // Skip if we already have this body and frontmatter (echo from our own upload)
+        if !tracker_clone.is_modified(&note) {
+            metrics::count(Counter::EchoSkips);
+            tracing::debug!("Skipping insert echo: {}", db_note.path);
+            return;
+        }
+
+        let content_hash = ContentTracker::note_hash(&note.content, &note.frontmatter);
         tracing::info!(
             "Tracker insert: path={}, id={}, content_len={}, hash={}",
             note.path, note.id, note.content.len(), &content_hash[..16]
         );
-        tracker_clone.update(&note.id, &note.content);
-        if let Err(e) = write_note_to_disk(&vault_clone, &note) {
+        tracker_clone.update(&note);
+        if let Err(e) = write_note_to_disk(&vault_clone, &note, tracker_clone.ids()) {
             tracing::error!("Failed to write {}: {}", note.path, e);
         } else {
+            metrics::count(Counter::Downloads);
             tracing::info!("Downloaded new: {}", note.path);
         }
+        tracker_clone.flush();
     });
 
     // Register callback for note deletions from server
     let vault_clone = absolute_vault_path.clone();
+    let ignore_clone = ignore.clone();
     let tracker_clone = tracker.clone();
     client.on_note_deleted(move |old_note| {
-        let path = vault_clone.join(&old_note.path);
-        if path.exists() {
+        // A local file at an ignored path isn't the synced note
+        if ignore_clone.is_ignored(&old_note.path, false) {
+            return;
+        }
+
+        if let Some(path) = disk_path(&vault_clone, &old_note.path).ok().filter(|p| p.exists()) {
             if let Err(e) = std::fs::remove_file(&path) {
                 tracing::error!("Failed to delete {}: {}", old_note.path, e);
             } else {
                 tracker_clone.remove(&old_note.id);
+                metrics::count(Counter::Deletes);
                 tracing::info!("Deleted local file: {}", old_note.path);
             }
         }
+        tracker_clone.flush();
     });
 
     // Register callback for folder inserts from server
     let vault_clone = absolute_vault_path.clone();
+    let ignore_clone = ignore.clone();
     client.on_folder_inserted(move |new_folder| {
-        // Skip @eaDir folders (Synology metadata)
-        if new_folder.path.contains("@eaDir") {
+        // Skip @eaDir folders (Synology metadata) and anything in .spacenotesignore
+        if ignore_clone.is_ignored(&new_folder.path, true) {
             return;
         }
 
-        let path = vault_clone.join(&new_folder.path);
+        let path = match disk_path(&vault_clone, &new_folder.path) {
+            Ok(path) => path,
+            Err(e) => {
+                tracing::error!("Failed to create folder {}: {}", new_folder.path, e);
+                return;
+            }
+        };
         if !path.exists() {
             if let Err(e) = std::fs::create_dir_all(&path) {
                 tracing::error!("Failed to create folder {}: {}", new_folder.path, e);
@@ -214,12 +428,17 @@ async fn main() -> Result<()> {
 
     // Register callback for folder deletions from server
     let vault_clone = absolute_vault_path.clone();
+    let ignore_clone = ignore.clone();
     client.on_folder_deleted(move |old_folder| {
-        let path = vault_clone.join(&old_folder.path);
-        if path.exists() && path.is_dir() {
+        if ignore_clone.is_ignored(&old_folder.path, true) {
+            return;
+        }
+
+        if let Some(path) = disk_path(&vault_clone, &old_folder.path).ok().filter(|p| p.is_dir()) {
             if let Err(e) = std::fs::remove_dir_all(&path) {
                 tracing::error!("Failed to delete folder {}: {}", old_folder.path, e);
             } else {
+                metrics::count(Counter::Deletes);
                 tracing::info!("Deleted local folder: {}", old_folder.path);
             }
         }
@@ -227,9 +446,20 @@ async fn main() -> Result<()> {
 
     // Register callback for folder updates from server (renames/moves)
     let vault_clone = absolute_vault_path.clone();
+    let ignore_clone = ignore.clone();
     client.on_folder_updated(move |old_folder, new_folder| {
-        let old_path = vault_clone.join(&old_folder.path);
-        let new_path = vault_clone.join(&new_folder.path);
+        let ignore = ignore_clone.current();
+        if ignore.is_ignored(&old_folder.path, true) || ignore.is_ignored(&new_folder.path, true) {
+            return;
+        }
+
+        let (old_path, new_path) = match (disk_path(&vault_clone, &old_folder.path), disk_path(&vault_clone, &new_folder.path)) {
+            (Ok(old_path), Ok(new_path)) => (old_path, new_path),
+            (Err(e), _) | (_, Err(e)) => {
+                tracing::error!("Failed to rename folder {} -> {}: {}", old_folder.path, new_folder.path, e);
+                return;
+            }
+        };
 
         if old_path.exists() && old_path != new_path {
             // Create parent directory for new location if needed
@@ -247,10 +477,102 @@ async fn main() -> Result<()> {
         }
     });
 
+    // Register callback for attachment inserts and updates from server. Their bytes are
+    // fetched with a subscription of their own, which can't be waited for inside a row
+    // callback, so downloads run on a thread of their own.
+    let (downloads_tx, downloads) = std::sync::mpsc::channel::<spacetime_bindings::Attachment>();
+    let vault_clone = absolute_vault_path.clone();
+    let ignore_clone = ignore.clone();
+    let tracker_clone = tracker.clone();
+    let client_clone = client.clone();
+    std::thread::spawn(move || {
+        for db_attachment in downloads {
+            if ignore_clone.is_ignored(&db_attachment.path, false) {
+                continue;
+            }
+
+            // Skip if we already have these bytes (echo from our own upload)
+            if !tracker_clone.attachment_changed(&db_attachment.path, &db_attachment.hash) {
+                metrics::count(Counter::EchoSkips);
+                tracing::debug!("Skipping attachment echo: {}", db_attachment.path);
+                continue;
+            }
+
+            let Some(data) = client_clone.fetch_chunks(&db_attachment.hash, db_attachment.chunk_count) else {
+                tracing::warn!("Attachment {} is missing chunks on the server", db_attachment.path);
+                continue;
+            };
+
+            // Deleted or replaced on the server while we were fetching: the newer change wins
+            let current = client_clone.get_attachment_by_path(&db_attachment.path);
+            if current.is_none_or(|a| a.hash != db_attachment.hash) {
+                continue;
+            }
+
+            let attachment = attachment::Attachment {
+                path: db_attachment.path.clone(),
+                name: db_attachment.name.clone(),
+                folder_path: db_attachment.folder_path.clone(),
+                hash: db_attachment.hash.clone(),
+                mime_type: db_attachment.mime_type.clone(),
+                size: db_attachment.size,
+                modified_time: db_attachment.modified_time,
+            };
+
+            tracker_clone.expect_attachment(&attachment.path, &attachment.hash);
+            if let Err(e) = write_attachment_to_disk(&vault_clone, &attachment, &data) {
+                tracing::error!("Failed to write attachment {}: {}", attachment.path, e);
+            } else {
+                tracker_clone.update_attachment(&attachment.path, &attachment.hash);
+                tracker_clone.flush();
+                metrics::count(Counter::Downloads);
+                tracing::info!("Downloaded attachment: {}", attachment.path);
+            }
+        }
+    });
+    let download_update = downloads_tx.clone();
+    client.on_attachment_inserted(move |new| {
+        let _ = downloads_tx.send(new.clone());
+    });
+    client.on_attachment_updated(move |_old, new| {
+        let _ = download_update.send(new.clone());
+    });
+
+    // Register callback for attachment deletions from server
+    let vault_clone = absolute_vault_path.clone();
+    let ignore_clone = ignore.clone();
+    let tracker_clone = tracker.clone();
+    client.on_attachment_deleted(move |old_attachment| {
+        if ignore_clone.is_ignored(&old_attachment.path, false) {
+            return;
+        }
+
+        if let Some(path) = disk_path(&vault_clone, &old_attachment.path).ok().filter(|p| p.exists()) {
+            if let Err(e) = std::fs::remove_file(&path) {
+                tracing::error!("Failed to delete attachment {}: {}", old_attachment.path, e);
+            } else {
+                tracker_clone.remove_attachment(&old_attachment.path);
+                tracker_clone.flush();
+                metrics::count(Counter::Deletes);
+                tracing::info!("Deleted local attachment: {}", old_attachment.path);
+            }
+        }
+    });
+
+    // If the connection drops, reconnect and catch up with whatever changed meanwhile
+    let vault_clone = absolute_vault_path.clone();
+    let ignore_clone = ignore.clone();
+    let tracker_clone = tracker.clone();
+    client.keep_connected(move |client| {
+        if let Err(e) = reconcile::reconcile_all(&vault_clone, client, &tracker_clone, &ignore_clone.current(), &device) {
+            tracing::error!("Reconciliation after reconnect failed: {}", e);
+        }
+    });
+
     tracing::info!("Two-way sync initialized.");
 
     // Start file watcher
-    watcher::start_watcher(absolute_vault_path, client, tracker).await?;
+    watcher::start_watcher(absolute_vault_path, client, tracker, ignore).await?;
 
     Ok(())
 }
@@ -0,0 +1,213 @@
+//! Line-based three-way merge of note bodies
+//!
+//! `base` is the content both sides last agreed on, `local` is what's on disk and
+//! `remote` is what's on the server. Changes that touch different lines are combined;
+//! changes to the same (or adjacent) lines can't be merged and are reported as a conflict.
+
+/// Larger diffs than this (lines x lines) aren't aligned line by line; the changed
+/// region is treated as a single hunk instead
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Debug, PartialEq)]
+pub enum MergeResult {
+    Clean(String),
+    Conflict, // Both sides changed the same lines differently
+}
+
+/// Replacement of `base[start..end]` by `lines`
+#[derive(Debug, Clone)]
+struct Hunk<'a> {
+    start: usize,
+    end: usize,
+    lines: Vec<&'a str>,
+}
+
+pub fn merge3(base: &str, local: &str, remote: &str) -> MergeResult {
+    if local == remote || remote == base {
+        return MergeResult::Clean(local.to_string());
+    }
+    if local == base {
+        return MergeResult::Clean(remote.to_string());
+    }
+
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let local_lines: Vec<&str> = local.split_inclusive('\n').collect();
+    let remote_lines: Vec<&str> = remote.split_inclusive('\n').collect();
+
+    let local_hunks = diff_hunks(&base_lines, &local_lines);
+    let remote_hunks = diff_hunks(&base_lines, &remote_lines);
+
+    let mut out = String::new();
+    let mut pos = 0;
+    let (mut i, mut j) = (0, 0);
+
+    loop {
+        // Start a group with whichever side changes the earliest base line
+        let first = match (local_hunks.get(i), remote_hunks.get(j)) {
+            (None, None) => break,
+            (Some(l), Some(r)) => l.start.min(r.start),
+            (Some(l), None) => l.start,
+            (None, Some(r)) => r.start,
+        };
+        let (group_start, mut group_end) = (first, first);
+        let (local_from, remote_from) = (i, j);
+
+        // Pull in every hunk (from either side) that overlaps or touches the group
+        loop {
+            if let Some(h) = local_hunks.get(i).filter(|h| h.start <= group_end) {
+                group_end = group_end.max(h.end);
+                i += 1;
+            } else if let Some(h) = remote_hunks.get(j).filter(|h| h.start <= group_end) {
+                group_end = group_end.max(h.end);
+                j += 1;
+            } else {
+                break;
+            }
+        }
+
+        out.extend(base_lines[pos..group_start].iter().copied());
+
+        let local_side = apply_hunks(&base_lines, group_start, group_end, &local_hunks[local_from..i]);
+        let remote_side = apply_hunks(&base_lines, group_start, group_end, &remote_hunks[remote_from..j]);
+
+        if local_from == i {
+            out.push_str(&remote_side);
+        } else if remote_from == j || local_side == remote_side {
+            out.push_str(&local_side);
+        } else {
+            return MergeResult::Conflict;
+        }
+
+        pos = group_end;
+    }
+
+    out.extend(base_lines[pos..].iter().copied());
+
+    MergeResult::Clean(out)
+}
+
+/// Text of `base[start..end]` after applying one side's hunks (all within that range)
+fn apply_hunks(base: &[&str], start: usize, end: usize, hunks: &[Hunk]) -> String {
+    let mut out = String::new();
+    let mut pos = start;
+    for hunk in hunks {
+        out.extend(base[pos..hunk.start].iter().copied());
+        out.extend(hunk.lines.iter().copied());
+        pos = hunk.end;
+    }
+    out.extend(base[pos..end].iter().copied());
+    out
+}
+
+/// Changes turning `base` into `other`, in base order
+fn diff_hunks<'a>(base: &[&'a str], other: &[&'a str]) -> Vec<Hunk<'a>> {
+    // Common prefix/suffix are cheap to strip and keep the LCS table small
+    let prefix = base.iter().zip(other).take_while(|(a, b)| a == b).count();
+    let suffix = base[prefix..]
+        .iter()
+        .rev()
+        .zip(other[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let a = &base[prefix..base.len() - suffix];
+    let b = &other[prefix..other.len() - suffix];
+
+    if a.is_empty() && b.is_empty() {
+        return Vec::new();
+    }
+    if a.is_empty() || b.is_empty() || a.len() * b.len() > MAX_DIFF_CELLS {
+        return vec![Hunk { start: prefix, end: prefix + a.len(), lines: b.to_vec() }];
+    }
+
+    // lcs[x][y] = length of the longest common subsequence of a[x..] and b[y..]
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for x in (0..a.len()).rev() {
+        for y in (0..b.len()).rev() {
+            lcs[x][y] = if a[x] == b[y] {
+                lcs[x + 1][y + 1] + 1
+            } else {
+                lcs[x + 1][y].max(lcs[x][y + 1])
+            };
+        }
+    }
+
+    let mut hunks = Vec::new();
+    let mut current: Option<Hunk> = None;
+    let (mut x, mut y) = (0, 0);
+
+    while x < a.len() || y < b.len() {
+        if x < a.len() && y < b.len() && a[x] == b[y] {
+            hunks.extend(current.take());
+            x += 1;
+            y += 1;
+            continue;
+        }
+
+        let hunk = current.get_or_insert_with(|| Hunk { start: prefix + x, end: prefix + x, lines: Vec::new() });
+        if y < b.len() && (x == a.len() || lcs[x][y + 1] >= lcs[x + 1][y]) {
+            hunk.lines.push(b[y]);
+            y += 1;
+        } else {
+            x += 1;
+            hunk.end = prefix + x;
+        }
+    }
+    hunks.extend(current);
+
+    hunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_side_changed() {
+        let base = "a\nb\nc\n";
+        assert_eq!(merge3(base, "a\nB\nc\n", base), MergeResult::Clean("a\nB\nc\n".to_string()));
+        assert_eq!(merge3(base, base, "a\nb\nC\n"), MergeResult::Clean("a\nb\nC\n".to_string()));
+    }
+
+    #[test]
+    fn test_non_overlapping_edits_merge() {
+        let base = "title\n\none\ntwo\nthree\nfour\nfive\n";
+        let local = "title\n\nONE\ntwo\nthree\nfour\nfive\n";
+        let remote = "title\n\none\ntwo\nthree\nfour\nfive\nsix\n";
+        assert_eq!(
+            merge3(base, local, remote),
+            MergeResult::Clean("title\n\nONE\ntwo\nthree\nfour\nfive\nsix\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_identical_edits_merge() {
+        let base = "a\nb\nc\n";
+        let both = "a\nx\nc\n";
+        assert_eq!(merge3(base, both, both), MergeResult::Clean(both.to_string()));
+    }
+
+    #[test]
+    fn test_same_line_edits_conflict() {
+        let base = "a\nb\nc\n";
+        assert_eq!(
+            merge3(base, "a\nlocal\nc\n", "a\nremote\nc\n"),
+            MergeResult::Conflict
+        );
+        assert_eq!(merge3("a", "b", "c"), MergeResult::Conflict);
+    }
+
+    #[test]
+    fn test_adjacent_line_edits_conflict() {
+        let base = "a\nb\nc\n";
+        assert_eq!(merge3(base, "A\nb\nc\n", "a\nB\nc\n"), MergeResult::Conflict);
+    }
+
+    #[test]
+    fn test_insert_and_delete_elsewhere() {
+        let base = "1\n2\n3\n4\n5\n6\n";
+        let local = "0\n1\n2\n3\n4\n5\n6\n";
+        let remote = "1\n2\n3\n5\n6\n";
+        assert_eq!(merge3(base, local, remote), MergeResult::Clean("0\n1\n2\n3\n5\n6\n".to_string()));
+    }
+}
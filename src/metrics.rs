@@ -0,0 +1,167 @@
+use once_cell::sync::Lazy;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Process-wide counters, served by the optional HTTP listener (see `http.rs`)
+pub static METRICS: Lazy<Metrics> = Lazy::new(Metrics::default);
+
+#[derive(Debug, Clone, Copy)]
+pub enum Counter {
+    /// Notes and attachments sent to the server
+    Uploads,
+    /// Notes and attachments written to the vault from the server
+    Downloads,
+    /// Deletions in either direction
+    Deletes,
+    /// Changes skipped because they were the echo of our own write
+    EchoSkips,
+    /// Reducer calls that failed to send or that the server rejected
+    ReducerFailures,
+}
+
+#[derive(Default)]
+pub struct Metrics {
+    uploads: AtomicU64,
+    downloads: AtomicU64,
+    deletes: AtomicU64,
+    echo_skips: AtomicU64,
+    reducer_failures: AtomicU64,
+    reconcile_runs: AtomicU64,
+    reconcile_millis_total: AtomicU64,
+    last_reconcile_millis: AtomicU64,
+    connected: AtomicBool,
+    subscribed: AtomicBool,
+    watching: AtomicBool,
+}
+
+/// What `/healthz` reports; healthy only if all three hold
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct Health {
+    pub connected: bool,
+    pub subscribed: bool,
+    pub watching: bool,
+}
+
+impl Health {
+    pub fn is_healthy(&self) -> bool {
+        self.connected && self.subscribed && self.watching
+    }
+}
+
+/// Count one event on the global metrics
+pub fn count(counter: Counter) {
+    METRICS.count(counter);
+}
+
+impl Metrics {
+    fn counter(&self, counter: Counter) -> &AtomicU64 {
+        match counter {
+            Counter::Uploads => &self.uploads,
+            Counter::Downloads => &self.downloads,
+            Counter::Deletes => &self.deletes,
+            Counter::EchoSkips => &self.echo_skips,
+            Counter::ReducerFailures => &self.reducer_failures,
+        }
+    }
+
+    pub fn count(&self, counter: Counter) {
+        self.counter(counter).fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_reconcile(&self, duration: Duration) {
+        let millis = duration.as_millis() as u64;
+        self.reconcile_runs.fetch_add(1, Ordering::Relaxed);
+        self.reconcile_millis_total.fetch_add(millis, Ordering::Relaxed);
+        self.last_reconcile_millis.store(millis, Ordering::Relaxed);
+    }
+
+    pub fn set_connected(&self, connected: bool) {
+        self.connected.store(connected, Ordering::Relaxed);
+        if !connected {
+            self.subscribed.store(false, Ordering::Relaxed);
+        }
+    }
+
+    pub fn set_subscribed(&self, subscribed: bool) {
+        self.subscribed.store(subscribed, Ordering::Relaxed);
+    }
+
+    pub fn set_watching(&self, watching: bool) {
+        self.watching.store(watching, Ordering::Relaxed);
+    }
+
+    pub fn health(&self) -> Health {
+        Health {
+            connected: self.connected.load(Ordering::Relaxed),
+            subscribed: self.subscribed.load(Ordering::Relaxed),
+            watching: self.watching.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Everything in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, value: String| {
+            let _ = writeln!(out, "# HELP spacenotes_{} {}", name, help);
+            let _ = writeln!(out, "# TYPE spacenotes_{} {}", name, kind);
+            let _ = writeln!(out, "spacenotes_{} {}", name, value);
+        };
+        let load = |value: &AtomicU64| value.load(Ordering::Relaxed);
+        let seconds = |millis: u64| format!("{:.3}", millis as f64 / 1000.0);
+        let health = self.health();
+
+        metric("uploads_total", "counter", "Notes and attachments sent to the server.", load(&self.uploads).to_string());
+        metric("downloads_total", "counter", "Notes and attachments written to the vault from the server.", load(&self.downloads).to_string());
+        metric("deletes_total", "counter", "Deletions synced in either direction.", load(&self.deletes).to_string());
+        metric("echo_skips_total", "counter", "Changes skipped as echoes of our own writes.", load(&self.echo_skips).to_string());
+        metric("reducer_failures_total", "counter", "Reducer calls that failed to send or were rejected.", load(&self.reducer_failures).to_string());
+        metric("reconcile_runs_total", "counter", "Completed reconciliations.", load(&self.reconcile_runs).to_string());
+        metric("reconcile_duration_seconds_total", "counter", "Time spent reconciling.", seconds(load(&self.reconcile_millis_total)));
+        metric("last_reconcile_duration_seconds", "gauge", "Duration of the latest reconciliation.", seconds(load(&self.last_reconcile_millis)));
+        metric("connected", "gauge", "Whether the SpacetimeDB connection is up.", (health.connected as u8).to_string());
+        metric("subscribed", "gauge", "Whether the subscription has been applied.", (health.subscribed as u8).to_string());
+        metric("watching", "gauge", "Whether the file watcher is running.", (health.watching as u8).to_string());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters_render() {
+        let metrics = Metrics::default();
+        metrics.count(Counter::Uploads);
+        metrics.count(Counter::Uploads);
+        metrics.count(Counter::EchoSkips);
+        metrics.record_reconcile(Duration::from_millis(1500));
+        metrics.record_reconcile(Duration::from_millis(250));
+
+        let text = metrics.render();
+        assert!(text.contains("# TYPE spacenotes_uploads_total counter\nspacenotes_uploads_total 2\n"));
+        assert!(text.contains("spacenotes_echo_skips_total 1\n"));
+        assert!(text.contains("spacenotes_downloads_total 0\n"));
+        assert!(text.contains("spacenotes_reconcile_runs_total 2\n"));
+        assert!(text.contains("spacenotes_reconcile_duration_seconds_total 1.750\n"));
+        assert!(text.contains("spacenotes_last_reconcile_duration_seconds 0.250\n"));
+    }
+
+    #[test]
+    fn test_health() {
+        let metrics = Metrics::default();
+        assert!(!metrics.health().is_healthy());
+
+        metrics.set_connected(true);
+        metrics.set_subscribed(true);
+        metrics.set_watching(true);
+        assert!(metrics.health().is_healthy());
+        assert!(metrics.render().contains("spacenotes_connected 1\n"));
+
+        // Losing the connection also loses the subscription
+        metrics.set_connected(false);
+        let health = metrics.health();
+        assert!(!health.connected && !health.subscribed && !health.is_healthy());
+    }
+}
@@ -12,6 +12,7 @@ pub struct Note {
     pub size: u64,
     pub created_time: u64,
     pub modified_time: u64,
+    pub version: u64, // Server version this content is based on (0 = not known to the server)
 }
 
 impl Note {
@@ -49,6 +50,7 @@ impl Note {
             size,
             created_time,
             modified_time,
+            version: 0,
         }
     }
 }
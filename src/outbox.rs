@@ -0,0 +1,402 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::attachment::Attachment;
+use crate::folder::Folder;
+use crate::note::Note;
+use crate::sync_state::read_state_file;
+
+/// A change made in the vault that the server still has to apply
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Change {
+    UpsertNote { note: Note, expected_version: u64 },
+    DeleteNote { id: String },
+    UpsertFolder { folder: Folder },
+    DeleteFolder { path: String },
+    UpsertAttachment {
+        attachment: Attachment,
+        file: PathBuf, // Where the bytes are read from again if `data` was lost (restart)
+        #[serde(skip)]
+        data: Arc<Vec<u8>>,
+    },
+    DeleteAttachment { path: String },
+    RecordConflict { note_id: String, conflict_note_id: String, device: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    change: Change,
+    // Handed to the connection, waiting for the server to confirm (not persisted:
+    // after a restart or reconnect everything is sent again)
+    #[serde(skip)]
+    sent: bool,
+    #[serde(skip)]
+    seq: u64, // Tells entries apart while this run has them
+}
+
+/// Outbound changes, persisted to `<vault>/.spacenotes/outbox` until the server has
+/// applied them, so edits made while disconnected survive a restart and are replayed
+/// in order. Edits of a note that haven't been sent yet are coalesced into one upload.
+pub struct Outbox {
+    file: Option<PathBuf>, // None: kept in memory only
+    entries: Vec<Entry>,
+    next_seq: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct OutboxFile {
+    entries: Vec<Entry>,
+}
+
+impl Outbox {
+    /// Load the outbox file (an empty outbox if there is none yet)
+    pub fn load(vault_path: &Path) -> Result<Self> {
+        let dir = vault_path.join(".spacenotes");
+        std::fs::create_dir_all(&dir)?;
+        let file = dir.join("outbox");
+
+        let mut entries = read_state_file::<OutboxFile>(&file, "outbox")?.map_or_else(Vec::new, |f| f.entries);
+        for (seq, entry) in entries.iter_mut().enumerate() {
+            entry.seq = seq as u64;
+        }
+
+        if !entries.is_empty() {
+            tracing::info!("Loaded {} queued changes from the last run", entries.len());
+        }
+        let next_seq = entries.len() as u64;
+        Ok(Self { file: Some(file), entries, next_seq })
+    }
+
+    /// Outbox for one-shot commands: nothing is persisted
+    pub fn ephemeral() -> Self {
+        Self { file: None, entries: Vec::new(), next_seq: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Version a note reaches once its queued uploads are applied (None: none queued)
+    pub fn pending_version(&self, id: &str) -> Option<u64> {
+        self.entries
+            .iter()
+            .filter_map(|e| match &e.change {
+                Change::UpsertNote { note, expected_version } if note.id == id => Some(expected_version + 1),
+                _ => None,
+            })
+            .max()
+    }
+
+    /// Queue a change, folding it into unsent changes it supersedes
+    ///
+    /// Returns the version an upsert is actually based on: if an earlier unsent upload
+    /// of the same note is merged into this one, that's the earlier upload's base; if
+    /// one is already on its way to the server, this one builds on the version it makes.
+    pub fn push(&mut self, change: Change) -> u64 {
+        let (change, based_on) = match change {
+            Change::UpsertNote { note, expected_version } => {
+                let earlier = self
+                    .take_unsent(|c| matches!(c, Change::UpsertNote { note: n, .. } if n.id == note.id))
+                    .into_iter()
+                    .find_map(|c| match c {
+                        Change::UpsertNote { expected_version, .. } => Some(expected_version),
+                        _ => None,
+                    });
+                let expected_version = match (earlier, self.pending_version(&note.id)) {
+                    (Some(earlier), _) => earlier,
+                    (None, Some(in_flight)) => in_flight.max(expected_version),
+                    (None, None) => expected_version,
+                };
+                (Change::UpsertNote { note, expected_version }, expected_version)
+            }
+            Change::DeleteNote { id } => {
+                let pending = self.take_unsent(|c| matches!(c, Change::UpsertNote { note, .. } if note.id == id));
+                let in_flight = self
+                    .entries
+                    .iter()
+                    .any(|e| matches!(&e.change, Change::UpsertNote { note, .. } if note.id == id));
+
+                // Created and deleted while offline: the server never has to hear of it
+                let created_here = pending
+                    .iter()
+                    .any(|c| matches!(c, Change::UpsertNote { expected_version: 0, .. }));
+                if created_here && !in_flight {
+                    self.take_unsent(|c| matches!(c, Change::RecordConflict { conflict_note_id, .. } if *conflict_note_id == id));
+                    return 0;
+                }
+                (Change::DeleteNote { id }, 0)
+            }
+            Change::UpsertFolder { folder } => {
+                self.take_unsent(|c| matches!(c, Change::UpsertFolder { folder: f } if f.path == folder.path));
+                (Change::UpsertFolder { folder }, 0)
+            }
+            Change::DeleteFolder { path } => {
+                let prefix = format!("{}/", path);
+                self.take_unsent(|c| match c {
+                    Change::UpsertFolder { folder } => folder.path == path || folder.path.starts_with(&prefix),
+                    Change::UpsertAttachment { attachment, .. } => attachment.path.starts_with(&prefix),
+                    _ => false,
+                });
+                (Change::DeleteFolder { path }, 0)
+            }
+            Change::UpsertAttachment { attachment, file, data } => {
+                self.take_unsent(|c| matches!(c, Change::UpsertAttachment { attachment: a, .. } if a.path == attachment.path));
+                (Change::UpsertAttachment { attachment, file, data }, 0)
+            }
+            Change::DeleteAttachment { path } => {
+                self.take_unsent(|c| matches!(c, Change::UpsertAttachment { attachment, .. } if attachment.path == path));
+                (Change::DeleteAttachment { path }, 0)
+            }
+            change @ Change::RecordConflict { .. } => (change, 0),
+        };
+
+        self.entries.push(Entry { change, sent: false, seq: self.next_seq });
+        self.next_seq += 1;
+        based_on
+    }
+
+    /// Remove unsent changes matching `pred`, returning them
+    fn take_unsent(&mut self, pred: impl Fn(&Change) -> bool) -> Vec<Change> {
+        let mut taken = Vec::new();
+        self.entries.retain(|e| {
+            if !e.sent && pred(&e.change) {
+                taken.push(e.change.clone());
+                false
+            } else {
+                true
+            }
+        });
+        taken
+    }
+
+    /// Changes not handed to the connection yet, oldest first, with their sequence
+    /// numbers; marks them as sent
+    pub fn take_unsent_batch(&mut self) -> Vec<(u64, Change)> {
+        self.entries
+            .iter_mut()
+            .filter(|e| !e.sent)
+            .map(|e| {
+                e.sent = true;
+                (e.seq, e.change.clone())
+            })
+            .collect()
+    }
+
+    /// Sending these changes failed (we're disconnected): they have to go out again
+    ///
+    /// Changes sent before them are left alone; the server may already have applied those.
+    pub fn mark_unsent(&mut self, seqs: &[u64]) {
+        for entry in &mut self.entries {
+            if seqs.contains(&entry.seq) {
+                entry.sent = false;
+            }
+        }
+    }
+
+    /// We reconnected: whatever the old connection didn't get confirmed goes out again
+    ///
+    /// The server may have applied some of it before the connection dropped; the reducers
+    /// accept a change they have already applied, so resending one is harmless.
+    pub fn mark_all_unsent(&mut self) {
+        for entry in &mut self.entries {
+            entry.sent = false;
+        }
+    }
+
+    /// The server applied (or rejected) the oldest sent change matching `pred`
+    pub fn confirm(&mut self, pred: impl Fn(&Change) -> bool) {
+        if let Some(index) = self.entries.iter().position(|e| e.sent && pred(&e.change)) {
+            self.entries.remove(index);
+        }
+    }
+
+    /// Write the outbox to disk
+    ///
+    /// Written to a temp file and renamed, so a crash never leaves a half-written outbox.
+    pub fn save(&self) -> Result<()> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+
+        let json = serde_json::to_string(&OutboxFile { entries: self.entries.clone() })?;
+        let tmp_path = file.with_extension("tmp");
+        std::fs::write(&tmp_path, json)?;
+        std::fs::rename(&tmp_path, file)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outbox() -> Outbox {
+        Outbox::ephemeral()
+    }
+
+    fn changes(batch: Vec<(u64, Change)>) -> Vec<Change> {
+        batch.into_iter().map(|(_, change)| change).collect()
+    }
+
+    fn note(id: &str, content: &str) -> Note {
+        Note::new(id.to_string(), "Note.md".to_string(), content.to_string(), "{}".to_string(), 0, 0, 0)
+    }
+
+    fn upsert(id: &str, content: &str, expected_version: u64) -> Change {
+        Change::UpsertNote { note: note(id, content), expected_version }
+    }
+
+    #[test]
+    fn test_unsent_edits_coalesce() {
+        let mut outbox = outbox();
+        assert_eq!(outbox.push(upsert("a", "one", 5)), 5);
+        outbox.push(Change::UpsertFolder { folder: Folder::new("Projects".to_string()) });
+        assert_eq!(outbox.push(upsert("a", "two", 6)), 5);
+
+        let batch = changes(outbox.take_unsent_batch());
+        assert_eq!(batch.len(), 2);
+        assert!(matches!(&batch[1], Change::UpsertNote { note, expected_version: 5 } if note.content == "two"));
+    }
+
+    #[test]
+    fn test_sent_edits_are_not_coalesced() {
+        let mut outbox = outbox();
+        outbox.push(upsert("a", "one", 5));
+        outbox.take_unsent_batch();
+        assert_eq!(outbox.push(upsert("a", "two", 6)), 6);
+        assert_eq!(outbox.len(), 2);
+
+        outbox.confirm(|c| matches!(c, Change::UpsertNote { note, expected_version: 5 } if note.id == "a"));
+        assert_eq!(outbox.len(), 1);
+    }
+
+    #[test]
+    fn test_upload_builds_on_the_one_in_flight() {
+        let mut outbox = outbox();
+        outbox.push(upsert("a", "one", 5));
+        outbox.take_unsent_batch();
+        assert_eq!(outbox.pending_version("a"), Some(6));
+
+        // The tracker still says 5 until the server confirms the first upload
+        assert_eq!(outbox.push(upsert("a", "two", 5)), 6);
+        assert_eq!(outbox.pending_version("a"), Some(7));
+        assert_eq!(outbox.pending_version("b"), None);
+    }
+
+    #[test]
+    fn test_delete_drops_unsent_upserts() {
+        let mut outbox = outbox();
+        outbox.push(upsert("a", "one", 3));
+        outbox.push(Change::DeleteNote { id: "a".to_string() });
+
+        let batch = changes(outbox.take_unsent_batch());
+        assert_eq!(batch.len(), 1);
+        assert!(matches!(&batch[0], Change::DeleteNote { id } if id == "a"));
+    }
+
+    #[test]
+    fn test_note_created_and_deleted_offline_is_dropped() {
+        let mut outbox = outbox();
+        outbox.push(upsert("a", "one", 0));
+        outbox.push(upsert("a", "two", 1));
+        outbox.push(Change::DeleteNote { id: "a".to_string() });
+        assert!(outbox.is_empty());
+    }
+
+    #[test]
+    fn test_conflict_copy_deleted_offline_drops_its_record() {
+        let mut outbox = outbox();
+        outbox.push(upsert("copy", "ours", 0));
+        outbox.push(Change::RecordConflict {
+            note_id: "a".to_string(),
+            conflict_note_id: "copy".to_string(),
+            device: "laptop".to_string(),
+        });
+        outbox.push(Change::DeleteNote { id: "copy".to_string() });
+        assert!(outbox.is_empty());
+    }
+
+    #[test]
+    fn test_folder_delete_drops_pending_subfolders() {
+        let mut outbox = outbox();
+        outbox.push(Change::UpsertFolder { folder: Folder::new("A".to_string()) });
+        outbox.push(Change::UpsertFolder { folder: Folder::new("A/B".to_string()) });
+        outbox.push(Change::UpsertFolder { folder: Folder::new("AB".to_string()) });
+        outbox.push(Change::DeleteFolder { path: "A".to_string() });
+
+        let batch = changes(outbox.take_unsent_batch());
+        assert_eq!(batch.len(), 2);
+        assert!(matches!(&batch[0], Change::UpsertFolder { folder } if folder.path == "AB"));
+    }
+
+    #[test]
+    fn test_attachment_upserts_coalesce() {
+        let upload = |hash: &str| Change::UpsertAttachment {
+            attachment: Attachment::new("img/a.png".to_string(), hash.to_string(), 3, 0),
+            file: PathBuf::from("/vault/img/a.png"),
+            data: Arc::new(vec![1, 2, 3]),
+        };
+
+        let mut outbox = outbox();
+        outbox.push(upload("one"));
+        outbox.push(upload("two"));
+        let batch = changes(outbox.take_unsent_batch());
+        assert_eq!(batch.len(), 1);
+        assert!(matches!(&batch[0], Change::UpsertAttachment { attachment, .. } if attachment.hash == "two"));
+
+        // Deleting before the upload went out: only the delete is sent
+        outbox.push(upload("three"));
+        outbox.push(Change::DeleteAttachment { path: "img/a.png".to_string() });
+        let batch = changes(outbox.take_unsent_batch());
+        assert_eq!(batch.len(), 1);
+        assert!(matches!(&batch[0], Change::DeleteAttachment { .. }));
+    }
+
+    #[test]
+    fn test_reconnect_resends_everything() {
+        let mut outbox = outbox();
+        outbox.push(upsert("a", "one", 1));
+        outbox.take_unsent_batch();
+        assert!(outbox.take_unsent_batch().is_empty());
+
+        outbox.mark_all_unsent();
+        assert_eq!(outbox.take_unsent_batch().len(), 1);
+    }
+
+    #[test]
+    fn test_failed_send_resends_only_what_did_not_go_out() {
+        let mut outbox = outbox();
+        outbox.push(upsert("a", "one", 1));
+        outbox.take_unsent_batch();
+
+        outbox.push(upsert("b", "two", 1));
+        outbox.push(upsert("c", "three", 1));
+        let batch = outbox.take_unsent_batch();
+        assert_eq!(batch.len(), 2);
+
+        // "b" went out, "c" failed to send
+        outbox.mark_unsent(&[batch[1].0]);
+        let resent = changes(outbox.take_unsent_batch());
+        assert_eq!(resent.len(), 1);
+        assert!(matches!(&resent[0], Change::UpsertNote { note, .. } if note.id == "c"));
+    }
+
+    #[test]
+    fn test_corrupt_file_is_set_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        std::fs::create_dir_all(vault.join(".spacenotes")).unwrap();
+        std::fs::write(vault.join(".spacenotes/outbox"), "{\"entries\": [").unwrap();
+
+        let outbox = Outbox::load(vault).unwrap();
+        assert!(outbox.is_empty());
+        assert!(vault.join(".spacenotes/outbox.corrupt").exists());
+    }
+}
@@ -1,93 +1,805 @@
 use anyhow::Result;
+use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
+use crate::attachment::Attachment;
 use crate::client::SpacetimeClient;
+use crate::conflict::save_conflict_copy;
+use crate::folder::Folder;
+use crate::id_index::NoteIds;
+use crate::ignore_rules::IgnoreRules;
+use crate::merge::{merge3, MergeResult};
+use crate::metrics::{self, Counter, METRICS};
 use crate::note::Note;
-use crate::scanner::scan_notes;
+use crate::sanitize::disk_path;
+use crate::scanner::{read_attachment_with_data_at, scan_attachments, scan_folders, scan_notes};
 use crate::tracker::ContentTracker;
-use crate::writer::write_note_to_disk;
+use crate::writer::{write_attachment_to_disk, write_note_to_disk};
 
-/// Reconcile local vault with SpacetimeDB on startup
-/// Uses last-write-wins based on timestamps
-pub fn reconcile_on_startup(
+/// Bring the vault and SpacetimeDB in line: notes, then folders, then attachments
+/// Run on startup and again after every reconnect.
+pub fn reconcile_all(
     vault_path: &Path,
     client: &SpacetimeClient,
     tracker: &ContentTracker,
+    ignore: &IgnoreRules,
+    device: &str,
 ) -> Result<()> {
-    // 1. Get all notes from SpacetimeDB
-    let server_notes = client.get_all_notes();
+    let start = std::time::Instant::now();
+
+    tracing::info!("Reconciling with server...");
+    reconcile_on_startup(vault_path, client, tracker, ignore, device)?;
+
+    tracing::info!("Reconciling folders...");
+    reconcile_folders(vault_path, client, ignore)?;
+
+    tracing::info!("Reconciling attachments...");
+    reconcile_attachments(vault_path, client, tracker, ignore)?;
+
+    METRICS.record_reconcile(start.elapsed());
+    Ok(())
+}
 
-    // 2. Get all local notes
-    let local_notes = scan_notes(vault_path)?;
+/// What reconciliation does with a note
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoteAction {
+    Download,     // Server version replaces the local file
+    Upload,       // Local file replaces the server version
+    Merge,        // Edited on both sides, merged cleanly
+    Conflict,     // Edited on both sides, overlapping: server wins, ours becomes a conflict copy
+    DeleteLocal,  // Deleted on the server while we were away
+    DeleteServer, // Deleted locally while the daemon was down
+    Touched,      // Same content on both sides, only the timestamps differ
+    Unchanged,
+}
+
+impl NoteAction {
+    fn label(self) -> &'static str {
+        match self {
+            NoteAction::Download => "download",
+            NoteAction::Upload => "upload",
+            NoteAction::Merge => "merge",
+            NoteAction::Conflict => "conflict",
+            NoteAction::DeleteLocal => "delete_local",
+            NoteAction::DeleteServer => "delete_server",
+            NoteAction::Touched => "touched",
+            NoteAction::Unchanged => "unchanged",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlannedNote {
+    pub id: String,
+    pub action: NoteAction,
+    pub path: String,
+    // Set if the note ends up at a different path on one side (a rename)
+    pub renamed_from: Option<String>,
+    #[serde(skip)]
+    merged: Option<Note>,
+}
 
-    // 3. Build lookup maps by ID
-    let server_map: HashMap<String, Note> = server_notes
+/// Everything reconciliation would change, computed without touching disk or server
+#[derive(Debug, Default, Serialize)]
+pub struct Plan {
+    pub notes: Vec<PlannedNote>,
+    pub folders_to_create: Vec<String>, // On the server, missing locally
+    pub folders_to_upload: Vec<String>, // Local, missing on the server
+}
+
+impl Plan {
+    pub fn count(&self, action: NoteAction) -> usize {
+        self.notes.iter().filter(|n| n.action == action).count()
+    }
+
+    /// Human-readable report of everything that would transfer or delete something
+    pub fn report(&self) -> String {
+        let mut out = String::new();
+        for note in self.notes.iter().filter(|n| !matches!(n.action, NoteAction::Touched | NoteAction::Unchanged)) {
+            let action = note.action.label();
+            match &note.renamed_from {
+                Some(from) => out.push_str(&format!("{:<13} {} (renamed from {})\n", action, note.path, from)),
+                None => out.push_str(&format!("{:<13} {}\n", action, note.path)),
+            }
+        }
+        for path in &self.folders_to_create {
+            out.push_str(&format!("{:<13} {}/\n", "create_folder", path));
+        }
+        for path in &self.folders_to_upload {
+            out.push_str(&format!("{:<13} {}/\n", "upload_folder", path));
+        }
+
+        out.push_str(&format!(
+            "\n{} downloads, {} uploads, {} merges, {} conflicts, {} local deletes, {} server deletes, {} renames, {} touched but unchanged, {} unchanged; {} folders to create, {} to upload\n",
+            self.count(NoteAction::Download),
+            self.count(NoteAction::Upload),
+            self.count(NoteAction::Merge),
+            self.count(NoteAction::Conflict),
+            self.count(NoteAction::DeleteLocal),
+            self.count(NoteAction::DeleteServer),
+            self.notes.iter().filter(|n| n.renamed_from.is_some()).count(),
+            self.count(NoteAction::Touched),
+            self.count(NoteAction::Unchanged),
+            self.folders_to_create.len(),
+            self.folders_to_upload.len(),
+        ));
+        out
+    }
+}
+
+/// Local and server notes by ID, as reconciliation sees them
+struct Snapshot {
+    local: HashMap<String, Note>,
+    server: HashMap<String, Note>,
+    tombstones: HashMap<String, u64>, // Note ID -> deletion time (ms)
+}
+
+fn snapshot(vault_path: &Path, client: &SpacetimeClient, ids: &NoteIds, ignore: &IgnoreRules) -> Result<Snapshot> {
+    // Server notes at ignored paths stay out of the vault
+    let server = client
+        .get_all_notes()
         .into_iter()
+        .filter(|n| !ignore.is_ignored(&n.path, false))
         .map(|n| (n.id.clone(), n))
         .collect();
 
-    let local_map: HashMap<String, Note> = local_notes
+    let local = scan_notes(vault_path, ids, ignore)?
         .into_iter()
         .map(|n| (n.id.clone(), n))
         .collect();
 
-    // 4. Reconcile each note by ID
-    let all_ids: HashSet<&String> = server_map.keys().chain(local_map.keys()).collect();
+    Ok(Snapshot { local, server, tombstones: client.get_tombstones() })
+}
+
+/// Work out what reconciliation would do, without changing anything
+pub fn plan(vault_path: &Path, client: &SpacetimeClient, tracker: &ContentTracker, ignore: &IgnoreRules) -> Result<Plan> {
+    let snapshot = snapshot(vault_path, client, tracker.ids(), ignore)?;
+    let (folders_to_create, folders_to_upload) = plan_folders(vault_path, client, ignore)?;
+
+    Ok(Plan {
+        notes: plan_notes(&snapshot, tracker),
+        folders_to_create,
+        folders_to_upload,
+    })
+}
+
+/// Decide each note by ID
+/// Notes whose body and frontmatter match on both sides are left alone, whatever their
+/// timestamps. Otherwise the side that changed since the last sync wins; if both did, the bodies are
+/// three-way merged against the last-synced base; if the edits overlap, the server's
+/// version is kept and ours is saved as a conflict copy. Without a base, newest timestamp wins
+/// (a tie is treated as a conflict).
+/// Notes the saved sync state knows about but that vanished from disk were deleted
+/// while the daemon was down, and are deleted on the server too.
+fn plan_notes(snapshot: &Snapshot, tracker: &ContentTracker) -> Vec<PlannedNote> {
+    let all_ids: HashSet<&String> = snapshot.server.keys().chain(snapshot.local.keys()).collect();
+
+    let mut notes: Vec<PlannedNote> = all_ids
+        .into_iter()
+        .map(|id| {
+            let local = snapshot.local.get(id);
+            let server = snapshot.server.get(id);
+            let (action, merged) = decide(id, local, server, snapshot, tracker);
+
+            // Where the note ends up, and where it was on the side that moves
+            let (path, other) = match action {
+                NoteAction::Upload
+                | NoteAction::Merge
+                | NoteAction::DeleteLocal
+                | NoteAction::Touched
+                | NoteAction::Unchanged => {
+                    (local.map(|n| &n.path), server.map(|n| &n.path))
+                }
+                NoteAction::Download | NoteAction::Conflict | NoteAction::DeleteServer => {
+                    (server.map(|n| &n.path), local.map(|n| &n.path))
+                }
+            };
+            let path = path.or(other).cloned().unwrap_or_default();
+            let renamed_from = other
+                .filter(|other| **other != path && !matches!(action, NoteAction::Touched | NoteAction::Unchanged))
+                .cloned();
+
+            PlannedNote { id: id.clone(), action, path, renamed_from, merged }
+        })
+        .collect();
+
+    notes.sort_by(|a, b| a.path.cmp(&b.path));
+    notes
+}
+
+fn decide(
+    id: &str,
+    local: Option<&Note>,
+    server: Option<&Note>,
+    snapshot: &Snapshot,
+    tracker: &ContentTracker,
+) -> (NoteAction, Option<Note>) {
+    match (local, server) {
+        // Both exist with the same body and frontmatter - nothing to transfer, whatever the timestamps say
+        (Some(local), Some(server)) if same_content(local, server) => {
+            if local.path != server.path {
+                // Same note at two paths: the server's moved if ours is where it was last
+                // synced; otherwise the file was renamed here (or the server has the lossy
+                // ASCII name older versions stored) and the real name on disk wins
+                if tracker.synced(id).is_some_and(|s| s.path == local.path) {
+                    (NoteAction::Download, None)
+                } else {
+                    (NoteAction::Upload, None)
+                }
+            } else if local.modified_time != server.modified_time {
+                (NoteAction::Touched, None)
+            } else {
+                (NoteAction::Unchanged, None)
+            }
+        }
+
+        // Both exist and differ - compare against the last-synced base, then timestamps
+        (Some(local), Some(server)) => {
+            let base = tracker.base(id);
+            let synced = tracker.synced(id);
+            // The frontmatter has no base of its own, just its hash as of the last sync
+            let frontmatter_changed = |note: &Note| {
+                synced.as_ref().is_some_and(|s| {
+                    !s.frontmatter_hash.is_empty()
+                        && ContentTracker::frontmatter_hash(&note.frontmatter) != s.frontmatter_hash
+                })
+            };
+            let (local_changed, server_changed) = match (&base, &synced) {
+                (Some(base), _) => (
+                    *base != local.content || frontmatter_changed(local),
+                    *base != server.content || frontmatter_changed(server),
+                ),
+                // No base body, but the last run still recorded what was in sync
+                (None, Some(synced)) => (
+                    ContentTracker::hash(&local.content) != synced.hash || frontmatter_changed(local),
+                    server.version != synced.version,
+                ),
+                (None, None) => (true, true),
+            };
+
+            // Without a base (or when neither body changed) fall back to timestamps
+            let same_state = local_changed == server_changed;
+            let server_wins = (server_changed && !local_changed)
+                || (same_state && server.modified_time > local.modified_time);
+            let local_wins = (local_changed && !server_changed)
+                || (same_state && local.modified_time > server.modified_time);
+
+            if let Some(base) = base.filter(|_| local_changed && server_changed && local.content != server.content) {
+                // Edited on both sides since the last sync - merge instead of picking a winner
+                match merge_note(&base, local, server) {
+                    Some(note) => (NoteAction::Merge, Some(note)),
+                    None => (NoteAction::Conflict, None),
+                }
+            } else if server_wins {
+                (NoteAction::Download, None)
+            } else if local_wins {
+                (NoteAction::Upload, None)
+            } else {
+                // Same timestamps on different content (e.g. a restored backup): keep the
+                // server's version and ours as a conflict copy rather than guess
+                (NoteAction::Conflict, None)
+            }
+        }
+
+        // Only on server, but it was in sync last run and untouched since - deleted locally while offline
+        (None, Some(server)) if tracker.synced(id).is_some_and(|s| s.version == server.version) => {
+            (NoteAction::DeleteServer, None)
+        }
+
+        // Only on server - download
+        (None, Some(_)) => (NoteAction::Download, None),
+
+        // Only local, but deleted on the server while we were away (and not edited since) - delete
+        (Some(local), None) if snapshot.tombstones.get(id).is_some_and(|&at| local.modified_time <= at) => {
+            (NoteAction::DeleteLocal, None)
+        }
+
+        // Only local - upload
+        (Some(_), None) => (NoteAction::Upload, None),
+
+        (None, None) => unreachable!(),
+    }
+}
+
+/// Whether two versions of a note have the same body and frontmatter
+fn same_content(a: &Note, b: &Note) -> bool {
+    ContentTracker::note_hash(&a.content, &a.frontmatter) == ContentTracker::note_hash(&b.content, &b.frontmatter)
+}
+
+/// Reconcile local vault with SpacetimeDB on startup (see `plan_notes` for the rules)
+pub fn reconcile_on_startup(
+    vault_path: &Path,
+    client: &SpacetimeClient,
+    tracker: &ContentTracker,
+    ignore: &IgnoreRules,
+    device: &str,
+) -> Result<()> {
+    let snapshot = snapshot(vault_path, client, tracker.ids(), ignore)?;
+    let plan = Plan { notes: plan_notes(&snapshot, tracker), ..Plan::default() };
+
+    for planned in &plan.notes {
+        let id = &planned.id;
+        let local = snapshot.local.get(id);
+        let server = snapshot.server.get(id);
+
+        match (planned.action, local, server) {
+            (NoteAction::Merge, Some(local), Some(server)) => {
+                let note = planned.merged.as_ref().unwrap_or(local);
+                tracker.uploading(note);
+                tracker.set_version(&note.id, server.version);
+                write_note_to_disk(vault_path, note, tracker.ids())?;
+                client.upsert_note(note, server.version);
+                tracing::info!("Merged local and server edits: {} (ID: {})", note.path, id);
+            }
+
+            // Overlapping edits: the server keeps its version, ours becomes a copy
+            (NoteAction::Conflict, Some(local), Some(server)) => {
+                save_conflict_copy(vault_path, client, tracker, local, id, device)?;
+                download(vault_path, tracker, local, server)?;
+            }
+
+            (NoteAction::Download, local, Some(server)) => {
+                download(vault_path, tracker, local.unwrap_or(server), server)?;
+                tracing::debug!("Downloaded: {} (ID: {})", server.path, id);
+            }
+
+            (NoteAction::Upload, Some(local), server) => {
+                let expected_version = server.map_or(0, |s| s.version);
+                tracker.uploading(local);
+                tracker.set_version(&local.id, expected_version);
+                client.upsert_note(local, expected_version);
+                tracing::debug!("Uploaded: {} (ID: {})", local.path, id);
+            }
+
+            (NoteAction::DeleteLocal, Some(local), None) => {
+                std::fs::remove_file(disk_path(vault_path, &local.path)?)?;
+                tracker.remove(&local.id);
+                metrics::count(Counter::Deletes);
+                tracing::info!("Deleted locally (deleted on server): {} (ID: {})", local.path, id);
+            }
+
+            (NoteAction::DeleteServer, None, Some(server)) => {
+                client.delete_note(id);
+                tracker.remove(id);
+                tracing::info!("Deleted on server (deleted locally while offline): {} (ID: {})", server.path, id);
+            }
+
+            // Nothing to sync - just update tracker
+            (NoteAction::Touched | NoteAction::Unchanged, Some(local), Some(server)) => {
+                tracker.update(local);
+                tracker.set_version(&local.id, server.version);
+            }
+
+            (action, _, _) => unreachable!("{:?} planned for {}", action, id),
+        }
+    }
+
+    tracker.flush();
+
+    tracing::info!(
+        "Reconciliation complete: {} downloaded, {} uploaded, {} merged, {} conflicts, {} deleted, {} touched but unchanged, {} unchanged",
+        plan.count(NoteAction::Download),
+        plan.count(NoteAction::Upload),
+        plan.count(NoteAction::Merge),
+        plan.count(NoteAction::Conflict),
+        plan.count(NoteAction::DeleteLocal) + plan.count(NoteAction::DeleteServer),
+        plan.count(NoteAction::Touched),
+        plan.count(NoteAction::Unchanged),
+    );
+
+    Ok(())
+}
+
+/// Write the server's version of a note, removing the local file if it was elsewhere
+pub fn download(vault_path: &Path, tracker: &ContentTracker, local: &Note, server: &Note) -> Result<()> {
+    if local.path != server.path {
+        let old_path = disk_path(vault_path, &local.path)?;
+        if old_path.exists() {
+            std::fs::remove_file(old_path)?;
+        }
+    }
+
+    tracker.update(server);
+    tracker.set_version(&server.id, server.version);
+    write_note_to_disk(vault_path, server, tracker.ids())?;
+    metrics::count(Counter::Downloads);
+    Ok(())
+}
+
+/// Combine a note edited on both sides: bodies are three-way merged, frontmatter
+/// comes from the newer side. Returns None if the edits overlap.
+fn merge_note(base: &str, local: &Note, server: &Note) -> Option<Note> {
+    let content = match merge3(base, &local.content, &server.content) {
+        MergeResult::Clean(content) => content,
+        MergeResult::Conflict => return None,
+    };
+
+    let frontmatter = if server.modified_time > local.modified_time {
+        server.frontmatter.clone()
+    } else {
+        local.frontmatter.clone()
+    };
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+
+    Some(Note {
+        size: content.len() as u64,
+        content,
+        frontmatter,
+        modified_time: now.max(local.modified_time).max(server.modified_time),
+        ..local.clone()
+    })
+}
+
+/// Folders to create locally (on the server only) and to upload (local only)
+fn plan_folders(vault_path: &Path, client: &SpacetimeClient, ignore: &IgnoreRules) -> Result<(Vec<String>, Vec<String>)> {
+    let local: HashSet<String> = scan_folders(vault_path, ignore)?.into_iter().map(|f| f.path).collect();
+    let server: HashSet<String> = client.get_all_folders().into_iter().map(|f| f.path).collect();
+
+    // Skip @eaDir folders (Synology metadata) and anything in .spacenotesignore
+    let mut to_create: Vec<String> = server
+        .iter()
+        .filter(|path| !local.contains(*path) && !ignore.is_ignored(path, true))
+        .cloned()
+        .collect();
+    let mut to_upload: Vec<String> = local.difference(&server).cloned().collect();
+
+    to_create.sort();
+    to_upload.sort();
+    Ok((to_create, to_upload))
+}
+
+/// Reconcile folders (two-way sync): create server folders locally, upload local ones
+pub fn reconcile_folders(vault_path: &Path, client: &SpacetimeClient, ignore: &IgnoreRules) -> Result<()> {
+    let (to_create, to_upload) = plan_folders(vault_path, client, ignore)?;
+
+    // Create folders that exist on server but not locally
+    for path in &to_create {
+        let folder_path = match disk_path(vault_path, path) {
+            Ok(folder_path) => folder_path,
+            Err(e) => {
+                tracing::error!("Failed to create folder {}: {}", path, e);
+                continue;
+            }
+        };
+        if !folder_path.exists() {
+            if let Err(e) = std::fs::create_dir_all(&folder_path) {
+                tracing::error!("Failed to create folder {}: {}", path, e);
+            } else {
+                tracing::info!("Created local folder from server: {}", path);
+            }
+        }
+    }
+
+    // Upload folders that exist locally but not on server
+    let folders: Vec<Folder> = to_upload.into_iter().map(Folder::new).collect();
+    client.sync_folders(&folders);
+
+    Ok(())
+}
+
+/// What reconciliation does with an attachment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AttachmentAction {
+    Download,
+    Upload,
+    DeleteLocal,
+    DeleteServer,
+    Unchanged,
+}
+
+/// Decide an attachment by path, against the hash it had when it was last in sync
+///
+/// An attachment that was in sync and is now missing on one side was deleted there
+/// while we were away, unless the other side changed it since. Without a sync record,
+/// the newer modification time wins and a file on one side only is copied over.
+fn decide_attachment(local: Option<&Attachment>, server: Option<&Attachment>, synced: Option<&str>) -> AttachmentAction {
+    match (local, server) {
+        (Some(local), Some(server)) if local.hash == server.hash => AttachmentAction::Unchanged,
+
+        // Both exist with different bytes - the side that changed since the last sync wins, else newer
+        (Some(local), Some(server)) => {
+            if synced == Some(local.hash.as_str()) {
+                AttachmentAction::Download
+            } else if synced == Some(server.hash.as_str()) {
+                AttachmentAction::Upload
+            } else if server.modified_time > local.modified_time {
+                AttachmentAction::Download
+            } else {
+                AttachmentAction::Upload
+            }
+        }
+
+        // Only on server, but in sync last run and unchanged since - deleted locally while offline
+        (None, Some(server)) if synced == Some(server.hash.as_str()) => AttachmentAction::DeleteServer,
+        (None, Some(_)) => AttachmentAction::Download,
+
+        // Only local, but in sync last run and unchanged since - deleted on the server (it's in the trash)
+        (Some(local), None) if synced == Some(local.hash.as_str()) => AttachmentAction::DeleteLocal,
+        (Some(_), None) => AttachmentAction::Upload,
+
+        (None, None) => unreachable!(),
+    }
+}
+
+/// Reconcile local attachments with SpacetimeDB on startup (see `decide_attachment` for the rules)
+pub fn reconcile_attachments(
+    vault_path: &Path,
+    client: &SpacetimeClient,
+    tracker: &ContentTracker,
+    ignore: &IgnoreRules,
+) -> Result<()> {
+    let server_map: HashMap<String, Attachment> = client
+        .get_all_attachments()
+        .into_iter()
+        .filter(|a| !ignore.is_ignored(&a.path, false))
+        .map(|a| (a.path.clone(), a))
+        .collect();
+
+    let local_map: HashMap<String, Attachment> = scan_attachments(vault_path, vault_path, ignore)?
+        .into_iter()
+        .map(|a| (a.path.clone(), a))
+        .collect();
+
+    let all_paths: HashSet<&String> = server_map.keys().chain(local_map.keys()).collect();
 
     let mut downloaded = 0;
     let mut uploaded = 0;
+    let mut deleted = 0;
     let mut unchanged = 0;
 
-    for id in all_ids {
-        match (local_map.get(id), server_map.get(id)) {
-            // Both exist - compare timestamps
-            (Some(local), Some(server)) => {
-                if server.modified_time > local.modified_time {
-                    // Server is newer - download to disk
-                    tracker.update(&server.id, &server.content);
-                    write_note_to_disk(vault_path, server)?;
-                    tracing::debug!("Downloaded newer: {} (ID: {})", server.path, id);
-                    downloaded += 1;
-                } else if local.modified_time > server.modified_time {
-                    // Local is newer - push to server
-                    tracker.update(&local.id, &local.content);
-                    client.upsert_note(local);
-                    tracing::debug!("Uploaded newer: {} (ID: {})", local.path, id);
-                    uploaded += 1;
-                } else {
-                    // Equal timestamps - just update tracker
-                    tracker.update(&local.id, &local.content);
-                    unchanged += 1;
-                }
+    for path in all_paths {
+        let local = local_map.get(path);
+        let server = server_map.get(path);
+        let synced = tracker.synced_attachment(path);
+
+        match (decide_attachment(local, server, synced.as_deref()), local, server) {
+            (AttachmentAction::Unchanged, Some(local), _) => {
+                tracker.update_attachment(path, &local.hash);
+                unchanged += 1;
             }
 
-            // Only on server - download
-            (None, Some(server)) => {
-                tracker.update(&server.id, &server.content);
-                write_note_to_disk(vault_path, server)?;
-                tracing::debug!("Downloaded new: {} (ID: {})", server.path, id);
+            (AttachmentAction::Download, _, Some(server)) => {
+                download_attachment(vault_path, client, tracker, server)?;
                 downloaded += 1;
             }
 
-            // Only local - upload (WARNING: resurrects deleted files)
-            (Some(local), None) => {
-                tracker.update(&local.id, &local.content);
-                client.upsert_note(local);
-                tracing::debug!("Uploaded new: {} (ID: {})", local.path, id);
+            (AttachmentAction::Upload, Some(local), _) => {
+                upload_attachment(vault_path, client, tracker, local)?;
                 uploaded += 1;
             }
 
-            (None, None) => unreachable!(),
+            (AttachmentAction::DeleteLocal, Some(local), None) => {
+                std::fs::remove_file(disk_path(vault_path, &local.path)?)?;
+                tracker.remove_attachment(path);
+                metrics::count(Counter::Deletes);
+                tracing::info!("Deleted attachment locally (deleted on server): {}", path);
+                deleted += 1;
+            }
+
+            (AttachmentAction::DeleteServer, None, Some(_)) => {
+                client.delete_attachment(path);
+                tracker.remove_attachment(path);
+                tracing::info!("Deleted attachment on server (deleted locally while offline): {}", path);
+                deleted += 1;
+            }
+
+            (action, _, _) => unreachable!("{:?} planned for {}", action, path),
         }
     }
 
+    tracker.flush();
+
     tracing::info!(
-        "Reconciliation complete: {} downloaded, {} uploaded, {} unchanged",
+        "Attachment reconciliation complete: {} downloaded, {} uploaded, {} deleted, {} unchanged",
         downloaded,
         uploaded,
+        deleted,
         unchanged
     );
 
     Ok(())
 }
+
+fn download_attachment(
+    vault_path: &Path,
+    client: &SpacetimeClient,
+    tracker: &ContentTracker,
+    attachment: &Attachment,
+) -> Result<()> {
+    let Some(data) = client.get_attachment_data(&attachment.path) else {
+        tracing::warn!("Attachment {} is missing chunks on the server, skipping", attachment.path);
+        return Ok(());
+    };
+
+    tracker.expect_attachment(&attachment.path, &attachment.hash);
+    write_attachment_to_disk(vault_path, attachment, &data)?;
+    tracker.update_attachment(&attachment.path, &attachment.hash);
+    metrics::count(Counter::Downloads);
+    tracing::debug!("Downloaded attachment: {}", attachment.path);
+    Ok(())
+}
+
+fn upload_attachment(
+    vault_path: &Path,
+    client: &SpacetimeClient,
+    tracker: &ContentTracker,
+    attachment: &Attachment,
+) -> Result<()> {
+    // Read again, hashing the bytes that are actually sent (the file may have changed since the scan)
+    let file = disk_path(vault_path, &attachment.path)?;
+    let (attachment, data) = match read_attachment_with_data_at(vault_path, &file) {
+        Ok(Some(read)) => read,
+        Ok(None) => return Ok(()),
+        Err(e) => {
+            tracing::warn!("Failed to read attachment {} for upload: {}", attachment.path, e);
+            return Ok(());
+        }
+    };
+
+    tracker.expect_attachment(&attachment.path, &attachment.hash);
+    client.upload_attachment(&attachment, &file, data);
+    tracing::debug!("Uploaded attachment: {}", attachment.path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::id_index::IdMode;
+
+    fn tracker() -> (ContentTracker, tempfile::TempDir) {
+        let vault = tempfile::tempdir().unwrap();
+        (ContentTracker::load(vault.path(), IdMode::Frontmatter).unwrap(), vault)
+    }
+
+    fn note(body: &str, frontmatter: &str, modified_time: u64) -> Note {
+        Note::new("a".to_string(), "Note.md".to_string(), body.to_string(), frontmatter.to_string(), 0, 0, modified_time)
+    }
+
+    fn plan(tracker: &ContentTracker, local: Note, server: Note) -> PlannedNote {
+        let snapshot = Snapshot {
+            local: HashMap::from([("a".to_string(), local)]),
+            server: HashMap::from([("a".to_string(), server)]),
+            tombstones: HashMap::new(),
+        };
+        plan_notes(&snapshot, tracker).remove(0)
+    }
+
+    fn action(tracker: &ContentTracker, local: Note, server: Note) -> NoteAction {
+        plan(tracker, local, server).action
+    }
+
+    fn action_after_deletion(tracker: &ContentTracker, local: Note, deleted_at: u64) -> NoteAction {
+        let snapshot = Snapshot {
+            local: HashMap::from([("a".to_string(), local)]),
+            server: HashMap::new(),
+            tombstones: HashMap::from([("a".to_string(), deleted_at)]),
+        };
+        plan_notes(&snapshot, tracker)[0].action
+    }
+
+    #[test]
+    fn test_deleted_on_server_is_deleted_locally() {
+        let (tracker, _vault) = tracker();
+
+        assert_eq!(action_after_deletion(&tracker, note("Body", "{}", 1000), 2000), NoteAction::DeleteLocal);
+        assert_eq!(action_after_deletion(&tracker, note("Body", "{}", 2000), 2000), NoteAction::DeleteLocal);
+    }
+
+    #[test]
+    fn test_edited_after_deletion_is_uploaded() {
+        let (tracker, _vault) = tracker();
+
+        // Edited here after another device deleted it: the edit brings it back
+        assert_eq!(action_after_deletion(&tracker, note("Edited", "{}", 3000), 2000), NoteAction::Upload);
+    }
+
+    #[test]
+    fn test_identical_content_is_not_transferred() {
+        let (tracker, _vault) = tracker();
+
+        // A newer mtime alone (e.g. a `touch` or a copy) doesn't make an upload
+        assert_eq!(action(&tracker, note("Body", "{}", 2000), note("Body", "{}", 1000)), NoteAction::Touched);
+        assert_eq!(action(&tracker, note("Body", "{}", 1000), note("Body", "{}", 2000)), NoteAction::Touched);
+        assert_eq!(action(&tracker, note("Body", "{}", 1000), note("Body", "{}", 1000)), NoteAction::Unchanged);
+    }
+
+    #[test]
+    fn test_different_content_with_equal_timestamps() {
+        let (tracker, _vault) = tracker();
+
+        // Nothing to tell which side is newer: the server wins and ours becomes a copy
+        assert_eq!(action(&tracker, note("Restored", "{}", 1000), note("Body", "{}", 1000)), NoteAction::Conflict);
+        assert_eq!(
+            action(&tracker, note("Body", r#"{"status":"done"}"#, 1000), note("Body", "{}", 1000)),
+            NoteAction::Conflict
+        );
+    }
+
+    #[test]
+    fn test_changed_side_wins_over_timestamps() {
+        let (tracker, _vault) = tracker();
+        tracker.update(&note("Body", r#"{"status":"draft"}"#, 1000));
+
+        // Only the local frontmatter changed since the last sync, though the server's mtime is newer
+        assert_eq!(
+            action(&tracker, note("Body", r#"{"status":"done"}"#, 1000), note("Body", r#"{"status":"draft"}"#, 5000)),
+            NoteAction::Upload
+        );
+    }
+
+    #[test]
+    fn test_edits_to_different_lines_are_merged() {
+        let (tracker, _vault) = tracker();
+        tracker.update(&note("One\nTwo\nThree\nFour\nFive\n", "{}", 1000));
+
+        let planned = plan(
+            &tracker,
+            note("One (local)\nTwo\nThree\nFour\nFive\n", "{}", 2000),
+            note("One\nTwo\nThree\nFour\nFive (server)\n", "{}", 3000),
+        );
+        assert_eq!(planned.action, NoteAction::Merge);
+        assert_eq!(planned.merged.unwrap().content, "One (local)\nTwo\nThree\nFour\nFive (server)\n");
+    }
+
+    #[test]
+    fn test_edits_to_the_same_lines_conflict() {
+        let (tracker, _vault) = tracker();
+        tracker.update(&note("One\nTwo\nThree\n", "{}", 1000));
+
+        let planned = plan(
+            &tracker,
+            note("One\nTwo (local)\nThree\n", "{}", 2000),
+            note("One\nTwo (server)\nThree\n", "{}", 3000),
+        );
+        assert_eq!(planned.action, NoteAction::Conflict);
+        assert!(planned.merged.is_none());
+    }
+
+    fn attachment(hash: &str, modified_time: u64) -> Attachment {
+        Attachment::new("img/diagram.png".to_string(), hash.to_string(), 0, modified_time)
+    }
+
+    #[test]
+    fn test_deleted_attachment_is_not_resurrected() {
+        // In sync last run, now only on disk: it was deleted on the server
+        assert_eq!(
+            decide_attachment(Some(&attachment("a1", 1000)), None, Some("a1")),
+            AttachmentAction::DeleteLocal
+        );
+        // ...unless it was edited here since
+        assert_eq!(decide_attachment(Some(&attachment("b2", 2000)), None, Some("a1")), AttachmentAction::Upload);
+        // Never synced: a new file
+        assert_eq!(decide_attachment(Some(&attachment("a1", 1000)), None, None), AttachmentAction::Upload);
+    }
+
+    #[test]
+    fn test_attachment_deleted_offline_is_deleted_on_server() {
+        assert_eq!(
+            decide_attachment(None, Some(&attachment("a1", 1000)), Some("a1")),
+            AttachmentAction::DeleteServer
+        );
+        // Replaced on the server since: the new version comes down
+        assert_eq!(decide_attachment(None, Some(&attachment("b2", 2000)), Some("a1")), AttachmentAction::Download);
+        assert_eq!(decide_attachment(None, Some(&attachment("a1", 1000)), None), AttachmentAction::Download);
+    }
+
+    #[test]
+    fn test_changed_attachment_wins_over_timestamps() {
+        let local = attachment("b2", 1000);
+        let server = attachment("a1", 5000);
+
+        // Only the local copy changed since the last sync, though the server's mtime is newer
+        assert_eq!(decide_attachment(Some(&local), Some(&server), Some("a1")), AttachmentAction::Upload);
+        assert_eq!(decide_attachment(Some(&local), Some(&server), Some("b2")), AttachmentAction::Download);
+        // No sync record: newer wins
+        assert_eq!(decide_attachment(Some(&local), Some(&server), None), AttachmentAction::Download);
+        assert_eq!(decide_attachment(Some(&local), Some(&local.clone()), None), AttachmentAction::Unchanged);
+    }
+}
@@ -1,38 +1,59 @@
-/// Sanitizes a file path to be URI-safe by replacing problematic Unicode characters
-/// with their ASCII equivalents or safe alternatives.
+use anyhow::{bail, Result};
+use std::path::{Path, PathBuf};
+use unicode_normalization::UnicodeNormalization;
+
+/// The form a vault-relative path is stored in: the exact file name, any Unicode,
+/// in NFC and with `/` separators.
 ///
-/// This prevents URI encoding/decoding issues in clients (especially Flutter)
-/// when paths contain characters like smart quotes, ellipsis, etc.
+/// macOS hands out decomposed (NFD) names, so `Café.md` created there and on Linux
+/// would otherwise be two different notes. Clients that need a URI-safe form use the
+/// note's `safe_path` column, which the server derives from this.
 ///
 /// # Examples
 /// ```
-/// let path = "folder/Note with … ellipsis.md";
-/// let sanitized = sanitize_path(path);
-/// assert_eq!(sanitized, "folder/Note with ... ellipsis.md");
+/// let path = "folder/Cafe\u{301} ☕.md";
+/// assert_eq!(normalize_path(path), "folder/Café ☕.md");
 /// ```
-pub fn sanitize_path(path: &str) -> String {
-    path
-        // Replace ellipsis with three dots
-        .replace('\u{2026}', "...")
-        // Replace smart quotes with regular quotes
-        .replace('\u{201C}', "\"")  // Left double quote
-        .replace('\u{201D}', "\"")  // Right double quote
-        .replace('\u{2018}', "'")   // Left single quote
-        .replace('\u{2019}', "'")   // Right single quote
-        // Replace em dash and en dash with regular dash
-        .replace('\u{2014}', "-")   // Em dash
-        .replace('\u{2013}', "-")   // En dash
-        .chars()
-        .map(|c| {
-            // Keep ASCII alphanumeric, forward slash (for paths), spaces, and safe punctuation
-            if c.is_ascii_alphanumeric() || "/. -_,()[]\"'".contains(c) {
-                c
-            } else {
-                // Replace any other character with underscore
-                '_'
-            }
-        })
-        .collect()
+pub fn normalize_path(path: &str) -> String {
+    path.replace('\\', "/").nfc().collect()
+}
+
+/// Where the note or attachment at `path` lives under `vault_path`
+///
+/// Each component is matched against the directory's entries by normalized name, so a
+/// file whose name is NFD on disk is read and written under that name rather than
+/// duplicated. Components that don't exist yet are used as is. Paths come from the
+/// server, so `.` and `..` components are refused rather than followed out of the vault.
+pub fn disk_path(vault_path: &Path, path: &str) -> Result<PathBuf> {
+    let mut resolved = vault_path.to_path_buf();
+    for component in path.split('/').filter(|c| !c.is_empty()) {
+        if component == "." || component == ".." {
+            bail!("Refusing path with a '{}' component: {}", component, path);
+        }
+
+        let candidate = resolved.join(component);
+        if candidate.exists() || has_one_spelling(component) {
+            resolved = candidate;
+            continue;
+        }
+
+        let on_disk = std::fs::read_dir(&resolved).ok().and_then(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .find(|e| normalize_path(&e.file_name().to_string_lossy()) == normalize_path(component))
+        });
+        resolved = match on_disk {
+            Some(entry) => entry.path(),
+            None => candidate,
+        };
+    }
+    Ok(resolved)
+}
+
+/// Is `name` written the same in NFC and NFD? Then no other file name normalizes to
+/// it, and looking for one in the directory is wasted work (true for ASCII names).
+fn has_one_spelling(name: &str) -> bool {
+    name.is_ascii() || (name.nfc().eq(name.chars()) && name.nfd().eq(name.chars()))
 }
 
 #[cfg(test)]
@@ -40,43 +61,61 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_sanitize_ellipsis() {
-        let input = "Sing the chorus low and ethereal over the intro before the current….md";
-        let expected = "Sing the chorus low and ethereal over the intro before the current....md";
-        assert_eq!(sanitize_path(input), expected);
+    fn test_unicode_preserved() {
+        let input = "folder/\u{201C}Smart\u{201D} quotes, em\u{2014}dash… Café ☕ 🎵.md";
+        assert_eq!(normalize_path(input), input);
     }
 
     #[test]
-    fn test_sanitize_smart_quotes() {
-        let input = "folder/\u{201C}Smart\u{201D} quotes \u{2018}here\u{2019}.md";
-        let expected = "folder/\"Smart\" quotes 'here'.md";
-        assert_eq!(sanitize_path(input), expected);
+    fn test_nfd_normalized_to_nfc() {
+        assert_eq!(normalize_path("Cafe\u{301}.md"), "Caf\u{e9}.md");
+        assert_eq!(normalize_path("Caf\u{e9}.md"), "Caf\u{e9}.md");
     }
 
     #[test]
-    fn test_sanitize_dashes() {
-        let input = "Note with\u{2014}em dash and\u{2013}en dash.md";
-        let expected = "Note with-em dash and-en dash.md";
-        assert_eq!(sanitize_path(input), expected);
+    fn test_backslash_separators() {
+        assert_eq!(normalize_path("Development\\Projects\\Note.md"), "Development/Projects/Note.md");
     }
 
     #[test]
-    fn test_preserve_path_separators() {
-        let input = "Development/Projects/My \u{201C}Project\u{201D}.md";
-        let expected = "Development/Projects/My \"Project\".md";
-        assert_eq!(sanitize_path(input), expected);
+    fn test_distinct_names_stay_distinct() {
+        assert_ne!(normalize_path("Café ☕.md"), normalize_path("Caf_ _.md"));
     }
 
     #[test]
-    fn test_replace_unknown_unicode() {
-        let input = "Note with emoji 🎵 and symbols ©.md";
-        let expected = "Note with emoji _ and symbols _.md";
-        assert_eq!(sanitize_path(input), expected);
+    fn test_clean_path_unchanged() {
+        let input = "Development/Clean-File_Name.md";
+        assert_eq!(normalize_path(input), input);
     }
 
     #[test]
-    fn test_clean_path_unchanged() {
-        let input = "Development/Clean-File_Name.md";
-        assert_eq!(sanitize_path(input), input);
+    fn test_disk_path_finds_decomposed_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        std::fs::create_dir_all(vault.join("Re\u{301}sume\u{301}")).unwrap();
+        std::fs::write(vault.join("Re\u{301}sume\u{301}/Cafe\u{301}.md"), "").unwrap();
+
+        let found = disk_path(vault, "R\u{e9}sum\u{e9}/Caf\u{e9}.md").unwrap();
+        assert_eq!(found, vault.join("Re\u{301}sume\u{301}/Cafe\u{301}.md"));
+
+        // Files that don't exist yet keep the given name
+        assert_eq!(disk_path(vault, "R\u{e9}sum\u{e9}/New.md").unwrap(), vault.join("Re\u{301}sume\u{301}/New.md"));
+    }
+
+    #[test]
+    fn test_disk_path_refuses_dot_components() {
+        let vault = Path::new("/vault");
+        assert!(disk_path(vault, "../etc/passwd").is_err());
+        assert!(disk_path(vault, "Notes/../../Outside.md").is_err());
+        assert!(disk_path(vault, "Notes/./Note.md").is_err());
+        assert_eq!(disk_path(vault, "Notes/..Note.md").unwrap(), vault.join("Notes/..Note.md"));
+    }
+
+    #[test]
+    fn test_one_spelling() {
+        assert!(has_one_spelling("Note.md"));
+        assert!(has_one_spelling("☕ 🎵.md"));
+        assert!(!has_one_spelling("Caf\u{e9}.md"));
+        assert!(!has_one_spelling("Cafe\u{301}.md"));
     }
 }
@@ -3,12 +3,15 @@ use std::path::Path;
 use std::time::UNIX_EPOCH;
 use walkdir::WalkDir;
 
+use crate::attachment::{hash_bytes, is_attachment_path, Attachment, MAX_ATTACHMENT_SIZE};
 use crate::folder::Folder;
-use crate::frontmatter::{extract_spacetime_id, parse_frontmatter};
+use crate::frontmatter::parse_frontmatter;
+use crate::id_index::NoteIds;
+use crate::ignore_rules::IgnoreRules;
 use crate::note::Note;
-use crate::sanitize::sanitize_path;
+use crate::sanitize::normalize_path;
 
-pub fn read_note_at(vault_path: &Path, abs_path: &Path) -> Result<Option<Note>> {
+pub fn read_note_at(vault_path: &Path, abs_path: &Path, ids: &NoteIds) -> Result<Option<Note>> {
     // Validation
     if !abs_path.exists() || !abs_path.is_file() {
         return Ok(None);
@@ -19,18 +22,14 @@ pub fn read_note_at(vault_path: &Path, abs_path: &Path) -> Result<Option<Note>>
         return Ok(None);
     }
 
-    // Relative path - sanitize to prevent URI encoding issues
-    let rel_path = sanitize_path(&abs_path
+    // Relative path - exact name, normalized to NFC
+    let rel_path = normalize_path(&abs_path
         .strip_prefix(vault_path)?
-        .to_string_lossy()
-        .to_string());
+        .to_string_lossy());
 
     // Read content
     let content = std::fs::read_to_string(abs_path)?;
 
-    // Extract UUID (READ-ONLY - do not inject here)
-    let id = extract_spacetime_id(&content).unwrap_or_default();
-
     let metadata = std::fs::metadata(abs_path)?;
 
     let size = metadata.len();
@@ -48,15 +47,22 @@ pub fn read_note_at(vault_path: &Path, abs_path: &Path) -> Result<Option<Note>>
     // Parse frontmatter
     let (body, frontmatter) = parse_frontmatter(&content);
 
+    // Look up the UUID (READ-ONLY - do not inject here)
+    let id = ids.resolve(abs_path, &rel_path, &content, &body);
+
     Ok(Some(Note::new(id, rel_path, body, frontmatter, size, created, modified)))
 }
 
 /// Scan filesystem to find a note by its UUID
-pub fn scan_for_note_by_id(vault_path: &Path, target_id: &str) -> Result<Option<Note>> {
-    let walker = WalkDir::new(vault_path).into_iter().filter_entry(|e| {
-        let name = e.file_name().to_string_lossy();
-        !name.starts_with('.') && name != "@eaDir"
-    });
+pub fn scan_for_note_by_id(
+    vault_path: &Path,
+    target_id: &str,
+    ids: &NoteIds,
+    ignore: &IgnoreRules,
+) -> Result<Option<Note>> {
+    let walker = WalkDir::new(vault_path)
+        .into_iter()
+        .filter_entry(|e| !ignore.is_ignored_path(vault_path, e.path(), e.file_type().is_dir()));
 
     for entry in walker.filter_map(|e| e.ok()) {
         let path = entry.path();
@@ -65,12 +71,10 @@ pub fn scan_for_note_by_id(vault_path: &Path, target_id: &str) -> Result<Option<
             continue;
         }
 
-        if let Ok(content) = std::fs::read_to_string(path) {
-            if let Some(id) = extract_spacetime_id(&content) {
-                if id == target_id {
-                    // Found it! Read the full note
-                    return read_note_at(vault_path, path);
-                }
+        if let Ok(Some(note)) = read_note_at(vault_path, path, ids) {
+            if note.id == target_id {
+                // Found it!
+                return Ok(Some(note));
             }
         }
     }
@@ -78,14 +82,13 @@ pub fn scan_for_note_by_id(vault_path: &Path, target_id: &str) -> Result<Option<
     Ok(None)
 }
 
-pub fn scan_notes(vault_path: &Path) -> Result<Vec<Note>> {
+pub fn scan_notes(vault_path: &Path, ids: &NoteIds, ignore: &IgnoreRules) -> Result<Vec<Note>> {
     let mut notes = Vec::new();
 
-    // Optimization: filter_entry prevents descending into hidden directories
-    let walker = WalkDir::new(vault_path).into_iter().filter_entry(|e| {
-        let name = e.file_name().to_string_lossy();
-        !name.starts_with('.') && name != "@eaDir"
-    });
+    // Optimization: filter_entry prevents descending into ignored directories
+    let walker = WalkDir::new(vault_path)
+        .into_iter()
+        .filter_entry(|e| !ignore.is_ignored_path(vault_path, e.path(), e.file_type().is_dir()));
 
     for entry in walker.filter_map(|e| e.ok()) {
         let path = entry.path();
@@ -95,9 +98,9 @@ pub fn scan_notes(vault_path: &Path) -> Result<Vec<Note>> {
             continue;
         }
 
-        // Get relative path - sanitize to prevent URI encoding issues
+        // Get relative path - exact name, normalized to NFC
         let rel_path = match path.strip_prefix(vault_path) {
-            Ok(p) => sanitize_path(&p.to_string_lossy().to_string()),
+            Ok(p) => normalize_path(&p.to_string_lossy()),
             Err(e) => {
                 tracing::warn!("Failed to get relative path for {:?}: {}", path, e);
                 continue;
@@ -113,13 +116,6 @@ pub fn scan_notes(vault_path: &Path) -> Result<Vec<Note>> {
             }
         };
 
-        // Extract UUID (READ-ONLY - do not inject here)
-        // Notes without UUIDs will be skipped during initial scan
-        let Some(id) = extract_spacetime_id(&content) else {
-            tracing::debug!("Skipping note without UUID: {}", rel_path);
-            continue;
-        };
-
         // Get metadata
         let metadata = match std::fs::metadata(path) {
             Ok(m) => m,
@@ -146,6 +142,14 @@ pub fn scan_notes(vault_path: &Path) -> Result<Vec<Note>> {
         // Parse frontmatter
         let (body, frontmatter) = parse_frontmatter(&content);
 
+        // Look up the UUID (READ-ONLY - do not inject here)
+        // Notes without UUIDs will be skipped during initial scan
+        let id = ids.resolve(path, &rel_path, &content, &body);
+        if id.is_empty() {
+            tracing::debug!("Skipping note without UUID: {}", rel_path);
+            continue;
+        }
+
         let note = Note::new(id, rel_path, body, frontmatter, size, created, modified);
         notes.push(note);
     }
@@ -153,14 +157,13 @@ pub fn scan_notes(vault_path: &Path) -> Result<Vec<Note>> {
     Ok(notes)
 }
 
-pub fn scan_folders(vault_path: &Path) -> Result<Vec<Folder>> {
+pub fn scan_folders(vault_path: &Path, ignore: &IgnoreRules) -> Result<Vec<Folder>> {
     let mut folders = Vec::new();
 
-    // Optimization: filter_entry prevents descending into hidden directories
-    let walker = WalkDir::new(vault_path).into_iter().filter_entry(|e| {
-        let name = e.file_name().to_string_lossy();
-        !name.starts_with('.') && name != "@eaDir"
-    });
+    // Optimization: filter_entry prevents descending into ignored directories
+    let walker = WalkDir::new(vault_path)
+        .into_iter()
+        .filter_entry(|e| !ignore.is_ignored_path(vault_path, e.path(), e.file_type().is_dir()));
 
     for entry in walker.filter_map(|e| e.ok()) {
         let path = entry.path();
@@ -170,11 +173,63 @@ pub fn scan_folders(vault_path: &Path) -> Result<Vec<Folder>> {
             continue;
         }
 
-        // Get relative path - sanitize to prevent URI encoding issues
-        let rel_path = sanitize_path(&path.strip_prefix(vault_path)?.to_string_lossy().to_string());
+        // Get relative path - exact name, normalized to NFC
+        let rel_path = normalize_path(&path.strip_prefix(vault_path)?.to_string_lossy());
 
         folders.push(Folder::new(rel_path));
     }
 
     Ok(folders)
 }
+
+/// Read an attachment's metadata and hash. Returns None if it's gone, not an attachment or too large.
+pub fn read_attachment_at(vault_path: &Path, abs_path: &Path) -> Result<Option<Attachment>> {
+    Ok(read_attachment_with_data_at(vault_path, abs_path)?.map(|(attachment, _)| attachment))
+}
+
+/// Like `read_attachment_at`, also returning the bytes that were hashed (for uploading)
+pub fn read_attachment_with_data_at(vault_path: &Path, abs_path: &Path) -> Result<Option<(Attachment, Vec<u8>)>> {
+    if !abs_path.is_file() || !is_attachment_path(abs_path) {
+        return Ok(None);
+    }
+
+    let metadata = std::fs::metadata(abs_path)?;
+    if metadata.len() > MAX_ATTACHMENT_SIZE {
+        tracing::warn!("Skipping attachment larger than {} bytes: {:?}", MAX_ATTACHMENT_SIZE, abs_path);
+        return Ok(None);
+    }
+
+    // Relative path - exact name, normalized to NFC
+    let rel_path = normalize_path(&abs_path
+        .strip_prefix(vault_path)?
+        .to_string_lossy());
+
+    let data = std::fs::read(abs_path)?;
+
+    let modified = metadata
+        .modified()?
+        .duration_since(UNIX_EPOCH)?
+        .as_millis() as u64;
+
+    Ok(Some((Attachment::new(rel_path, hash_bytes(&data), data.len() as u64, modified), data)))
+}
+
+/// Collect all attachments (non-markdown files) under `dir`, which must be inside the vault
+pub fn scan_attachments(vault_path: &Path, dir: &Path, ignore: &IgnoreRules) -> Result<Vec<Attachment>> {
+    let mut attachments = Vec::new();
+
+    // Optimization: filter_entry prevents descending into ignored directories
+    let walker = WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|e| !ignore.is_ignored_path(vault_path, e.path(), e.file_type().is_dir()));
+
+    for entry in walker.filter_map(|e| e.ok()) {
+        match read_attachment_at(vault_path, entry.path()) {
+            Ok(Some(attachment)) => attachments.push(attachment),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to read attachment {:?}: {}", entry.path(), e),
+        }
+    }
+
+    Ok(attachments)
+}
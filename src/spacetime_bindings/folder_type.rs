@@ -10,6 +10,7 @@ pub struct Folder {
     pub path: String,
     pub name: String,
     pub depth: u32,
+    pub parent_path: String,
 }
 
 impl __sdk::InModule for Folder {
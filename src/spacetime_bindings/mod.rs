@@ -6,56 +6,172 @@
 #![allow(unused, clippy::all)]
 use spacetimedb_sdk::__codegen::{self as __sdk, __lib, __sats, __ws};
 
+pub mod append_to_note_reducer;
+pub mod attachment_chunk_table;
+pub mod attachment_chunk_type;
+pub mod attachment_table;
+pub mod attachment_type;
 pub mod clear_all_reducer;
+pub mod conflict_table;
+pub mod conflict_type;
 pub mod create_folder_reducer;
 pub mod create_note_reducer;
+pub mod delete_attachment_reducer;
 pub mod delete_folder_reducer;
 pub mod delete_note_reducer;
+pub mod empty_trash_reducer;
+pub mod find_replace_in_note_if_version_reducer;
+pub mod find_replace_in_note_reducer;
 pub mod folder_table;
 pub mod folder_type;
+pub mod get_recent_notes_reducer;
 pub mod identity_connected_reducer;
 pub mod identity_disconnected_reducer;
+pub mod link_rewrite_table;
+pub mod link_rewrite_type;
+pub mod list_note_revisions_reducer;
 pub mod move_folder_reducer;
 pub mod move_note_reducer;
+pub mod note_link_table;
+pub mod note_link_type;
+pub mod note_revision_table;
+pub mod note_revision_type;
 pub mod note_table;
+pub mod note_tag_table;
+pub mod note_tag_type;
 pub mod note_type;
+pub mod prepend_to_note_reducer;
+pub mod rebuild_link_index_reducer;
+pub mod rebuild_tag_index_reducer;
+pub mod record_conflict_reducer;
 pub mod rename_note_reducer;
+pub mod repair_folder_hierarchy_reducer;
+pub mod repair_safe_paths_reducer;
+pub mod resolve_conflict_reducer;
+pub mod restore_from_trash_reducer;
+pub mod restore_note_revision_reducer;
+pub mod tombstone_table;
+pub mod tombstone_type;
+pub mod trash_entry_type;
+pub mod trash_table;
+pub mod update_note_content_if_version_reducer;
 pub mod update_note_content_reducer;
 pub mod update_note_path_reducer;
+pub mod upload_attachment_chunk_reducer;
+pub mod upsert_attachment_reducer;
 pub mod upsert_folder_reducer;
+pub mod upsert_note_if_version_reducer;
 pub mod upsert_note_reducer;
 
+pub use append_to_note_reducer::{
+    append_to_note, set_flags_for_append_to_note, AppendToNoteCallbackId,
+};
+pub use attachment_chunk_table::*;
+pub use attachment_chunk_type::AttachmentChunk;
+pub use attachment_table::*;
+pub use attachment_type::Attachment;
 pub use clear_all_reducer::{clear_all, set_flags_for_clear_all, ClearAllCallbackId};
+pub use conflict_table::*;
+pub use conflict_type::Conflict;
 pub use create_folder_reducer::{
     create_folder, set_flags_for_create_folder, CreateFolderCallbackId,
 };
 pub use create_note_reducer::{create_note, set_flags_for_create_note, CreateNoteCallbackId};
+pub use delete_attachment_reducer::{
+    delete_attachment, set_flags_for_delete_attachment, DeleteAttachmentCallbackId,
+};
 pub use delete_folder_reducer::{
     delete_folder, set_flags_for_delete_folder, DeleteFolderCallbackId,
 };
 pub use delete_note_reducer::{delete_note, set_flags_for_delete_note, DeleteNoteCallbackId};
+pub use empty_trash_reducer::{empty_trash, set_flags_for_empty_trash, EmptyTrashCallbackId};
+pub use find_replace_in_note_if_version_reducer::{
+    find_replace_in_note_if_version, set_flags_for_find_replace_in_note_if_version,
+    FindReplaceInNoteIfVersionCallbackId,
+};
+pub use find_replace_in_note_reducer::{
+    find_replace_in_note, set_flags_for_find_replace_in_note, FindReplaceInNoteCallbackId,
+};
 pub use folder_table::*;
 pub use folder_type::Folder;
+pub use get_recent_notes_reducer::{
+    get_recent_notes, set_flags_for_get_recent_notes, GetRecentNotesCallbackId,
+};
 pub use identity_connected_reducer::{
     identity_connected, set_flags_for_identity_connected, IdentityConnectedCallbackId,
 };
 pub use identity_disconnected_reducer::{
     identity_disconnected, set_flags_for_identity_disconnected, IdentityDisconnectedCallbackId,
 };
+pub use link_rewrite_table::*;
+pub use link_rewrite_type::LinkRewrite;
+pub use list_note_revisions_reducer::{
+    list_note_revisions, set_flags_for_list_note_revisions, ListNoteRevisionsCallbackId,
+};
 pub use move_folder_reducer::{move_folder, set_flags_for_move_folder, MoveFolderCallbackId};
 pub use move_note_reducer::{move_note, set_flags_for_move_note, MoveNoteCallbackId};
+pub use note_link_table::*;
+pub use note_link_type::NoteLink;
+pub use note_revision_table::*;
+pub use note_revision_type::NoteRevision;
 pub use note_table::*;
+pub use note_tag_table::*;
+pub use note_tag_type::NoteTag;
 pub use note_type::Note;
+pub use prepend_to_note_reducer::{
+    prepend_to_note, set_flags_for_prepend_to_note, PrependToNoteCallbackId,
+};
+pub use rebuild_link_index_reducer::{
+    rebuild_link_index, set_flags_for_rebuild_link_index, RebuildLinkIndexCallbackId,
+};
+pub use rebuild_tag_index_reducer::{
+    rebuild_tag_index, set_flags_for_rebuild_tag_index, RebuildTagIndexCallbackId,
+};
+pub use record_conflict_reducer::{
+    record_conflict, set_flags_for_record_conflict, RecordConflictCallbackId,
+};
 pub use rename_note_reducer::{rename_note, set_flags_for_rename_note, RenameNoteCallbackId};
+pub use repair_folder_hierarchy_reducer::{
+    repair_folder_hierarchy, set_flags_for_repair_folder_hierarchy, RepairFolderHierarchyCallbackId,
+};
+pub use repair_safe_paths_reducer::{
+    repair_safe_paths, set_flags_for_repair_safe_paths, RepairSafePathsCallbackId,
+};
+pub use resolve_conflict_reducer::{
+    resolve_conflict, set_flags_for_resolve_conflict, ResolveConflictCallbackId,
+};
+pub use restore_from_trash_reducer::{
+    restore_from_trash, set_flags_for_restore_from_trash, RestoreFromTrashCallbackId,
+};
+pub use restore_note_revision_reducer::{
+    restore_note_revision, set_flags_for_restore_note_revision, RestoreNoteRevisionCallbackId,
+};
+pub use tombstone_table::*;
+pub use tombstone_type::Tombstone;
+pub use trash_entry_type::TrashEntry;
+pub use trash_table::*;
+pub use update_note_content_if_version_reducer::{
+    set_flags_for_update_note_content_if_version, update_note_content_if_version,
+    UpdateNoteContentIfVersionCallbackId,
+};
 pub use update_note_content_reducer::{
     set_flags_for_update_note_content, update_note_content, UpdateNoteContentCallbackId,
 };
 pub use update_note_path_reducer::{
     set_flags_for_update_note_path, update_note_path, UpdateNotePathCallbackId,
 };
+pub use upload_attachment_chunk_reducer::{
+    set_flags_for_upload_attachment_chunk, upload_attachment_chunk, UploadAttachmentChunkCallbackId,
+};
+pub use upsert_attachment_reducer::{
+    set_flags_for_upsert_attachment, upsert_attachment, UpsertAttachmentCallbackId,
+};
 pub use upsert_folder_reducer::{
     set_flags_for_upsert_folder, upsert_folder, UpsertFolderCallbackId,
 };
+pub use upsert_note_if_version_reducer::{
+    set_flags_for_upsert_note_if_version, upsert_note_if_version, UpsertNoteIfVersionCallbackId,
+};
 pub use upsert_note_reducer::{set_flags_for_upsert_note, upsert_note, UpsertNoteCallbackId};
 
 #[derive(Clone, PartialEq, Debug)]
@@ -66,6 +182,10 @@ pub use upsert_note_reducer::{set_flags_for_upsert_note, upsert_note, UpsertNote
 /// to indicate which reducer caused the event.
 
 pub enum Reducer {
+    AppendToNote {
+        path: String,
+        content: String,
+    },
     ClearAll,
     CreateFolder {
         path: String,
@@ -84,25 +204,73 @@ pub enum Reducer {
         created_time: u64,
         modified_time: u64,
     },
+    DeleteAttachment {
+        path: String,
+    },
     DeleteFolder {
         path: String,
     },
     DeleteNote {
         id: String,
     },
+    EmptyTrash,
+    FindReplaceInNote {
+        path: String,
+        old_text: String,
+        new_text: String,
+        replace_all: bool,
+    },
+    FindReplaceInNoteIfVersion {
+        path: String,
+        old_text: String,
+        new_text: String,
+        replace_all: bool,
+        expected_version: u64,
+    },
+    GetRecentNotes {
+        limit: u32,
+    },
     IdentityConnected,
     IdentityDisconnected,
+    ListNoteRevisions {
+        note_id: String,
+    },
     MoveFolder {
         old_path: String,
         new_path: String,
+        rewrite_links: bool,
     },
     MoveNote {
         old_path: String,
         new_path: String,
+        rewrite_links: bool,
+    },
+    PrependToNote {
+        path: String,
+        content: String,
+    },
+    RebuildLinkIndex,
+    RebuildTagIndex,
+    RecordConflict {
+        note_id: String,
+        conflict_note_id: String,
+        device: String,
     },
     RenameNote {
         id: String,
         new_path: String,
+        rewrite_links: bool,
+    },
+    RepairFolderHierarchy,
+    RepairSafePaths,
+    ResolveConflict {
+        id: u64,
+    },
+    RestoreFromTrash {
+        id: u64,
+    },
+    RestoreNoteRevision {
+        revision_id: u64,
     },
     UpdateNoteContent {
         id: String,
@@ -111,9 +279,31 @@ pub enum Reducer {
         size: u64,
         modified_time: u64,
     },
+    UpdateNoteContentIfVersion {
+        id: String,
+        content: String,
+        frontmatter: String,
+        size: u64,
+        modified_time: u64,
+        expected_version: u64,
+    },
     UpdateNotePath {
         id: String,
         new_path: String,
+        rewrite_links: bool,
+    },
+    UploadAttachmentChunk {
+        hash: String,
+        chunk_index: u32,
+        data: Vec<u8>,
+    },
+    UpsertAttachment {
+        path: String,
+        hash: String,
+        mime_type: String,
+        size: u64,
+        chunk_count: u32,
+        modified_time: u64,
     },
     UpsertFolder {
         path: String,
@@ -132,6 +322,19 @@ pub enum Reducer {
         created_time: u64,
         modified_time: u64,
     },
+    UpsertNoteIfVersion {
+        id: String,
+        path: String,
+        name: String,
+        content: String,
+        folder_path: String,
+        depth: u32,
+        frontmatter: String,
+        size: u64,
+        created_time: u64,
+        modified_time: u64,
+        expected_version: u64,
+    },
 }
 
 impl __sdk::InModule for Reducer {
@@ -141,20 +344,40 @@ impl __sdk::InModule for Reducer {
 impl __sdk::Reducer for Reducer {
     fn reducer_name(&self) -> &'static str {
         match self {
+            Reducer::AppendToNote { .. } => "append_to_note",
             Reducer::ClearAll => "clear_all",
             Reducer::CreateFolder { .. } => "create_folder",
             Reducer::CreateNote { .. } => "create_note",
+            Reducer::DeleteAttachment { .. } => "delete_attachment",
             Reducer::DeleteFolder { .. } => "delete_folder",
             Reducer::DeleteNote { .. } => "delete_note",
+            Reducer::EmptyTrash => "empty_trash",
+            Reducer::FindReplaceInNote { .. } => "find_replace_in_note",
+            Reducer::FindReplaceInNoteIfVersion { .. } => "find_replace_in_note_if_version",
+            Reducer::GetRecentNotes { .. } => "get_recent_notes",
             Reducer::IdentityConnected => "identity_connected",
             Reducer::IdentityDisconnected => "identity_disconnected",
+            Reducer::ListNoteRevisions { .. } => "list_note_revisions",
             Reducer::MoveFolder { .. } => "move_folder",
             Reducer::MoveNote { .. } => "move_note",
+            Reducer::PrependToNote { .. } => "prepend_to_note",
+            Reducer::RebuildLinkIndex => "rebuild_link_index",
+            Reducer::RebuildTagIndex => "rebuild_tag_index",
+            Reducer::RecordConflict { .. } => "record_conflict",
             Reducer::RenameNote { .. } => "rename_note",
+            Reducer::RepairFolderHierarchy => "repair_folder_hierarchy",
+            Reducer::RepairSafePaths => "repair_safe_paths",
+            Reducer::ResolveConflict { .. } => "resolve_conflict",
+            Reducer::RestoreFromTrash { .. } => "restore_from_trash",
+            Reducer::RestoreNoteRevision { .. } => "restore_note_revision",
             Reducer::UpdateNoteContent { .. } => "update_note_content",
+            Reducer::UpdateNoteContentIfVersion { .. } => "update_note_content_if_version",
             Reducer::UpdateNotePath { .. } => "update_note_path",
+            Reducer::UploadAttachmentChunk { .. } => "upload_attachment_chunk",
+            Reducer::UpsertAttachment { .. } => "upsert_attachment",
             Reducer::UpsertFolder { .. } => "upsert_folder",
             Reducer::UpsertNote { .. } => "upsert_note",
+            Reducer::UpsertNoteIfVersion { .. } => "upsert_note_if_version",
             _ => unreachable!(),
         }
     }
@@ -163,6 +386,10 @@ impl TryFrom<__ws::ReducerCallInfo<__ws::BsatnFormat>> for Reducer {
     type Error = __sdk::Error;
     fn try_from(value: __ws::ReducerCallInfo<__ws::BsatnFormat>) -> __sdk::Result<Self> {
         match &value.reducer_name[..] {
+            "append_to_note" => Ok(__sdk::parse_reducer_args::<
+                append_to_note_reducer::AppendToNoteArgs,
+            >("append_to_note", &value.args)?
+            .into()),
             "clear_all" => Ok(
                 __sdk::parse_reducer_args::<clear_all_reducer::ClearAllArgs>(
                     "clear_all",
@@ -181,6 +408,10 @@ impl TryFrom<__ws::ReducerCallInfo<__ws::BsatnFormat>> for Reducer {
                 )?
                 .into(),
             ),
+            "delete_attachment" => Ok(__sdk::parse_reducer_args::<
+                delete_attachment_reducer::DeleteAttachmentArgs,
+            >("delete_attachment", &value.args)?
+            .into()),
             "delete_folder" => Ok(__sdk::parse_reducer_args::<
                 delete_folder_reducer::DeleteFolderArgs,
             >("delete_folder", &value.args)?
@@ -192,6 +423,27 @@ impl TryFrom<__ws::ReducerCallInfo<__ws::BsatnFormat>> for Reducer {
                 )?
                 .into(),
             ),
+            "empty_trash" => Ok(
+                __sdk::parse_reducer_args::<empty_trash_reducer::EmptyTrashArgs>(
+                    "empty_trash",
+                    &value.args,
+                )?
+                .into(),
+            ),
+            "find_replace_in_note" => Ok(__sdk::parse_reducer_args::<
+                find_replace_in_note_reducer::FindReplaceInNoteArgs,
+            >("find_replace_in_note", &value.args)?
+            .into()),
+            "find_replace_in_note_if_version" => {
+                Ok(__sdk::parse_reducer_args::<
+                    find_replace_in_note_if_version_reducer::FindReplaceInNoteIfVersionArgs,
+                >("find_replace_in_note_if_version", &value.args)?
+                .into())
+            }
+            "get_recent_notes" => Ok(__sdk::parse_reducer_args::<
+                get_recent_notes_reducer::GetRecentNotesArgs,
+            >("get_recent_notes", &value.args)?
+            .into()),
             "identity_connected" => Ok(__sdk::parse_reducer_args::<
                 identity_connected_reducer::IdentityConnectedArgs,
             >("identity_connected", &value.args)?
@@ -200,6 +452,10 @@ impl TryFrom<__ws::ReducerCallInfo<__ws::BsatnFormat>> for Reducer {
                 identity_disconnected_reducer::IdentityDisconnectedArgs,
             >("identity_disconnected", &value.args)?
             .into()),
+            "list_note_revisions" => Ok(__sdk::parse_reducer_args::<
+                list_note_revisions_reducer::ListNoteRevisionsArgs,
+            >("list_note_revisions", &value.args)?
+            .into()),
             "move_folder" => Ok(
                 __sdk::parse_reducer_args::<move_folder_reducer::MoveFolderArgs>(
                     "move_folder",
@@ -214,6 +470,22 @@ impl TryFrom<__ws::ReducerCallInfo<__ws::BsatnFormat>> for Reducer {
                 )?
                 .into(),
             ),
+            "prepend_to_note" => Ok(__sdk::parse_reducer_args::<
+                prepend_to_note_reducer::PrependToNoteArgs,
+            >("prepend_to_note", &value.args)?
+            .into()),
+            "rebuild_link_index" => Ok(__sdk::parse_reducer_args::<
+                rebuild_link_index_reducer::RebuildLinkIndexArgs,
+            >("rebuild_link_index", &value.args)?
+            .into()),
+            "rebuild_tag_index" => Ok(__sdk::parse_reducer_args::<
+                rebuild_tag_index_reducer::RebuildTagIndexArgs,
+            >("rebuild_tag_index", &value.args)?
+            .into()),
+            "record_conflict" => Ok(__sdk::parse_reducer_args::<
+                record_conflict_reducer::RecordConflictArgs,
+            >("record_conflict", &value.args)?
+            .into()),
             "rename_note" => Ok(
                 __sdk::parse_reducer_args::<rename_note_reducer::RenameNoteArgs>(
                     "rename_note",
@@ -221,14 +493,48 @@ impl TryFrom<__ws::ReducerCallInfo<__ws::BsatnFormat>> for Reducer {
                 )?
                 .into(),
             ),
+            "repair_folder_hierarchy" => Ok(__sdk::parse_reducer_args::<
+                repair_folder_hierarchy_reducer::RepairFolderHierarchyArgs,
+            >("repair_folder_hierarchy", &value.args)?
+            .into()),
+            "repair_safe_paths" => Ok(__sdk::parse_reducer_args::<
+                repair_safe_paths_reducer::RepairSafePathsArgs,
+            >("repair_safe_paths", &value.args)?
+            .into()),
+            "resolve_conflict" => Ok(__sdk::parse_reducer_args::<
+                resolve_conflict_reducer::ResolveConflictArgs,
+            >("resolve_conflict", &value.args)?
+            .into()),
+            "restore_from_trash" => Ok(__sdk::parse_reducer_args::<
+                restore_from_trash_reducer::RestoreFromTrashArgs,
+            >("restore_from_trash", &value.args)?
+            .into()),
+            "restore_note_revision" => Ok(__sdk::parse_reducer_args::<
+                restore_note_revision_reducer::RestoreNoteRevisionArgs,
+            >("restore_note_revision", &value.args)?
+            .into()),
             "update_note_content" => Ok(__sdk::parse_reducer_args::<
                 update_note_content_reducer::UpdateNoteContentArgs,
             >("update_note_content", &value.args)?
             .into()),
+            "update_note_content_if_version" => {
+                Ok(__sdk::parse_reducer_args::<
+                    update_note_content_if_version_reducer::UpdateNoteContentIfVersionArgs,
+                >("update_note_content_if_version", &value.args)?
+                .into())
+            }
             "update_note_path" => Ok(__sdk::parse_reducer_args::<
                 update_note_path_reducer::UpdateNotePathArgs,
             >("update_note_path", &value.args)?
             .into()),
+            "upload_attachment_chunk" => Ok(__sdk::parse_reducer_args::<
+                upload_attachment_chunk_reducer::UploadAttachmentChunkArgs,
+            >("upload_attachment_chunk", &value.args)?
+            .into()),
+            "upsert_attachment" => Ok(__sdk::parse_reducer_args::<
+                upsert_attachment_reducer::UpsertAttachmentArgs,
+            >("upsert_attachment", &value.args)?
+            .into()),
             "upsert_folder" => Ok(__sdk::parse_reducer_args::<
                 upsert_folder_reducer::UpsertFolderArgs,
             >("upsert_folder", &value.args)?
@@ -240,6 +546,10 @@ impl TryFrom<__ws::ReducerCallInfo<__ws::BsatnFormat>> for Reducer {
                 )?
                 .into(),
             ),
+            "upsert_note_if_version" => Ok(__sdk::parse_reducer_args::<
+                upsert_note_if_version_reducer::UpsertNoteIfVersionArgs,
+            >("upsert_note_if_version", &value.args)?
+            .into()),
             unknown => {
                 Err(
                     __sdk::InternalError::unknown_name("reducer", unknown, "ReducerCallInfo")
@@ -254,8 +564,17 @@ impl TryFrom<__ws::ReducerCallInfo<__ws::BsatnFormat>> for Reducer {
 #[allow(non_snake_case)]
 #[doc(hidden)]
 pub struct DbUpdate {
+    attachment: __sdk::TableUpdate<Attachment>,
+    attachment_chunk: __sdk::TableUpdate<AttachmentChunk>,
+    conflict: __sdk::TableUpdate<Conflict>,
     folder: __sdk::TableUpdate<Folder>,
+    link_rewrite: __sdk::TableUpdate<LinkRewrite>,
     note: __sdk::TableUpdate<Note>,
+    note_link: __sdk::TableUpdate<NoteLink>,
+    note_revision: __sdk::TableUpdate<NoteRevision>,
+    note_tag: __sdk::TableUpdate<NoteTag>,
+    tombstone: __sdk::TableUpdate<Tombstone>,
+    trash: __sdk::TableUpdate<TrashEntry>,
 }
 
 impl TryFrom<__ws::DatabaseUpdate<__ws::BsatnFormat>> for DbUpdate {
@@ -264,12 +583,39 @@ impl TryFrom<__ws::DatabaseUpdate<__ws::BsatnFormat>> for DbUpdate {
         let mut db_update = DbUpdate::default();
         for table_update in raw.tables {
             match &table_update.table_name[..] {
+                "attachment" => db_update
+                    .attachment
+                    .append(attachment_table::parse_table_update(table_update)?),
+                "attachment_chunk" => db_update
+                    .attachment_chunk
+                    .append(attachment_chunk_table::parse_table_update(table_update)?),
+                "conflict" => db_update
+                    .conflict
+                    .append(conflict_table::parse_table_update(table_update)?),
                 "folder" => db_update
                     .folder
                     .append(folder_table::parse_table_update(table_update)?),
+                "link_rewrite" => db_update
+                    .link_rewrite
+                    .append(link_rewrite_table::parse_table_update(table_update)?),
                 "note" => db_update
                     .note
                     .append(note_table::parse_table_update(table_update)?),
+                "note_link" => db_update
+                    .note_link
+                    .append(note_link_table::parse_table_update(table_update)?),
+                "note_revision" => db_update
+                    .note_revision
+                    .append(note_revision_table::parse_table_update(table_update)?),
+                "note_tag" => db_update
+                    .note_tag
+                    .append(note_tag_table::parse_table_update(table_update)?),
+                "tombstone" => db_update
+                    .tombstone
+                    .append(tombstone_table::parse_table_update(table_update)?),
+                "trash" => db_update
+                    .trash
+                    .append(trash_table::parse_table_update(table_update)?),
 
                 unknown => {
                     return Err(__sdk::InternalError::unknown_name(
@@ -296,12 +642,39 @@ impl __sdk::DbUpdate for DbUpdate {
     ) -> AppliedDiff<'_> {
         let mut diff = AppliedDiff::default();
 
+        diff.attachment = cache
+            .apply_diff_to_table::<Attachment>("attachment", &self.attachment)
+            .with_updates_by_pk(|row| &row.path);
+        diff.attachment_chunk = cache
+            .apply_diff_to_table::<AttachmentChunk>("attachment_chunk", &self.attachment_chunk)
+            .with_updates_by_pk(|row| &row.id);
+        diff.conflict = cache
+            .apply_diff_to_table::<Conflict>("conflict", &self.conflict)
+            .with_updates_by_pk(|row| &row.id);
         diff.folder = cache
             .apply_diff_to_table::<Folder>("folder", &self.folder)
             .with_updates_by_pk(|row| &row.path);
+        diff.link_rewrite = cache
+            .apply_diff_to_table::<LinkRewrite>("link_rewrite", &self.link_rewrite)
+            .with_updates_by_pk(|row| &row.id);
         diff.note = cache
             .apply_diff_to_table::<Note>("note", &self.note)
             .with_updates_by_pk(|row| &row.id);
+        diff.note_link = cache
+            .apply_diff_to_table::<NoteLink>("note_link", &self.note_link)
+            .with_updates_by_pk(|row| &row.id);
+        diff.note_revision = cache
+            .apply_diff_to_table::<NoteRevision>("note_revision", &self.note_revision)
+            .with_updates_by_pk(|row| &row.id);
+        diff.note_tag = cache
+            .apply_diff_to_table::<NoteTag>("note_tag", &self.note_tag)
+            .with_updates_by_pk(|row| &row.id);
+        diff.tombstone = cache
+            .apply_diff_to_table::<Tombstone>("tombstone", &self.tombstone)
+            .with_updates_by_pk(|row| &row.note_id);
+        diff.trash = cache
+            .apply_diff_to_table::<TrashEntry>("trash", &self.trash)
+            .with_updates_by_pk(|row| &row.id);
 
         diff
     }
@@ -311,8 +684,17 @@ impl __sdk::DbUpdate for DbUpdate {
 #[allow(non_snake_case)]
 #[doc(hidden)]
 pub struct AppliedDiff<'r> {
+    attachment: __sdk::TableAppliedDiff<'r, Attachment>,
+    attachment_chunk: __sdk::TableAppliedDiff<'r, AttachmentChunk>,
+    conflict: __sdk::TableAppliedDiff<'r, Conflict>,
     folder: __sdk::TableAppliedDiff<'r, Folder>,
+    link_rewrite: __sdk::TableAppliedDiff<'r, LinkRewrite>,
     note: __sdk::TableAppliedDiff<'r, Note>,
+    note_link: __sdk::TableAppliedDiff<'r, NoteLink>,
+    note_revision: __sdk::TableAppliedDiff<'r, NoteRevision>,
+    note_tag: __sdk::TableAppliedDiff<'r, NoteTag>,
+    tombstone: __sdk::TableAppliedDiff<'r, Tombstone>,
+    trash: __sdk::TableAppliedDiff<'r, TrashEntry>,
     __unused: std::marker::PhantomData<&'r ()>,
 }
 
@@ -326,8 +708,29 @@ impl<'r> __sdk::AppliedDiff<'r> for AppliedDiff<'r> {
         event: &EventContext,
         callbacks: &mut __sdk::DbCallbacks<RemoteModule>,
     ) {
+        callbacks.invoke_table_row_callbacks::<Attachment>("attachment", &self.attachment, event);
+        callbacks.invoke_table_row_callbacks::<AttachmentChunk>(
+            "attachment_chunk",
+            &self.attachment_chunk,
+            event,
+        );
+        callbacks.invoke_table_row_callbacks::<Conflict>("conflict", &self.conflict, event);
         callbacks.invoke_table_row_callbacks::<Folder>("folder", &self.folder, event);
+        callbacks.invoke_table_row_callbacks::<LinkRewrite>(
+            "link_rewrite",
+            &self.link_rewrite,
+            event,
+        );
         callbacks.invoke_table_row_callbacks::<Note>("note", &self.note, event);
+        callbacks.invoke_table_row_callbacks::<NoteLink>("note_link", &self.note_link, event);
+        callbacks.invoke_table_row_callbacks::<NoteRevision>(
+            "note_revision",
+            &self.note_revision,
+            event,
+        );
+        callbacks.invoke_table_row_callbacks::<NoteTag>("note_tag", &self.note_tag, event);
+        callbacks.invoke_table_row_callbacks::<Tombstone>("tombstone", &self.tombstone, event);
+        callbacks.invoke_table_row_callbacks::<TrashEntry>("trash", &self.trash, event);
     }
 }
 
@@ -1047,7 +1450,16 @@ impl __sdk::SpacetimeModule for RemoteModule {
     type SubscriptionHandle = SubscriptionHandle;
 
     fn register_tables(client_cache: &mut __sdk::ClientCache<Self>) {
+        attachment_table::register_table(client_cache);
+        attachment_chunk_table::register_table(client_cache);
+        conflict_table::register_table(client_cache);
         folder_table::register_table(client_cache);
+        link_rewrite_table::register_table(client_cache);
         note_table::register_table(client_cache);
+        note_link_table::register_table(client_cache);
+        note_revision_table::register_table(client_cache);
+        note_tag_table::register_table(client_cache);
+        tombstone_table::register_table(client_cache);
+        trash_table::register_table(client_cache);
     }
 }
@@ -9,6 +9,7 @@ use spacetimedb_sdk::__codegen::{self as __sdk, __lib, __sats, __ws};
 pub(super) struct MoveNoteArgs {
     pub old_path: String,
     pub new_path: String,
+    pub rewrite_links: bool,
 }
 
 impl From<MoveNoteArgs> for super::Reducer {
@@ -16,6 +17,7 @@ impl From<MoveNoteArgs> for super::Reducer {
         Self::MoveNote {
             old_path: args.old_path,
             new_path: args.new_path,
+            rewrite_links: args.rewrite_links,
         }
     }
 }
@@ -36,7 +38,12 @@ pub trait move_note {
     /// This method returns immediately, and errors only if we are unable to send the request.
     /// The reducer will run asynchronously in the future,
     ///  and its status can be observed by listening for [`Self::on_move_note`] callbacks.
-    fn move_note(&self, old_path: String, new_path: String) -> __sdk::Result<()>;
+    fn move_note(
+        &self,
+        old_path: String,
+        new_path: String,
+        rewrite_links: bool,
+    ) -> __sdk::Result<()>;
     /// Register a callback to run whenever we are notified of an invocation of the reducer `move_note`.
     ///
     /// Callbacks should inspect the [`__sdk::ReducerEvent`] contained in the [`super::ReducerEventContext`]
@@ -46,7 +53,7 @@ pub trait move_note {
     /// to cancel the callback.
     fn on_move_note(
         &self,
-        callback: impl FnMut(&super::ReducerEventContext, &String, &String) + Send + 'static,
+        callback: impl FnMut(&super::ReducerEventContext, &String, &String, &bool) + Send + 'static,
     ) -> MoveNoteCallbackId;
     /// Cancel a callback previously registered by [`Self::on_move_note`],
     /// causing it not to run in the future.
@@ -54,13 +61,24 @@ pub trait move_note {
 }
 
 impl move_note for super::RemoteReducers {
-    fn move_note(&self, old_path: String, new_path: String) -> __sdk::Result<()> {
-        self.imp
-            .call_reducer("move_note", MoveNoteArgs { old_path, new_path })
+    fn move_note(
+        &self,
+        old_path: String,
+        new_path: String,
+        rewrite_links: bool,
+    ) -> __sdk::Result<()> {
+        self.imp.call_reducer(
+            "move_note",
+            MoveNoteArgs {
+                old_path,
+                new_path,
+                rewrite_links,
+            },
+        )
     }
     fn on_move_note(
         &self,
-        mut callback: impl FnMut(&super::ReducerEventContext, &String, &String) + Send + 'static,
+        mut callback: impl FnMut(&super::ReducerEventContext, &String, &String, &bool) + Send + 'static,
     ) -> MoveNoteCallbackId {
         MoveNoteCallbackId(self.imp.on_reducer(
             "move_note",
@@ -68,7 +86,12 @@ impl move_note for super::RemoteReducers {
                 let super::ReducerEventContext {
                     event:
                         __sdk::ReducerEvent {
-                            reducer: super::Reducer::MoveNote { old_path, new_path },
+                            reducer:
+                                super::Reducer::MoveNote {
+                                    old_path,
+                                    new_path,
+                                    rewrite_links,
+                                },
                             ..
                         },
                     ..
@@ -76,7 +99,7 @@ impl move_note for super::RemoteReducers {
                 else {
                     unreachable!()
                 };
-                callback(ctx, old_path, new_path)
+                callback(ctx, old_path, new_path, rewrite_links)
             }),
         ))
     }
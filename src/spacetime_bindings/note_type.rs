@@ -17,6 +17,9 @@ pub struct Note {
     pub size: u64,
     pub created_time: u64,
     pub modified_time: u64,
+    pub db_updated_at: __sdk::Timestamp,
+    pub version: u64,
+    pub safe_path: String,
 }
 
 impl __sdk::InModule for Note {
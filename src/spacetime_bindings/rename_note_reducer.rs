@@ -9,6 +9,7 @@ use spacetimedb_sdk::__codegen::{self as __sdk, __lib, __sats, __ws};
 pub(super) struct RenameNoteArgs {
     pub id: String,
     pub new_path: String,
+    pub rewrite_links: bool,
 }
 
 impl From<RenameNoteArgs> for super::Reducer {
@@ -16,6 +17,7 @@ impl From<RenameNoteArgs> for super::Reducer {
         Self::RenameNote {
             id: args.id,
             new_path: args.new_path,
+            rewrite_links: args.rewrite_links,
         }
     }
 }
@@ -36,7 +38,7 @@ pub trait rename_note {
     /// This method returns immediately, and errors only if we are unable to send the request.
     /// The reducer will run asynchronously in the future,
     ///  and its status can be observed by listening for [`Self::on_rename_note`] callbacks.
-    fn rename_note(&self, id: String, new_path: String) -> __sdk::Result<()>;
+    fn rename_note(&self, id: String, new_path: String, rewrite_links: bool) -> __sdk::Result<()>;
     /// Register a callback to run whenever we are notified of an invocation of the reducer `rename_note`.
     ///
     /// Callbacks should inspect the [`__sdk::ReducerEvent`] contained in the [`super::ReducerEventContext`]
@@ -46,7 +48,7 @@ pub trait rename_note {
     /// to cancel the callback.
     fn on_rename_note(
         &self,
-        callback: impl FnMut(&super::ReducerEventContext, &String, &String) + Send + 'static,
+        callback: impl FnMut(&super::ReducerEventContext, &String, &String, &bool) + Send + 'static,
     ) -> RenameNoteCallbackId;
     /// Cancel a callback previously registered by [`Self::on_rename_note`],
     /// causing it not to run in the future.
@@ -54,13 +56,19 @@ pub trait rename_note {
 }
 
 impl rename_note for super::RemoteReducers {
-    fn rename_note(&self, id: String, new_path: String) -> __sdk::Result<()> {
-        self.imp
-            .call_reducer("rename_note", RenameNoteArgs { id, new_path })
+    fn rename_note(&self, id: String, new_path: String, rewrite_links: bool) -> __sdk::Result<()> {
+        self.imp.call_reducer(
+            "rename_note",
+            RenameNoteArgs {
+                id,
+                new_path,
+                rewrite_links,
+            },
+        )
     }
     fn on_rename_note(
         &self,
-        mut callback: impl FnMut(&super::ReducerEventContext, &String, &String) + Send + 'static,
+        mut callback: impl FnMut(&super::ReducerEventContext, &String, &String, &bool) + Send + 'static,
     ) -> RenameNoteCallbackId {
         RenameNoteCallbackId(self.imp.on_reducer(
             "rename_note",
@@ -68,7 +76,12 @@ impl rename_note for super::RemoteReducers {
                 let super::ReducerEventContext {
                     event:
                         __sdk::ReducerEvent {
-                            reducer: super::Reducer::RenameNote { id, new_path },
+                            reducer:
+                                super::Reducer::RenameNote {
+                                    id,
+                                    new_path,
+                                    rewrite_links,
+                                },
                             ..
                         },
                     ..
@@ -76,7 +89,7 @@ impl rename_note for super::RemoteReducers {
                 else {
                     unreachable!()
                 };
-                callback(ctx, id, new_path)
+                callback(ctx, id, new_path, rewrite_links)
             }),
         ))
     }
@@ -9,6 +9,7 @@ use spacetimedb_sdk::__codegen::{self as __sdk, __lib, __sats, __ws};
 pub(super) struct UpdateNotePathArgs {
     pub id: String,
     pub new_path: String,
+    pub rewrite_links: bool,
 }
 
 impl From<UpdateNotePathArgs> for super::Reducer {
@@ -16,6 +17,7 @@ impl From<UpdateNotePathArgs> for super::Reducer {
         Self::UpdateNotePath {
             id: args.id,
             new_path: args.new_path,
+            rewrite_links: args.rewrite_links,
         }
     }
 }
@@ -36,7 +38,12 @@ pub trait update_note_path {
     /// This method returns immediately, and errors only if we are unable to send the request.
     /// The reducer will run asynchronously in the future,
     ///  and its status can be observed by listening for [`Self::on_update_note_path`] callbacks.
-    fn update_note_path(&self, id: String, new_path: String) -> __sdk::Result<()>;
+    fn update_note_path(
+        &self,
+        id: String,
+        new_path: String,
+        rewrite_links: bool,
+    ) -> __sdk::Result<()>;
     /// Register a callback to run whenever we are notified of an invocation of the reducer `update_note_path`.
     ///
     /// Callbacks should inspect the [`__sdk::ReducerEvent`] contained in the [`super::ReducerEventContext`]
@@ -46,7 +53,7 @@ pub trait update_note_path {
     /// to cancel the callback.
     fn on_update_note_path(
         &self,
-        callback: impl FnMut(&super::ReducerEventContext, &String, &String) + Send + 'static,
+        callback: impl FnMut(&super::ReducerEventContext, &String, &String, &bool) + Send + 'static,
     ) -> UpdateNotePathCallbackId;
     /// Cancel a callback previously registered by [`Self::on_update_note_path`],
     /// causing it not to run in the future.
@@ -54,13 +61,24 @@ pub trait update_note_path {
 }
 
 impl update_note_path for super::RemoteReducers {
-    fn update_note_path(&self, id: String, new_path: String) -> __sdk::Result<()> {
-        self.imp
-            .call_reducer("update_note_path", UpdateNotePathArgs { id, new_path })
+    fn update_note_path(
+        &self,
+        id: String,
+        new_path: String,
+        rewrite_links: bool,
+    ) -> __sdk::Result<()> {
+        self.imp.call_reducer(
+            "update_note_path",
+            UpdateNotePathArgs {
+                id,
+                new_path,
+                rewrite_links,
+            },
+        )
     }
     fn on_update_note_path(
         &self,
-        mut callback: impl FnMut(&super::ReducerEventContext, &String, &String) + Send + 'static,
+        mut callback: impl FnMut(&super::ReducerEventContext, &String, &String, &bool) + Send + 'static,
     ) -> UpdateNotePathCallbackId {
         UpdateNotePathCallbackId(self.imp.on_reducer(
             "update_note_path",
@@ -68,7 +86,12 @@ impl update_note_path for super::RemoteReducers {
                 let super::ReducerEventContext {
                     event:
                         __sdk::ReducerEvent {
-                            reducer: super::Reducer::UpdateNotePath { id, new_path },
+                            reducer:
+                                super::Reducer::UpdateNotePath {
+                                    id,
+                                    new_path,
+                                    rewrite_links,
+                                },
                             ..
                         },
                     ..
@@ -76,7 +99,7 @@ impl update_note_path for super::RemoteReducers {
                 else {
                     unreachable!()
                 };
-                callback(ctx, id, new_path)
+                callback(ctx, id, new_path, rewrite_links)
             }),
         ))
     }
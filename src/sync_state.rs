@@ -0,0 +1,241 @@
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// What a note looked like the last time it was in sync with the server
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NoteState {
+    pub path: String,
+    pub hash: String,       // SHA256 of the synced content
+    #[serde(default)]
+    pub frontmatter_hash: String, // SHA256 of the synced frontmatter (empty if saved by an older version)
+    pub version: u64,       // Server version at that point
+    pub modified_time: u64, // File mtime (ms since epoch) at that point
+}
+
+/// Per-note (and per-attachment) sync state, persisted to `<vault>/.spacenotes/state`
+/// so a restart knows what was synced before the daemon went down
+pub struct SyncState {
+    file: Option<PathBuf>, // None: read-only, nothing is saved
+    notes: HashMap<String, NoteState>,
+    attachments: HashMap<String, String>, // Path -> hash of the synced bytes
+    dirty: bool,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct StateFile {
+    notes: HashMap<String, NoteState>,
+    #[serde(default)]
+    attachments: HashMap<String, String>,
+}
+
+/// Read one of the daemon's JSON files under `.spacenotes` (None if there is none yet)
+///
+/// A file that doesn't parse is moved aside to `<name>.corrupt` and also reads as None,
+/// so the daemon starts without it instead of refusing to start.
+pub fn read_state_file<T: DeserializeOwned>(file: &Path, what: &str) -> Result<Option<T>> {
+    match parse_state_file(file)? {
+        Some(Ok(contents)) => Ok(Some(contents)),
+        Some(Err(e)) => {
+            tracing::error!("Corrupt {} in {:?} ({}), starting without it", what, file, e);
+            std::fs::rename(file, file.with_extension("corrupt"))?;
+            Ok(None)
+        }
+        None => Ok(None),
+    }
+}
+
+/// Like `read_state_file`, but a corrupt file is left where it is (for read-only runs)
+pub fn peek_state_file<T: DeserializeOwned>(file: &Path, what: &str) -> Result<Option<T>> {
+    match parse_state_file(file)? {
+        Some(Ok(contents)) => Ok(Some(contents)),
+        Some(Err(e)) => {
+            tracing::warn!("Corrupt {} in {:?} ({}), ignoring it", what, file, e);
+            Ok(None)
+        }
+        None => Ok(None),
+    }
+}
+
+fn parse_state_file<T: DeserializeOwned>(file: &Path) -> Result<Option<serde_json::Result<T>>> {
+    match std::fs::read_to_string(file) {
+        Ok(json) => Ok(Some(serde_json::from_str(&json))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+impl SyncState {
+    /// Load the state file (an empty state if there is none yet)
+    ///
+    /// A corrupt file is moved aside to `state.corrupt` and the state starts empty.
+    /// That's safe: without a record of past syncs, reconciliation can't tell a file
+    /// deleted here from one that's new on the server, so it downloads it again rather
+    /// than deleting anything on the server.
+    pub fn load(vault_path: &Path) -> Result<Self> {
+        let dir = vault_path.join(".spacenotes");
+        std::fs::create_dir_all(&dir)?;
+        let file = dir.join("state");
+
+        let StateFile { notes, attachments } = read_state_file(&file, "sync state")?.unwrap_or_default();
+
+        tracing::info!("Loaded sync state for {} notes and {} attachments", notes.len(), attachments.len());
+        Ok(Self { file: Some(file), notes, attachments, dirty: false })
+    }
+
+    /// Read the state file without creating, moving or ever saving anything
+    pub fn load_read_only(vault_path: &Path) -> Result<Self> {
+        let file = vault_path.join(".spacenotes").join("state");
+        let StateFile { notes, attachments } = peek_state_file(&file, "sync state")?.unwrap_or_default();
+        Ok(Self { file: None, notes, attachments, dirty: false })
+    }
+
+    pub fn notes(&self) -> &HashMap<String, NoteState> {
+        &self.notes
+    }
+
+    pub fn get(&self, id: &str) -> Option<&NoteState> {
+        self.notes.get(id)
+    }
+
+    /// Change (or create) the entry for `id`
+    pub fn update(&mut self, id: &str, change: impl FnOnce(&mut NoteState)) {
+        change(self.notes.entry(id.to_string()).or_default());
+        self.dirty = true;
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        if self.notes.remove(id).is_some() {
+            self.dirty = true;
+        }
+    }
+
+    pub fn attachments(&self) -> &HashMap<String, String> {
+        &self.attachments
+    }
+
+    /// Record the hash of an attachment's bytes as synced
+    pub fn update_attachment(&mut self, path: &str, hash: &str) {
+        if self.attachments.get(path).is_none_or(|synced| synced != hash) {
+            self.attachments.insert(path.to_string(), hash.to_string());
+            self.dirty = true;
+        }
+    }
+
+    pub fn remove_attachment(&mut self, path: &str) {
+        if self.attachments.remove(path).is_some() {
+            self.dirty = true;
+        }
+    }
+
+    /// Write the state if anything changed since the last save
+    ///
+    /// Written to a temp file and renamed, so a crash never leaves a half-written state.
+    pub fn save(&mut self) -> Result<()> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let json = serde_json::to_string(&StateFile {
+            notes: self.notes.clone(),
+            attachments: self.attachments.clone(),
+        })?;
+        let tmp_path = file.with_extension("tmp");
+        std::fs::write(&tmp_path, json)?;
+        std::fs::rename(&tmp_path, file)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_save_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        let mut state = SyncState::load(vault).unwrap();
+        state.update("a", |s| {
+            s.path = "Note.md".to_string();
+            s.hash = "abc".to_string();
+            s.version = 3;
+        });
+        state.update_attachment("img/a.png", "def");
+        state.save().unwrap();
+        assert!(!vault.join(".spacenotes/state.tmp").exists());
+
+        let loaded = SyncState::load(vault).unwrap();
+        let note = loaded.get("a").unwrap();
+        assert_eq!((note.path.as_str(), note.hash.as_str(), note.version), ("Note.md", "abc", 3));
+        assert_eq!(loaded.attachments().get("img/a.png").map(String::as_str), Some("def"));
+    }
+
+    #[test]
+    fn test_removals_are_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        let mut state = SyncState::load(vault).unwrap();
+        state.update("a", |s| s.version = 1);
+        state.update_attachment("img/a.png", "def");
+        state.save().unwrap();
+
+        state.remove("a");
+        state.remove_attachment("img/a.png");
+        state.save().unwrap();
+
+        let loaded = SyncState::load(vault).unwrap();
+        assert!(loaded.notes().is_empty());
+        assert!(loaded.attachments().is_empty());
+    }
+
+    #[test]
+    fn test_missing_file_is_empty_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        let state = SyncState::load(vault).unwrap();
+        assert!(state.notes().is_empty());
+        assert!(state.attachments().is_empty());
+    }
+
+    #[test]
+    fn test_state_without_attachments_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        std::fs::create_dir_all(vault.join(".spacenotes")).unwrap();
+        std::fs::write(
+            vault.join(".spacenotes/state"),
+            r#"{"notes":{"a":{"path":"Note.md","hash":"abc","version":2,"modified_time":0}}}"#,
+        )
+        .unwrap();
+
+        let state = SyncState::load(vault).unwrap();
+        assert_eq!(state.get("a").unwrap().version, 2);
+        assert!(state.attachments().is_empty());
+    }
+
+    #[test]
+    fn test_corrupt_file_is_set_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        std::fs::create_dir_all(vault.join(".spacenotes")).unwrap();
+        std::fs::write(vault.join(".spacenotes/state"), "{\"notes\": {\"a\": ").unwrap();
+
+        let mut state = SyncState::load(vault).unwrap();
+        assert!(state.notes().is_empty());
+        assert!(vault.join(".spacenotes/state.corrupt").exists());
+
+        // The next save writes a fresh, readable state
+        state.update("b", |s| s.version = 1);
+        state.save().unwrap();
+        assert_eq!(SyncState::load(vault).unwrap().get("b").unwrap().version, 1);
+    }
+}
@@ -1,18 +1,102 @@
+use anyhow::Result;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 
+use crate::base_store::BaseStore;
+use crate::frontmatter::canonical_frontmatter;
+use crate::id_index::{IdMode, NoteIds};
+use crate::note::Note;
+use crate::sync_state::{NoteState, SyncState};
+
 #[derive(Clone)]
 pub struct ContentTracker {
-    // Maps UUID -> "a1b2c3..." (SHA256 hash)
+    // Maps UUID -> "a1b2c3..." (see `note_hash`)
     hashes: Arc<Mutex<HashMap<String, String>>>,
+    // Maps UUID -> last server version we saw (expected_version for our next upload)
+    versions: Arc<Mutex<HashMap<String, u64>>>,
+    // Persists the synced content itself, as the base for three-way merges
+    base: BaseStore,
+    // Persists hash/version/path/mtime of every synced note across restarts
+    state: Arc<Mutex<SyncState>>,
+    // Where note IDs come from (frontmatter, or the sidecar index)
+    ids: NoteIds,
 }
 
 impl ContentTracker {
-    pub fn new() -> Self {
+    /// Tracker backed by the vault's `.spacenotes` directory: starts from the state
+    /// saved by the previous run and keeps the merge bases there
+    pub fn load(vault_path: &Path, id_mode: IdMode) -> Result<Self> {
+        let state = SyncState::load(vault_path)?;
+        let base = BaseStore::open(vault_path)?;
+        let ids = NoteIds::load(vault_path, id_mode)?;
+        Ok(Self::from_parts(state, base, ids))
+    }
+
+    /// Tracker that reads the previous run's state but never writes to `.spacenotes`
+    /// (for a dry run)
+    pub fn load_read_only(vault_path: &Path, id_mode: IdMode) -> Result<Self> {
+        let state = SyncState::load_read_only(vault_path)?;
+        let base = BaseStore::open_read_only(vault_path);
+        let ids = NoteIds::load_read_only(vault_path, id_mode)?;
+        Ok(Self::from_parts(state, base, ids))
+    }
+
+    fn from_parts(state: SyncState, base: BaseStore, ids: NoteIds) -> Self {
+        let hashes = state
+            .notes()
+            .iter()
+            .map(|(id, s)| (id.clone(), format!("{}{}", s.hash, s.frontmatter_hash)))
+            .chain(state.attachments().iter().map(|(path, hash)| (Self::attachment_key(path), hash.clone())))
+            .collect();
+        let versions = state.notes().iter().map(|(id, s)| (id.clone(), s.version)).collect();
+
+        ids.adopt(state.notes().iter().map(|(id, s)| (id.as_str(), s.path.as_str())));
+
         Self {
-            hashes: Arc::new(Mutex::new(HashMap::new())),
+            hashes: Arc::new(Mutex::new(hashes)),
+            versions: Arc::new(Mutex::new(versions)),
+            base,
+            state: Arc::new(Mutex::new(state)),
+            ids,
+        }
+    }
+
+    pub fn ids(&self) -> &NoteIds {
+        &self.ids
+    }
+
+    /// What a note looked like when it was last in sync (possibly from a previous run)
+    pub fn synced(&self, id: &str) -> Option<NoteState> {
+        self.state.lock().unwrap().get(id).cloned()
+    }
+
+    /// Persist the sync state if it changed (call after a batch of updates)
+    pub fn flush(&self) {
+        if let Err(e) = self.state.lock().unwrap().save() {
+            tracing::error!("Failed to save sync state: {}", e);
         }
+        self.ids.flush();
+    }
+
+    /// Content of a note as of the last sync, if known
+    pub fn base(&self, id: &str) -> Option<String> {
+        self.base.load(id)
+    }
+
+    /// Record the server version of a note (from the subscription, or after our own upload)
+    pub fn set_version(&self, id: &str, version: u64) {
+        let mut map = self.versions.lock().unwrap();
+        map.insert(id.to_string(), version);
+
+        self.state.lock().unwrap().update(id, |s| s.version = version);
+    }
+
+    /// Last known server version of a note (0 if the server doesn't have it)
+    pub fn version(&self, id: &str) -> u64 {
+        let map = self.versions.lock().unwrap();
+        map.get(id).copied().unwrap_or(0)
     }
 
     /// Calculate hash of content string
@@ -22,38 +106,73 @@ impl ContentTracker {
         hex::encode(hasher.finalize())
     }
 
-    /// Update the tracker with new content (e.g., after downloading from Server)
-    pub fn update(&self, id: &str, content: &str) {
-        let hash = Self::hash(content);
+    /// Hash of frontmatter JSON, ignoring key order and the spacetime_id
+    pub fn frontmatter_hash(frontmatter: &str) -> String {
+        Self::hash(&canonical_frontmatter(frontmatter))
+    }
+
+    /// What change detection compares: the hash of the body followed by that of the
+    /// frontmatter, so editing only `tags:` or `status:` is a change too
+    pub fn note_hash(body: &str, frontmatter: &str) -> String {
+        format!("{}{}", Self::hash(body), Self::frontmatter_hash(frontmatter))
+    }
+
+    /// Update the tracker with a note that was just synced (e.g., after downloading from Server)
+    pub fn update(&self, note: &Note) {
+        self.uploading(note);
+        self.record_synced(note);
+    }
+
+    /// Remember a note we're uploading, so its echo and our own file events aren't taken
+    /// for changes. Its merge base and sync state stay as they were until the server
+    /// accepts the upload (`record_synced`): a rejected upload is still a local edit.
+    ///
+    /// The tracked path moves right away, so the file's old path isn't taken for a deletion.
+    pub fn uploading(&self, note: &Note) {
         let mut map = self.hashes.lock().unwrap();
-        map.insert(id.to_string(), hash);
+        map.insert(note.id.clone(), Self::note_hash(&note.content, &note.frontmatter));
+
+        self.state.lock().unwrap().update(&note.id, |s| s.path = note.path.clone());
+        self.ids.record(note);
     }
 
-    /// Check if content has changed WITHOUT updating the tracker (read-only)
-    pub fn has_changed(&self, id: &str, current_content: &str) -> bool {
-        let new_hash = Self::hash(current_content);
+    /// Make `note` the merge base and sync state: the server has this content now
+    pub fn record_synced(&self, note: &Note) {
+        self.base.save(&note.id, &note.content);
+
+        self.state.lock().unwrap().update(&note.id, |s| {
+            s.path = note.path.clone();
+            s.hash = Self::hash(&note.content);
+            s.frontmatter_hash = Self::frontmatter_hash(&note.frontmatter);
+            s.modified_time = note.modified_time;
+        });
+    }
+
+    /// Check if body or frontmatter has changed WITHOUT updating the tracker (read-only)
+    pub fn has_changed(&self, note: &Note) -> bool {
+        let new_hash = Self::note_hash(&note.content, &note.frontmatter);
         let map = self.hashes.lock().unwrap();
 
-        match map.get(id) {
+        match map.get(&note.id) {
             Some(old_hash) => *old_hash != new_hash,
             None => true, // New file or not tracked yet
         }
     }
 
-    /// Check if the content on disk is different from what we last synced/downloaded
+    /// Check if the body or frontmatter is different from what we last synced/downloaded
     /// Updates the tracker with the new hash as a side effect
-    pub fn is_modified(&self, id: &str, current_content: &str) -> bool {
-        let new_hash = Self::hash(current_content);
+    pub fn is_modified(&self, note: &Note) -> bool {
+        let new_hash = Self::note_hash(&note.content, &note.frontmatter);
         let mut map = self.hashes.lock().unwrap();
 
-        match map.get(id) {
+        match map.get(&note.id) {
             Some(old_hash) if *old_hash == new_hash => {
                 // Content matches what we tracked -> No change
                 false
             }
             _ => {
                 // Content differs (or new file) -> Update tracker and return true
-                map.insert(id.to_string(), new_hash);
+                map.insert(note.id.clone(), new_hash);
                 true
             }
         }
@@ -63,5 +182,159 @@ impl ContentTracker {
     pub fn remove(&self, id: &str) {
         let mut map = self.hashes.lock().unwrap();
         map.remove(id);
+        self.versions.lock().unwrap().remove(id);
+
+        self.base.remove(id);
+
+        self.state.lock().unwrap().remove(id);
+    }
+
+    /// Attachments have no UUID; they are tracked by path, with the hash of their bytes
+    fn attachment_key(path: &str) -> String {
+        format!("attachment:{}", path)
+    }
+
+    /// Remember the hash of an attachment we're uploading or about to write, so its echo
+    /// isn't taken for a change. The sync state only follows once the bytes are on the
+    /// server and on disk (`update_attachment`).
+    pub fn expect_attachment(&self, path: &str, hash: &str) {
+        let mut map = self.hashes.lock().unwrap();
+        map.insert(Self::attachment_key(path), hash.to_string());
+    }
+
+    /// Record the hash of an attachment the server accepted or we wrote to disk
+    pub fn update_attachment(&self, path: &str, hash: &str) {
+        self.expect_attachment(path, hash);
+        self.state.lock().unwrap().update_attachment(path, hash);
+    }
+
+    /// Hash of an attachment's bytes when it was last in sync (possibly from a previous run)
+    pub fn synced_attachment(&self, path: &str) -> Option<String> {
+        self.state.lock().unwrap().attachments().get(path).cloned()
+    }
+
+    /// Check if an attachment's bytes differ from what we last synced (read-only)
+    pub fn attachment_changed(&self, path: &str, hash: &str) -> bool {
+        let map = self.hashes.lock().unwrap();
+        map.get(&Self::attachment_key(path)).is_none_or(|old_hash| old_hash != hash)
+    }
+
+    pub fn remove_attachment(&self, path: &str) {
+        let mut map = self.hashes.lock().unwrap();
+        map.remove(&Self::attachment_key(path));
+
+        self.state.lock().unwrap().remove_attachment(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    fn note(frontmatter: &str) -> Note {
+        Note::new("a".to_string(), "Note.md".to_string(), "Body\n".to_string(), frontmatter.to_string(), 5, 0, 0)
+    }
+
+    #[test]
+    fn test_local_frontmatter_edit_is_a_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        let tracker = ContentTracker::load(vault, IdMode::Frontmatter).unwrap();
+        tracker.update(&note(r#"{"status":"draft","tags":["x"]}"#));
+
+        // What the watcher reads back: same body, only the YAML edited
+        assert!(tracker.has_changed(&note(r#"{"status":"done","tags":["x"]}"#)));
+        assert!(tracker.has_changed(&note(r#"{"status":"draft","tags":["x","y"]}"#)));
+        assert!(!tracker.has_changed(&note(r#"{"status":"draft","tags":["x"]}"#)));
+    }
+
+    #[test]
+    fn test_server_frontmatter_edit_is_a_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        let tracker = ContentTracker::load(vault, IdMode::Frontmatter).unwrap();
+        tracker.update(&note(r#"{"status":"draft"}"#));
+
+        // An update from another client that only touched the frontmatter
+        assert!(tracker.is_modified(&note(r#"{"status":"done"}"#)));
+        // ... and its echo once written to disk
+        assert!(!tracker.is_modified(&note(r#"{"status":"done"}"#)));
+    }
+
+    #[test]
+    fn test_upload_is_synced_only_once_accepted() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        let tracker = ContentTracker::load(vault, IdMode::Frontmatter).unwrap();
+        tracker.update(&note("{}"));
+
+        let mut edited = note("{}");
+        edited.content = "Edited\n".to_string();
+        tracker.uploading(&edited);
+
+        // Its echo isn't a change, but the merge base is still what the server had
+        assert!(!tracker.has_changed(&edited));
+        assert_eq!(tracker.base("a").as_deref(), Some("Body\n"));
+
+        tracker.record_synced(&edited);
+        assert_eq!(tracker.base("a").as_deref(), Some("Edited\n"));
+        assert_eq!(tracker.synced("a").unwrap().hash, ContentTracker::hash("Edited\n"));
+    }
+
+    #[test]
+    fn test_key_order_and_id_are_not_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        let tracker = ContentTracker::load(vault, IdMode::Frontmatter).unwrap();
+        tracker.update(&note(r#"{"a":1,"b":2}"#));
+
+        assert!(!tracker.has_changed(&note(r#"{"spacetime_id":"a","b":2,"a":1}"#)));
+    }
+
+    #[test]
+    fn test_frontmatter_hash_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        let tracker = ContentTracker::load(vault, IdMode::Frontmatter).unwrap();
+        tracker.update(&note(r#"{"status":"draft"}"#));
+        tracker.flush();
+
+        let tracker = ContentTracker::load(vault, IdMode::Frontmatter).unwrap();
+        assert!(!tracker.has_changed(&note(r#"{"status":"draft"}"#)));
+        assert!(tracker.has_changed(&note(r#"{"status":"done"}"#)));
+        assert!(!tracker.synced("a").unwrap().frontmatter_hash.is_empty());
+    }
+
+    #[test]
+    fn test_attachment_is_synced_only_once_stored() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        let tracker = ContentTracker::load(vault, IdMode::Frontmatter).unwrap();
+
+        // Uploading (or about to write): its echo is ours, but nothing is synced yet
+        tracker.expect_attachment("img/a.png", "h1");
+        assert!(!tracker.attachment_changed("img/a.png", "h1"));
+        assert_eq!(tracker.synced_attachment("img/a.png"), None);
+
+        tracker.update_attachment("img/a.png", "h1");
+        assert_eq!(tracker.synced_attachment("img/a.png").as_deref(), Some("h1"));
+    }
+
+    #[test]
+    fn test_read_only_tracker_writes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        let tracker = ContentTracker::load_read_only(vault, IdMode::Sidecar).unwrap();
+        tracker.update(&note("{}"));
+        tracker.flush();
+        assert!(!vault.join(".spacenotes").exists());
+
+        // A corrupt state file is ignored, not moved aside
+        std::fs::create_dir_all(vault.join(".spacenotes")).unwrap();
+        std::fs::write(vault.join(".spacenotes/state"), "{").unwrap();
+        let tracker = ContentTracker::load_read_only(vault, IdMode::Sidecar).unwrap();
+        assert!(tracker.synced("a").is_none());
+        assert!(vault.join(".spacenotes/state").exists());
     }
 }
@@ -1,21 +1,25 @@
 use anyhow::Result;
 use notify_debouncer_mini::{new_debouncer, notify::RecursiveMode, DebounceEventResult};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use uuid::Uuid;
+use walkdir::WalkDir;
 
+use crate::attachment::is_attachment_path;
 use crate::client::SpacetimeClient;
 use crate::folder::Folder;
-use crate::frontmatter::inject_spacetime_id;
-use crate::sanitize::sanitize_path;
-use crate::scanner::{read_note_at, scan_for_note_by_id};
+use crate::ignore_rules::SharedIgnoreRules;
+use crate::metrics::{self, Counter, METRICS};
+use crate::sanitize::{disk_path, normalize_path};
+use crate::scanner::{read_attachment_with_data_at, read_note_at, scan_for_note_by_id};
 use crate::tracker::ContentTracker;
 
 pub async fn start_watcher(
     vault_path: PathBuf,
     client: Arc<SpacetimeClient>,
     tracker: Arc<ContentTracker>,
+    ignore_rules: SharedIgnoreRules,
 ) -> Result<()> {
     let vault_path_clone = vault_path.clone();
 
@@ -24,35 +28,59 @@ pub async fn start_watcher(
         move |res: DebounceEventResult| {
             match res {
                 Ok(events) => {
+                    METRICS.set_watching(true);
+
+                    // The ignore file itself is dot-prefixed, so it never gets past the filter below
+                    if events.iter().any(|event| ignore_rules.is_rules_file(&event.path)) {
+                        ignore_rules.reload();
+                    }
+                    let ignore = ignore_rules.current();
+
                     for event in events {
                         let path = &event.path;
 
-                        // Skip hidden files/directories and Synology system folders
-                        if path.iter().any(|name| {
-                            name.to_str().map_or(false, |s| s.starts_with('.') || s == "@eaDir")
-                        }) {
+                        // Skip hidden files, Synology system folders and anything in .spacenotesignore
+                        if ignore.is_ignored_path(&vault_path_clone, path, path.is_dir()) {
                             continue;
                         }
 
                         // Handle markdown files
                         if path.extension().map_or(false, |e| e == "md") {
-                            match read_note_at(&vault_path_clone, path) {
+                            match read_note_at(&vault_path_clone, path, tracker.ids()) {
                                 Ok(Some(mut note)) => {
                                     // CHECK TRACKER (Echo Prevention)
-                                    // If we extracted an ID, and the content hasn't changed, STOP.
+                                    // If we extracted an ID, and neither body nor frontmatter changed, STOP.
                                     if !note.id.is_empty() {
-                                        let content_hash = ContentTracker::hash(&note.content);
-                                        let has_changed = tracker.has_changed(&note.id, &note.content);
+                                        let content_hash = ContentTracker::note_hash(&note.content, &note.frontmatter);
+                                        let has_changed = tracker.has_changed(&note);
                                         tracing::info!(
                                             "Watcher echo check: path={}, id={}, content_len={}, hash={}, has_changed={}",
                                             note.path, note.id, note.content.len(), &content_hash[..16], has_changed
                                         );
                                         if !has_changed {
+                                            metrics::count(Counter::EchoSkips);
                                             tracing::info!("Watcher ignoring echo: {}", note.path);
                                             continue;
                                         }
                                     }
 
+                                    // Sidecar mode: files are never rewritten. A path the DB already
+                                    // knows (e.g. after losing the index) keeps that note's UUID.
+                                    if note.id.is_empty() && tracker.ids().is_sidecar() {
+                                        let new_id = match client.get_note_by_path(&note.path) {
+                                            Some(existing) => {
+                                                tracker.set_version(&existing.id, existing.version);
+                                                existing.id
+                                            }
+                                            None => Uuid::new_v4().to_string(),
+                                        };
+                                        tracing::info!("Assigning UUID {} to {}", new_id, note.path);
+                                        if let Err(e) = tracker.ids().assign(path, &mut note, new_id) {
+                                            tracing::error!("Failed to assign UUID to {}: {}", note.path, e);
+                                            continue;
+                                        }
+                                    }
+
                                     // Check if note has a UUID
                                     if note.id.is_empty() {
                                         // SAFETY CHECK: Does the DB already know about this file?
@@ -76,17 +104,14 @@ pub async fn start_watcher(
                                                 continue;
                                             }
 
-                                            // New file without UUID - inject one
+                                            // New file without UUID - inject one (updates the note object too)
                                             let new_id = Uuid::new_v4().to_string();
                                             tracing::info!("Injecting UUID {} into {}", new_id, note.path);
 
-                                            let new_content = inject_spacetime_id(&raw_content, &new_id);
-                                            if let Err(e) = std::fs::write(path, &new_content) {
+                                            if let Err(e) = tracker.ids().assign(path, &mut note, new_id) {
                                                 tracing::error!("Failed to inject UUID into {}: {}", note.path, e);
                                                 continue;
                                             }
-                                            // Update note object with new ID
-                                            note.id = new_id;
                                         } else {
                                             tracing::error!("Failed to read {} for UUID injection", note.path);
                                             continue;
@@ -94,9 +119,10 @@ pub async fn start_watcher(
                                     }
 
                                     // UPSERT (Only if tracker says content changed)
-                                    if tracker.is_modified(&note.id, &note.content) {
-                                        client.upsert_note(&note);
-                                        tracker.update(&note.id, &note.content);
+                                    if tracker.is_modified(&note) {
+                                        // The tracked version moves once the server confirms the upload
+                                        client.upsert_note(&note, tracker.version(&note.id));
+                                        tracker.uploading(&note);
                                         tracing::info!("Synced: {} (ID: {})", note.name, note.id);
                                     } else {
                                         tracing::debug!("Skipping unchanged: {} (ID: {})", note.path, note.id);
@@ -105,11 +131,17 @@ pub async fn start_watcher(
                                 Ok(None) => {
                                     // File was deleted - look up ID from client cache
                                     if let Ok(rel) = path.strip_prefix(&vault_path_clone) {
-                                        let rel_path = sanitize_path(&rel.to_string_lossy().to_string());
+                                        let rel_path = normalize_path(&rel.to_string_lossy());
 
                                         // Find the note in the client cache by path
                                         let notes = client.get_all_notes();
                                         if let Some(note) = notes.iter().find(|n| n.path == rel_path) {
+                                            // Already seen at its new path in this batch: it was renamed, not deleted
+                                            if tracker.synced(&note.id).is_some_and(|s| s.path != rel_path) {
+                                                tracing::debug!("Note moved away from {} (ID: {})", rel_path, note.id);
+                                                continue;
+                                            }
+
                                             client.delete_note(&note.id);
                                             tracker.remove(&note.id);
                                             tracing::info!("Deleted note: {} (ID: {})", rel_path, note.id);
@@ -126,17 +158,32 @@ pub async fn start_watcher(
                         // Handle directories (check is_dir first, then handle deleted dirs)
                         else if path.is_dir() {
                             // Directory exists - created or modified
-                            if let Ok(rel) = path.strip_prefix(&vault_path_clone) {
-                                let rel_path = sanitize_path(&rel.to_string_lossy().to_string());
-                                let folder = Folder::new(rel_path.clone());
-                                client.upsert_folder(&folder);
-                                tracing::info!("Synced folder: {}", rel_path);
+                            let Ok(rel) = path.strip_prefix(&vault_path_clone) else {
+                                continue;
+                            };
+                            let rel_path = normalize_path(&rel.to_string_lossy());
+                            let is_new = !rel_path.is_empty() && !client.has_folder(&rel_path);
+                            let folder = Folder::new(rel_path.clone());
+                            client.upsert_folder(&folder);
+                            tracing::info!("Synced folder: {}", rel_path);
+
+                            // A folder created or moved into the vault (or renamed) doesn't report its
+                            // files; a folder we already know reports each changed file on its own
+                            if is_new {
+                                let files = WalkDir::new(path)
+                                    .into_iter()
+                                    .filter_entry(|e| !ignore.is_ignored_path(&vault_path_clone, e.path(), e.file_type().is_dir()))
+                                    .filter_map(|e| e.ok())
+                                    .filter(|e| e.file_type().is_file() && is_attachment_path(e.path()));
+                                for file in files {
+                                    sync_attachment(&vault_path_clone, file.path(), &client, &tracker);
+                                }
                             }
                         }
                         // Handle deleted directories (no extension and doesn't exist)
                         else if path.extension().is_none() && !path.exists() {
                             if let Ok(rel) = path.strip_prefix(&vault_path_clone) {
-                                let old_folder_path = sanitize_path(&rel.to_string_lossy().to_string());
+                                let old_folder_path = normalize_path(&rel.to_string_lossy());
 
                                 // Get all notes that were in this folder from DB
                                 let notes_in_folder: Vec<_> = client.get_all_notes()
@@ -146,17 +193,16 @@ pub async fn start_watcher(
 
                                 // Check if notes still exist on disk (indicates folder rename)
                                 for note in &notes_in_folder {
-                                    let old_path = vault_path_clone.join(&note.path);
-                                    if !old_path.exists() {
+                                    if !disk_path(&vault_path_clone, &note.path).is_ok_and(|p| p.exists()) {
                                         // Note missing at old path - try to find by UUID
-                                        match scan_for_note_by_id(&vault_path_clone, &note.id) {
+                                        match scan_for_note_by_id(&vault_path_clone, &note.id, tracker.ids(), &ignore) {
                                             Ok(Some(mut new_note)) => {
                                                 // Found it at new location! Update path in DB
                                                 if new_note.id.is_empty() {
                                                     new_note.id = note.id.clone();
                                                 }
-                                                client.upsert_note(&new_note);
-                                                tracker.update(&new_note.id, &new_note.content);
+                                                client.upsert_note(&new_note, note.version);
+                                                tracker.uploading(&new_note);
                                                 tracing::info!("Updated note path: {} -> {}", note.path, new_note.path);
                                             }
                                             Ok(None) => {
@@ -176,9 +222,19 @@ pub async fn start_watcher(
                                 tracing::info!("Deleted folder: {}", old_folder_path);
                             }
                         }
+                        // Handle attachments (images, PDFs, ...)
+                        else if is_attachment_path(path) {
+                            sync_attachment(&vault_path_clone, path, &client, &tracker);
+                        }
                     }
+
+                    tracker.flush();
+                }
+                Err(e) => {
+                    // Events were lost (e.g. inotify overflow): unhealthy until the next batch
+                    METRICS.set_watching(false);
+                    tracing::error!("Watch error: {:?}", e);
                 }
-                Err(e) => tracing::error!("Watch error: {:?}", e),
             }
         },
     )?;
@@ -187,6 +243,7 @@ pub async fn start_watcher(
         .watcher()
         .watch(&vault_path, RecursiveMode::Recursive)?;
 
+    METRICS.set_watching(true);
     tracing::info!("Watcher started on {:?}", vault_path);
 
     // Keep alive indefinitely
@@ -194,3 +251,38 @@ pub async fn start_watcher(
 
     Ok(())
 }
+
+/// Upload a created/changed attachment, or delete it on the server if it's gone from disk
+fn sync_attachment(vault_path: &Path, path: &Path, client: &SpacetimeClient, tracker: &ContentTracker) {
+    match read_attachment_with_data_at(vault_path, path) {
+        Ok(Some((attachment, data))) => {
+            // CHECK TRACKER (Echo Prevention)
+            if !tracker.attachment_changed(&attachment.path, &attachment.hash) {
+                metrics::count(Counter::EchoSkips);
+                tracing::debug!("Watcher ignoring attachment echo: {}", attachment.path);
+                return;
+            }
+
+            tracker.expect_attachment(&attachment.path, &attachment.hash);
+            client.upload_attachment(&attachment, path, data);
+            tracing::info!("Synced attachment: {} ({} bytes)", attachment.path, attachment.size);
+        }
+        Ok(None) => {
+            if path.exists() {
+                return; // Present but not synced (e.g. too large)
+            }
+
+            if let Ok(rel) = path.strip_prefix(vault_path) {
+                let rel_path = normalize_path(&rel.to_string_lossy());
+                if client.get_attachment_by_path(&rel_path).is_some() {
+                    client.delete_attachment(&rel_path);
+                    tracker.remove_attachment(&rel_path);
+                    tracing::info!("Deleted attachment: {}", rel_path);
+                }
+            }
+        }
+        Err(e) => {
+            tracing::error!("Error processing attachment {:?}: {}", path, e);
+        }
+    }
+}
@@ -1,10 +1,14 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use std::path::Path;
 
+use crate::attachment::Attachment;
+use crate::frontmatter::{extract_spacetime_id, render_note_file};
+use crate::id_index::NoteIds;
 use crate::note::Note;
+use crate::sanitize::disk_path;
 
-pub fn write_note_to_disk(vault_root: &Path, note: &Note) -> Result<()> {
-    let file_path = vault_root.join(&note.path);
+pub fn write_note_to_disk(vault_root: &Path, note: &Note, ids: &NoteIds) -> Result<()> {
+    let file_path = disk_path(vault_root, &note.path)?;
 
     // Security check (prevent writing outside vault)
     if !file_path.starts_with(vault_root) {
@@ -17,31 +21,13 @@ pub fn write_note_to_disk(vault_root: &Path, note: &Note) -> Result<()> {
     }
 
     // Reconstruct YAML Frontmatter + Body
-    // Always include spacetime_id in frontmatter
-    let content = {
-        // 1. Prepare JSON Object
-        let mut json_val: serde_json::Value = if !note.frontmatter.is_empty() && note.frontmatter != "{}" {
-            serde_json::from_str(&note.frontmatter).unwrap_or(serde_json::Value::Object(Default::default()))
-        } else {
-            serde_json::Value::Object(Default::default())
-        };
-
-        // 2. Force Insert UUID
-        if let serde_json::Value::Object(ref mut map) = json_val {
-            map.insert("spacetime_id".to_string(), serde_json::Value::String(note.id.clone()));
-        }
-
-        // 3. Serialize & Sanitize
-        let yaml_str = serde_yaml::to_string(&json_val)
-            .context("Failed to serialize frontmatter")?;
-
-        // Strip existing markers to control the sandwich manually
-        let clean_yaml = yaml_str.trim_start_matches("---\n").trim();
-
-        // 4. Strict Formatting (The Fix)
-        // Ensures exactly one newline before and after delimiters
-        format!("---\n{}\n---\n\n{}", clean_yaml, note.content)
-    };
+    // Include spacetime_id in frontmatter (in sidecar mode only if the file already has it);
+    // the frontmatter already on disk is edited in place, so keys that didn't change keep
+    // their exact formatting
+    let existing = std::fs::read_to_string(&file_path).ok();
+    let embed_id = !ids.is_sidecar() || existing.as_deref().and_then(extract_spacetime_id).is_some();
+    let id = embed_id.then_some(note.id.as_str());
+    let content = render_note_file(existing.as_deref(), &note.frontmatter, id, &note.content);
 
     // ATOMIC WRITE (Write to tmp -> Rename)
     // This guarantees we never have a half-written file if the app crashes
@@ -58,5 +44,37 @@ pub fn write_note_to_disk(vault_root: &Path, note: &Note) -> Result<()> {
     );
     let _ = filetime::set_file_mtime(&file_path, mtime);
 
+    // The rename gave the file a new inode
+    ids.record(note);
+
+    Ok(())
+}
+
+pub fn write_attachment_to_disk(vault_root: &Path, attachment: &Attachment, data: &[u8]) -> Result<()> {
+    let file_path = disk_path(vault_root, &attachment.path)?;
+
+    // Security check (prevent writing outside vault)
+    if !file_path.starts_with(vault_root) {
+        anyhow::bail!("Security violation: Path {:?} is outside vault", attachment.path);
+    }
+
+    // Ensure parent folder exists
+    if let Some(parent) = file_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    // ATOMIC WRITE (Write to hidden tmp -> Rename)
+    // Hidden, so the watcher doesn't pick up the half-written file as an attachment
+    let tmp_path = file_path.with_file_name(format!(".{}.tmp", attachment.name));
+    std::fs::write(&tmp_path, data)?;
+    std::fs::rename(&tmp_path, &file_path)?;
+
+    // Sync Timestamp (see write_note_to_disk)
+    let mtime = filetime::FileTime::from_unix_time(
+        (attachment.modified_time / 1000) as i64,
+        ((attachment.modified_time % 1000) * 1_000_000) as u32,
+    );
+    let _ = filetime::set_file_mtime(&file_path, mtime);
+
     Ok(())
 }